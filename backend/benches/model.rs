@@ -0,0 +1,24 @@
+use backend::model::{init_dev_db, ListElementMAC, ListQuery, ModelManager};
+use criterion::{criterion_group, criterion_main, Criterion};
+
+// seeded like the dev db, rebuilt on every run
+const DB_PATH: &str = "sql/bench.tldb";
+
+// the same reads through the pool cached per path and through one shared pool
+fn cached_vs_shared(c: &mut Criterion) {
+    init_dev_db(Some(DB_PATH)).unwrap();
+    let mm = ModelManager::new(DB_PATH).unwrap();
+
+    let mut group = c.benchmark_group("get");
+    group.bench_function("cached by path", |b| b.iter(|| ListElementMAC::get_from_path(DB_PATH, 10).unwrap()));
+    group.bench_function("shared pool", |b| b.iter(|| ListElementMAC::get(&mm, 10).unwrap()));
+    group.finish();
+
+    let mut group = c.benchmark_group("get_all");
+    group.bench_function("cached by path", |b| b.iter(|| ListElementMAC::get_all_from_path(DB_PATH, &ListQuery::default()).unwrap()));
+    group.bench_function("shared pool", |b| b.iter(|| ListElementMAC::get_all(&mm, &ListQuery::default()).unwrap()));
+    group.finish();
+}
+
+criterion_group!(benches, cached_vs_shared);
+criterion_main!(benches);
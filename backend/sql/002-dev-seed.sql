@@ -1,4 +0,0 @@
--- DEV SEED
-INSERT INTO list (id, title, tags, notes) VALUES (10, 'List Element 10', 'foo','Lipsum Orem');
-INSERT INTO list (id, title, tags, notes, "status") VALUES (11, 'List Element 11', 'foo bar baz','Lipsum Orem', 'Closed');
-INSERT INTO list (title, tags, notes, "status") VALUES ('List Element', 'foo bar','Lipsum Orem', 'Archived');
\ No newline at end of file
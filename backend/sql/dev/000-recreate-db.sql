@@ -0,0 +1,14 @@
+-- DEV ONLY -- Brute Force recrete DB for live dev and unit testing
+BEGIN TRANSACTION;
+PRAGMA FOREIGN_KEYS = OFF;
+DROP TABLE IF EXISTS list_history;
+DROP TABLE IF EXISTS undo_stack;
+DROP TABLE IF EXISTS list_fts;
+DROP TABLE IF EXISTS list_tag;
+DROP TABLE IF EXISTS tag;
+DROP TABLE IF EXISTS list;
+DROP TABLE IF EXISTS list_element_status;
+DROP TABLE IF EXISTS schema_version;
+DROP TABLE IF EXISTS __diesel_schema_migrations;
+PRAGMA FOREIGN_KEYS = ON;
+COMMIT;
@@ -0,0 +1,10 @@
+-- DEV SEED
+INSERT INTO list (id, title, notes, "status", episodes_watched, episodes_total, score) VALUES (10, 'List Element 10', 'Lipsum Orem', 'Watching', 11, 12, 80);
+INSERT INTO list (id, title, notes, "status", episodes_watched, episodes_total, score) VALUES (11, 'List Element 11', 'Lipsum Orem', 'Completed', 24, 24, 90);
+INSERT INTO list (title, notes, "status", score) VALUES ('List Element', 'Lipsum Orem', 'Dropped', 40);
+
+-- tags, list.tags is filled in by the list_tag triggers
+INSERT INTO tag (id, name) VALUES (1, 'foo'), (2, 'bar'), (3, 'baz');
+INSERT INTO list_tag (list_id, tag_id) VALUES (10, 1), (11, 1), (11, 2), (11, 3);
+INSERT INTO list_tag (list_id, tag_id) SELECT id, 1 FROM list WHERE title = 'List Element';
+INSERT INTO list_tag (list_id, tag_id) SELECT id, 2 FROM list WHERE title = 'List Element';
@@ -0,0 +1,4 @@
+-- TEST FIXTURE -- titles and notes for full text search, on top of the seed
+INSERT INTO list (title, notes) VALUES ('Cowboy Bebop', 'Space western with jazz');
+INSERT INTO list (title, notes) VALUES ('Space Dandy', 'A dandy guy in space');
+INSERT INTO list (title, notes) VALUES ('Samurai Champloo', 'Hip hop samurai from the Cowboy Bebop director');
@@ -1,13 +1,12 @@
-
 -- ListElementStatus Enum
 CREATE TABLE IF NOT EXISTS list_element_status (
     status TEXT NOT NULL PRIMARY KEY,
     id INTEGER
 );
 
-INSERT INTO list_element_status (status, id) VALUES ('Open', 1);
-INSERT INTO list_element_status (status, id) VALUES ('Closed', 2);
-INSERT INTO list_element_status (status, id) VALUES ('Archived', 3);
+INSERT OR IGNORE INTO list_element_status (status, id) VALUES ('Open', 1);
+INSERT OR IGNORE INTO list_element_status (status, id) VALUES ('Closed', 2);
+INSERT OR IGNORE INTO list_element_status (status, id) VALUES ('Archived', 3);
 
 -- List 
 CREATE TABLE IF NOT EXISTS list (
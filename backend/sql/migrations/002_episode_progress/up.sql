@@ -0,0 +1,3 @@
+-- Episode progress
+ALTER TABLE list ADD COLUMN episodes_watched INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE list ADD COLUMN episodes_total INTEGER;    --NULL while the show is still airing or unknown
@@ -0,0 +1,62 @@
+-- Tags
+CREATE TABLE IF NOT EXISTS tag (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    name TEXT NOT NULL UNIQUE COLLATE NOCASE
+);
+
+-- List <-> Tag, rowid keeps the order tags were added in
+CREATE TABLE IF NOT EXISTS list_tag (
+    list_id INTEGER NOT NULL,
+    tag_id INTEGER NOT NULL,
+    PRIMARY KEY (list_id, tag_id),
+    FOREIGN KEY(list_id) REFERENCES list(id) ON DELETE CASCADE,
+    FOREIGN KEY(tag_id) REFERENCES tag(id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS list_tag_tag_id ON list_tag(tag_id);
+
+-- Split the existing whitespace separated tag strings
+CREATE TEMP TABLE tag_split AS
+WITH RECURSIVE split(list_id, pos, name, rest) AS (
+    SELECT id, 0, '', trim(tags) || ' ' FROM list WHERE tags IS NOT NULL
+    UNION ALL
+    SELECT list_id, pos + 1, substr(rest, 1, instr(rest, ' ') - 1), ltrim(substr(rest, instr(rest, ' ') + 1))
+    FROM split WHERE rest <> ''
+)
+SELECT list_id, pos, name FROM split WHERE name <> '';
+
+INSERT OR IGNORE INTO tag (name) SELECT name FROM tag_split ORDER BY list_id, pos;
+
+INSERT OR IGNORE INTO list_tag (list_id, tag_id)
+SELECT s.list_id, t.id FROM tag_split s JOIN tag t ON t.name = s.name ORDER BY s.list_id, s.pos;
+
+DROP TABLE tag_split;
+
+-- list.tags is now a display copy of list_tag, kept in sync below
+CREATE TRIGGER IF NOT EXISTS list_tag_insert AFTER INSERT ON list_tag
+BEGIN
+    UPDATE list SET tags = (
+        SELECT group_concat(name, ' ') FROM (
+            SELECT t.name FROM list_tag lt JOIN tag t ON t.id = lt.tag_id
+            WHERE lt.list_id = NEW.list_id ORDER BY lt.rowid
+        )
+    ) WHERE id = NEW.list_id;
+END;
+
+CREATE TRIGGER IF NOT EXISTS list_tag_delete AFTER DELETE ON list_tag
+BEGIN
+    UPDATE list SET tags = (
+        SELECT group_concat(name, ' ') FROM (
+            SELECT t.name FROM list_tag lt JOIN tag t ON t.id = lt.tag_id
+            WHERE lt.list_id = OLD.list_id ORDER BY lt.rowid
+        )
+    ) WHERE id = OLD.list_id;
+
+    -- drop tags nothing uses anymore
+    DELETE FROM tag WHERE id = OLD.tag_id AND NOT EXISTS (SELECT 1 FROM list_tag WHERE tag_id = OLD.tag_id);
+END;
+
+CREATE TRIGGER IF NOT EXISTS list_delete_tags AFTER DELETE ON list
+BEGIN
+    DELETE FROM list_tag WHERE list_id = OLD.id;
+END;
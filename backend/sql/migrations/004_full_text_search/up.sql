@@ -0,0 +1,27 @@
+-- Full text search over titles and notes, contents live in list
+CREATE VIRTUAL TABLE IF NOT EXISTS list_fts USING fts5(
+    title,
+    notes,
+    content = 'list',
+    content_rowid = 'id',
+    tokenize = 'unicode61 remove_diacritics 2'
+);
+
+CREATE TRIGGER IF NOT EXISTS list_fts_insert AFTER INSERT ON list
+BEGIN
+    INSERT INTO list_fts (rowid, title, notes) VALUES (NEW.id, NEW.title, NEW.notes);
+END;
+
+CREATE TRIGGER IF NOT EXISTS list_fts_delete AFTER DELETE ON list
+BEGIN
+    INSERT INTO list_fts (list_fts, rowid, title, notes) VALUES ('delete', OLD.id, OLD.title, OLD.notes);
+END;
+
+CREATE TRIGGER IF NOT EXISTS list_fts_update AFTER UPDATE OF title, notes ON list
+BEGIN
+    INSERT INTO list_fts (list_fts, rowid, title, notes) VALUES ('delete', OLD.id, OLD.title, OLD.notes);
+    INSERT INTO list_fts (rowid, title, notes) VALUES (NEW.id, NEW.title, NEW.notes);
+END;
+
+-- index what is already there
+INSERT INTO list_fts (list_fts) VALUES ('rebuild');
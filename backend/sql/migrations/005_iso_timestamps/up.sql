@@ -0,0 +1,10 @@
+-- Timestamps are UTC ISO-8601, e.g. 2022-10-18T20:15:00Z
+UPDATE list SET ctime = strftime('%Y-%m-%dT%H:%M:%SZ', ctime);
+UPDATE list SET mtime = strftime('%Y-%m-%dT%H:%M:%SZ', mtime) WHERE mtime IS NOT NULL;
+
+-- the column default is still datetime('now'), normalize whatever got inserted
+CREATE TRIGGER IF NOT EXISTS list_ctime_iso AFTER INSERT ON list
+WHEN NEW.ctime NOT LIKE '____-__-__T__:__:__Z'
+BEGIN
+    UPDATE list SET ctime = strftime('%Y-%m-%dT%H:%M:%SZ', NEW.ctime) WHERE id = NEW.id;
+END;
@@ -0,0 +1,63 @@
+-- Anime list statuses, Open/Closed/Archived become Watching/Completed/Dropped
+DELETE FROM list_element_status;
+
+INSERT INTO list_element_status (status, id) VALUES ('Watching', 1);
+INSERT INTO list_element_status (status, id) VALUES ('Completed', 2);
+INSERT INTO list_element_status (status, id) VALUES ('On Hold', 3);
+INSERT INTO list_element_status (status, id) VALUES ('Dropped', 4);
+INSERT INTO list_element_status (status, id) VALUES ('Plan to Watch', 5);
+
+-- SQLite can't change a column default in place, rebuild list with the new status and ctime defaults
+CREATE TABLE list_new (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    title TEXT NOT NULL,
+    notes TEXT,
+    tags TEXT,
+    ctime TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%SZ', 'now')),
+    mtime TEXT,    --modified time 
+    status TEXT NOT NULL DEFAULT 'Plan to Watch',
+    episodes_watched INTEGER NOT NULL DEFAULT 0,
+    episodes_total INTEGER,    --NULL while the show is still airing or unknown
+    FOREIGN KEY(status) REFERENCES list_element_status(status)
+);
+
+INSERT INTO list_new (id, title, notes, tags, ctime, mtime, status, episodes_watched, episodes_total)
+SELECT id, title, notes, tags, ctime, mtime,
+    CASE status
+        WHEN 'Open' THEN 'Watching'
+        WHEN 'Closed' THEN 'Completed'
+        WHEN 'Archived' THEN 'Dropped'
+        ELSE status
+    END,
+    episodes_watched, episodes_total
+FROM list;
+
+-- keep ids of deleted rows from being handed out again
+UPDATE sqlite_sequence SET seq = max(seq, (SELECT seq FROM sqlite_sequence WHERE name = 'list')) WHERE name = 'list_new';
+
+-- dropping list takes its triggers with it, the list_tag ones only need the name back
+DROP TABLE list;
+PRAGMA legacy_alter_table = ON;
+ALTER TABLE list_new RENAME TO list;
+PRAGMA legacy_alter_table = OFF;
+
+CREATE TRIGGER IF NOT EXISTS list_delete_tags AFTER DELETE ON list
+BEGIN
+    DELETE FROM list_tag WHERE list_id = OLD.id;
+END;
+
+CREATE TRIGGER IF NOT EXISTS list_fts_insert AFTER INSERT ON list
+BEGIN
+    INSERT INTO list_fts (rowid, title, notes) VALUES (NEW.id, NEW.title, NEW.notes);
+END;
+
+CREATE TRIGGER IF NOT EXISTS list_fts_delete AFTER DELETE ON list
+BEGIN
+    INSERT INTO list_fts (list_fts, rowid, title, notes) VALUES ('delete', OLD.id, OLD.title, OLD.notes);
+END;
+
+CREATE TRIGGER IF NOT EXISTS list_fts_update AFTER UPDATE OF title, notes ON list
+BEGIN
+    INSERT INTO list_fts (list_fts, rowid, title, notes) VALUES ('delete', OLD.id, OLD.title, OLD.notes);
+    INSERT INTO list_fts (rowid, title, notes) VALUES (NEW.id, NEW.title, NEW.notes);
+END;
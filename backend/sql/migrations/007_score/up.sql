@@ -0,0 +1,2 @@
+-- Personal score, stored on a 0-100 scale whatever scale it is shown in. NULL is unscored
+ALTER TABLE list ADD COLUMN score INTEGER CHECK (score BETWEEN 0 AND 100);
@@ -0,0 +1,5 @@
+-- Fields carried over from list site exports. Dates are YYYY-MM-DD, NULL is unknown
+ALTER TABLE list ADD COLUMN started_on TEXT;
+ALTER TABLE list ADD COLUMN finished_on TEXT;
+-- MyAnimeList anime id, what MAL matches on when importing our exports
+ALTER TABLE list ADD COLUMN mal_id INTEGER;
@@ -0,0 +1,12 @@
+-- Every create, update and delete of a list element. No foreign key, the history outlives the element.
+CREATE TABLE list_history (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    list_id INTEGER NOT NULL,
+    action TEXT NOT NULL CHECK (action IN ('Created', 'Updated', 'Deleted')),
+    -- JSON array of {"field", "before", "after"}, before is null on create and after on delete
+    changes TEXT NOT NULL,
+    changed_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%SZ', 'now')),
+    -- what made the change: gui, cli, tui, api or backend
+    source TEXT NOT NULL
+);
+CREATE INDEX list_history_list_id ON list_history (list_id, id);
@@ -0,0 +1,8 @@
+-- Recent reversible edits, oldest first. Undone rows are the redo side and go away on the next edit.
+CREATE TABLE undo_stack (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    -- JSON of the operation with the element before and after it
+    operation TEXT NOT NULL,
+    undone BOOLEAN NOT NULL DEFAULT 0,
+    created_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%SZ', 'now'))
+);
@@ -0,0 +1,17 @@
+-- Deleting moves an element to the trash, NULL is not trashed. Purged for good after the configured days.
+ALTER TABLE list ADD COLUMN deleted_at TEXT;
+CREATE INDEX list_deleted_at ON list (deleted_at);
+
+-- Trashing and restoring are actions of their own in the history
+CREATE TABLE list_history_new (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    list_id INTEGER NOT NULL,
+    action TEXT NOT NULL CHECK (action IN ('Created', 'Updated', 'Trashed', 'Restored', 'Deleted')),
+    changes TEXT NOT NULL,
+    changed_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%SZ', 'now')),
+    source TEXT NOT NULL
+);
+INSERT INTO list_history_new SELECT id, list_id, action, changes, changed_at, source FROM list_history;
+DROP TABLE list_history;
+ALTER TABLE list_history_new RENAME TO list_history;
+CREATE INDEX list_history_list_id ON list_history (list_id, id);
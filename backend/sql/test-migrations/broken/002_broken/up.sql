@@ -0,0 +1,2 @@
+INSERT INTO foo (id) VALUES (1);
+INSERT INTO bar (id) VALUES (1);
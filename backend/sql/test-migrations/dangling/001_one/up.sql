@@ -0,0 +1,2 @@
+CREATE TABLE foo (id INTEGER PRIMARY KEY);
+CREATE TABLE bar (foo_id INTEGER REFERENCES foo(id));
@@ -0,0 +1 @@
+INSERT INTO bar (foo_id) VALUES (1);
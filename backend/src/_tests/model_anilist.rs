@@ -0,0 +1,209 @@
+use crate::model;
+use model::test_support::TestDb;
+use model::element::{ListElementMAC, ListElementStatus};
+use model::query::ListQuery;
+use model::import::ImportIssue;
+use super::{parse_anilist, AniListScoreFormat};
+
+const ANILIST_EXPORT: &str = r#"{
+    "data": {
+        "MediaListCollection": {
+            "user": { "mediaListOptions": { "scoreFormat": "POINT_10_DECIMAL" } },
+            "lists": [
+                {
+                    "name": "Completed",
+                    "isCustomList": false,
+                    "status": "COMPLETED",
+                    "entries": [
+                        {
+                            "mediaId": 1,
+                            "status": "COMPLETED",
+                            "score": 8.5,
+                            "progress": 26,
+                            "repeat": 2,
+                            "private": false,
+                            "notes": "See you space cowboy",
+                            "customLists": { "Favourites": true, "Rewatch Later": false },
+                            "advancedScores": { "Story": 9, "Music": 10 },
+                            "startedAt": { "year": 2019, "month": 1, "day": 5 },
+                            "completedAt": { "year": 2019, "month": null, "day": null },
+                            "media": { "id": 1, "idMal": 1, "episodes": 26, "title": { "userPreferred": "Cowboy Bebop", "romaji": "Cowboy Bebop" } }
+                        }
+                    ]
+                },
+                {
+                    "name": "Watching",
+                    "isCustomList": false,
+                    "status": "CURRENT",
+                    "entries": [
+                        {
+                            "mediaId": 30,
+                            "status": "REPEATING",
+                            "score": 0,
+                            "progress": 3,
+                            "private": true,
+                            "media": { "id": 30, "idMal": 30, "episodes": null, "title": { "romaji": "Shinseiki Evangelion", "english": "Neon Genesis Evangelion" } }
+                        },
+                        {
+                            "mediaId": 99,
+                            "status": "CURRENT",
+                            "media": { "id": 99, "title": {} }
+                        }
+                    ]
+                },
+                {
+                    "name": "Space Stuff",
+                    "isCustomList": true,
+                    "entries": [
+                        {
+                            "mediaId": 1,
+                            "status": "COMPLETED",
+                            "score": 8.5,
+                            "progress": 26,
+                            "media": { "id": 1, "idMal": 1, "episodes": 26, "title": { "userPreferred": "Cowboy Bebop" } }
+                        }
+                    ]
+                }
+            ]
+        }
+    }
+}"#;
+
+#[test]
+fn model_anilist_parse_fields() -> Result<(), Box<dyn std::error::Error>> {
+
+    let (records, issues) = parse_anilist(ANILIST_EXPORT)?;
+
+    assert_eq!(2, records.len(), "AniList Parse Test Records");
+
+    let (_, bebop) = &records[0];
+    assert_eq!(Some("Cowboy Bebop".to_string()), bebop.title, "AniList Parse Test Title");
+    assert_eq!(Some(ListElementStatus::Completed), bebop.status, "AniList Parse Test Status");
+    assert_eq!(Some(85), bebop.score.flatten(), "AniList Parse Test Decimal Score");
+    assert_eq!((Some(26), Some(26)), (bebop.episodes_watched, bebop.episodes_total.flatten()), "AniList Parse Test Episodes");
+    assert_eq!(Some("2019-01-05".to_string()), bebop.started_on.clone().flatten(), "AniList Parse Test Start Date");
+    assert_eq!(None, bebop.finished_on, "AniList Parse Test Partial Date");
+    assert_eq!(Some(1), bebop.mal_id.flatten(), "AniList Parse Test MAL Id");
+    assert_eq!(Some("Favourites Space-Stuff".to_string()), bebop.tags, "AniList Parse Test Custom Lists");
+
+    let (_, eva) = &records[1];
+    assert_eq!(Some("Shinseiki Evangelion".to_string()), eva.title, "AniList Parse Test Romaji Title");
+    assert_eq!(Some(ListElementStatus::Watching), eva.status, "AniList Parse Test Repeating");
+    assert_eq!((None, None), (eva.score, eva.episodes_total), "AniList Parse Test Unknowns");
+
+    let unmapped = |title: &str, field: &str| issues.iter().any(|issue| matches!(issue,
+        ImportIssue::Unmapped { title: t, field: f, .. } if t == title && *f == field));
+
+    assert!(unmapped("Cowboy Bebop", "repeat"), "AniList Parse Test Repeat Issue");
+    assert!(unmapped("Cowboy Bebop", "advancedScores"), "AniList Parse Test Advanced Scores Issue");
+    assert!(unmapped("Cowboy Bebop", "completedAt"), "AniList Parse Test Partial Date Issue");
+    assert!(unmapped("Shinseiki Evangelion", "private"), "AniList Parse Test Private Issue");
+    assert!(unmapped("Shinseiki Evangelion", "status"), "AniList Parse Test Status Issue");
+    assert!(issues.contains(&ImportIssue::Skipped { index: 2, reason: "no media title".to_string() }), "AniList Parse Test Skipped");
+
+    Ok(())
+}
+
+#[test]
+fn model_anilist_score_formats() -> Result<(), Box<dyn std::error::Error>> {
+
+    let formats = [
+        (AniListScoreFormat::Point100, "POINT_100"),
+        (AniListScoreFormat::Point10Decimal, "POINT_10_DECIMAL"),
+        (AniListScoreFormat::Point10, "POINT_10"),
+        (AniListScoreFormat::Point5, "POINT_5"),
+        (AniListScoreFormat::Point3, "POINT_3"),
+    ];
+
+    for (format, name) in formats {
+        let json = format!("\"{}\"", name);
+
+        assert_eq!(format, serde_json::from_str::<AniListScoreFormat>(&json)?, "AniList Score Format Test Read {}", name);
+        assert_eq!(json, serde_json::to_string(&format)?, "AniList Score Format Test Write {}", name);
+        // a whole export in the format reads, not only the value
+        assert!(parse_anilist(&ANILIST_EXPORT.replace("POINT_10_DECIMAL", name)).is_ok(), "AniList Score Format Test Export {}", name);
+    }
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn model_anilist_import_skipped_index() -> Result<(), Box<dyn std::error::Error>> {
+
+    let db = TestDb::seeded()?;
+    // the parser drops the first entry, the second has more episodes watched than there are
+    let json = r#"{ "MediaListCollection": { "lists": [
+        { "name": "Watching", "status": "CURRENT", "entries": [
+            { "mediaId": 1, "media": { "id": 1, "title": {} } },
+            { "mediaId": 2, "progress": 5, "media": { "id": 2, "episodes": 3, "title": { "romaji": "Too Far" } } },
+            { "mediaId": 3, "progress": 1, "media": { "id": 3, "episodes": 3, "title": { "romaji": "Fine" } } }
+        ] }
+    ] } }"#;
+
+    let report = ListElementMAC::import_anilist(&db, json, true)?;
+    let skipped = report.issues.iter().filter_map(|issue| match issue {
+        ImportIssue::Skipped { index, .. } => Some(*index),
+        _ => None,
+    }).collect::<Vec<_>>();
+
+    assert_eq!(vec![0, 1], skipped, "AniList Skipped Index Test File Positions");
+    assert_eq!(1, report.entries.len(), "AniList Skipped Index Test Entries");
+
+    Ok(())
+}
+
+#[test]
+fn model_anilist_parse_invalid() {
+
+    let result = parse_anilist(r#"{ "lists": [] }"#);
+
+    assert!(matches!(result, Err(model::Error::JsonError(_))), "AniList Parse Invalid Test");
+}
+
+#[tokio::test]
+async fn model_anilist_import() -> Result<(), Box<dyn std::error::Error>> {
+
+    let db = TestDb::seeded()?;
+
+    let dry_run = ListElementMAC::import_anilist(&db, ANILIST_EXPORT, true)?;
+    assert_eq!(2, dry_run.entries.len(), "AniList Import Test Dry Run Entries");
+    assert_eq!(3, ListElementMAC::get_all(&db, &ListQuery::default())?.len(), "AniList Import Test Dry Run Untouched");
+
+    let report = ListElementMAC::import_anilist(&db, ANILIST_EXPORT, false)?;
+    assert_eq!(2, report.created.len(), "AniList Import Test Created");
+    assert_eq!(5, ListElementMAC::get_all(&db, &ListQuery::default())?.len(), "AniList Import Test Length");
+
+    let again = ListElementMAC::import_anilist(&db, ANILIST_EXPORT, false)?;
+    assert_eq!(2, again.duplicates().count(), "AniList Import Test Reimport Duplicates");
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn model_anilist_export_round_trip() -> Result<(), Box<dyn std::error::Error>> {
+
+    let db = TestDb::seeded()?;
+
+    let json = ListElementMAC::export_anilist(&db)?;
+    let (records, issues) = parse_anilist(&json)?;
+    let elements = ListElementMAC::get_all(&db, &ListQuery::default())?;
+
+    assert!(issues.is_empty(), "AniList Export Test No Issues");
+    assert_eq!(elements.len(), records.len(), "AniList Export Test Length");
+
+    for element in &elements {
+        let (_, record) = records.iter().find(|(_, r)| r.title.as_deref() == Some(element.title.as_str())).ok_or("missing element")?;
+
+        assert_eq!(Some(element.status), record.status, "AniList Export Test Status");
+        // custom lists are a map, they come back sorted
+        let sorted = |tags: &Option<String>| {
+            let mut tags: Vec<&str> = tags.as_deref().unwrap_or_default().split_whitespace().collect();
+            tags.sort();
+            tags.join(" ")
+        };
+        assert_eq!(sorted(&element.tags), sorted(&record.tags), "AniList Export Test Tags");
+        assert_eq!(element.score, record.score.flatten(), "AniList Export Test Score");
+    }
+
+    Ok(())
+}
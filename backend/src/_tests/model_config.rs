@@ -0,0 +1,64 @@
+use crate::model;
+use model::db::init_db;
+use model::migration::current_version;
+use model::ScoreScale;
+use super::{pick_db, Config, DbSource, TRASH_DAYS};
+
+#[tokio::test]
+async fn model_config_pick_db() -> Result<(), Box<dyn std::error::Error>> {
+
+    assert_eq!(Some(("a.tldb".to_string(), DbSource::Flag)), pick_db(Some("a.tldb"), Some("b.tldb"), Some("c.tldb")), "Config Pick Test Flag");
+    assert_eq!(Some(("b.tldb".to_string(), DbSource::Env)), pick_db(None, Some("b.tldb"), Some("c.tldb")), "Config Pick Test Env");
+    assert_eq!(Some(("c.tldb".to_string(), DbSource::Config)), pick_db(Some(""), Some(" "), Some("c.tldb")), "Config Pick Test Empty");
+    assert_eq!(None, pick_db(None, None, None), "Config Pick Test Default");
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn model_config_save_load() -> Result<(), Box<dyn std::error::Error>> {
+
+    let dir = tempfile::tempdir()?;
+    let config_path = dir.path().join("config.toml");
+
+    // missing file
+    assert_eq!(Config::default(), Config::load_from(&config_path)?, "Config Load Test Missing");
+
+    let mut config = Config::default();
+    config.use_db("one.tldb");
+    config.use_db("two.tldb");
+    config.use_db("one.tldb");
+    config.save_to(&config_path)?;
+
+    let loaded = Config::load_from(&config_path)?;
+    assert_eq!(Some("one.tldb".to_string()), loaded.db, "Config Load Test Db");
+    assert_eq!(vec!["one.tldb".to_string(), "two.tldb".to_string()], loaded.recent, "Config Load Test Recent");
+
+    assert_eq!(ScoreScale::default(), loaded.score_scale, "Config Load Test Score Scale Default");
+    Config { score_scale: ScoreScale::Star5, ..loaded.clone() }.save_to(&config_path)?;
+    assert_eq!(ScoreScale::Star5, Config::load_from(&config_path)?.score_scale, "Config Load Test Score Scale");
+
+    assert_eq!(TRASH_DAYS, loaded.trash_retention(), "Config Load Test Trash Default");
+    std::fs::write(&config_path, "trash_days = 0")?;
+    assert_eq!(0, Config::load_from(&config_path)?.trash_retention(), "Config Load Test Trash Days");
+
+    std::fs::write(&config_path, "db = [")?;
+    assert!(matches!(Config::load_from(&config_path), Err(model::Error::ConfigError(..))), "Config Load Test Invalid");
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn model_config_init_db_creates_dir() -> Result<(), Box<dyn std::error::Error>> {
+
+    let dir = tempfile::tempdir()?;
+    let db_path = dir.path().join("nested").join("db.tldb");
+
+    let mm = init_db(Some(&db_path.to_string_lossy()))?;
+    let version = current_version(&mut *mm.db().get()?)?;
+
+    assert!(db_path.exists(), "Config Init Test Created");
+    assert!(version > 0, "Config Init Test Migrated");
+
+    Ok(())
+}
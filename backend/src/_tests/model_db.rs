@@ -1,21 +1,110 @@
-use super::{init_db};
-use fallible_iterator::FallibleIterator;
+use super::{init_db, init_dev_db, ModelManager};
+use crate::model::schema::list;
+use crate::model::{ListElementMAC, ListElementPatch};
+use diesel::prelude::*;
+
+// a db file in a temp dir of the test's own
+fn temp_db(dir: &tempfile::TempDir) -> String {
+    dir.path().join("test.tldb").to_string_lossy().into_owned()
+}
 
 #[tokio::test]
 async fn model_db_init_db() -> Result<(), Box<dyn std::error::Error>> {
 
-    // ACTION -> adjust this to use diesel?
-    let db = init_db(None)?.clone().get().unwrap();
-    let mut stmt = db.prepare("SELECT * from list")?;
-    let rows = stmt.query([])?;
-    
-    // CHECK 
+    let dir = tempfile::tempdir()?;
+
+    // ACTION
+    let mm = init_dev_db(Some(&temp_db(&dir)))?;
+    let actual_size = list::table.count().get_result::<i64>(&mut mm.db().get()?)?;
 
-    const EXPECTED_SIZE : usize  = 3;
+    // CHECK
 
-    let actual_size = rows.map(|r| r.get::<usize, i32>(0)).count()?;
+    const EXPECTED_SIZE : i64  = 3;
 
     assert_eq!(EXPECTED_SIZE,actual_size , "Number of seed list elements");
 
     Ok(())
-}
\ No newline at end of file
+}
+
+#[tokio::test]
+async fn model_db_init_db_does_not_reseed() -> Result<(), Box<dyn std::error::Error>> {
+
+    let dir = tempfile::tempdir()?;
+
+    init_dev_db(Some(&temp_db(&dir)))?;
+
+    // ACTION -> a normal app start on an existing db
+    let mm = init_db(Some(&temp_db(&dir)))?;
+    let actual_size = list::table.count().get_result::<i64>(&mut mm.db().get()?)?;
+
+    // CHECK
+    assert_eq!(3, actual_size, "Number of list elements after second init");
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn model_db_model_manager_shares_pool() -> Result<(), Box<dyn std::error::Error>> {
+
+    let dir = tempfile::tempdir()?;
+
+    // ACTION -> open migrates a new db, a clone writes through the same pool
+    let mm = ModelManager::open(&temp_db(&dir))?;
+    let shared = mm.clone();
+
+    let created = ListElementMAC::create(&shared, ListElementPatch { title: Some("Mob Psycho 100".to_string()), ..Default::default() })?;
+    let fetched = ListElementMAC::get(&mm, created.id)?;
+
+    // CHECK
+    assert_eq!(temp_db(&dir), mm.path(), "ModelManager Test path");
+    assert_eq!((created.id, created.title), (fetched.id, fetched.title), "ModelManager Test element written through the clone");
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn model_db_cached_shares_pool() -> Result<(), Box<dyn std::error::Error>> {
+
+    let dir = tempfile::tempdir()?;
+    init_db(Some(&temp_db(&dir)))?;
+
+    let first = ModelManager::cached(&temp_db(&dir))?;
+    let held: Vec<_> = (0..first.db().max_size()).map(|_| first.db().get()).collect::<Result<_, _>>()?;
+
+    // every connection is taken, a second pool would still hand one out
+    let second = ModelManager::cached(&temp_db(&dir))?;
+    assert!(second.db().try_get().is_none(), "Cached Test Same Pool");
+
+    drop(held);
+    assert!(second.db().try_get().is_some(), "Cached Test Released");
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn model_db_cached_evicts() -> Result<(), Box<dyn std::error::Error>> {
+
+    let dir = tempfile::tempdir()?;
+    init_db(Some(&temp_db(&dir)))?;
+
+    // another spelling of the same path is the same pool
+    let respelled = dir.path().join(".").join("test.tldb").to_string_lossy().into_owned();
+    assert_eq!(super::cache_key(&temp_db(&dir)), super::cache_key(&respelled), "Cached Evicts Test Canonical Path");
+
+    // once closed the next call builds a pool of its own, other tests share the cache so only the outcome is checked
+    let first = ModelManager::cached(&temp_db(&dir))?;
+    let held: Vec<_> = (0..first.db().max_size()).map(|_| first.db().get()).collect::<Result<_, _>>()?;
+    ModelManager::close_cached(&respelled);
+    assert!(!ModelManager::close_cached(&temp_db(&dir)), "Cached Evicts Test Closed");
+    assert!(ModelManager::cached(&temp_db(&dir))?.db().try_get().is_some(), "Cached Evicts Test New Pool");
+    drop(held);
+
+    // only the most recently used pools are kept
+    let others: Vec<_> = (0..super::CACHED_MAX).map(|_| tempfile::tempdir()).collect::<Result<_, _>>()?;
+    for other in &others {
+        ModelManager::cached(&temp_db(other))?;
+    }
+    assert!(!ModelManager::close_cached(&temp_db(&dir)), "Cached Evicts Test Least Recently Used");
+
+    Ok(())
+}
@@ -0,0 +1,95 @@
+use crate::model;
+use model::test_support::TestDb;
+use model::{ListElementMAC, ListElementPatch, ListElementStatus, ScoreScale, DEFAULT_SOURCE};
+use super::{FieldChange, HistoryAction, HistoryMAC};
+use serde_json::{json, Value};
+
+fn change(field: &str, before: Value, after: Value) -> FieldChange {
+    FieldChange { field: field.to_string(), before, after }
+}
+
+#[tokio::test]
+async fn model_history_create_update_delete() -> Result<(), Box<dyn std::error::Error>> {
+
+    let db = TestDb::empty()?;
+    let mm = (*db).clone().with_source("gui");
+
+    let element = ListElementMAC::create(&mm, ListElementPatch {
+        title: Some("History Test".to_string()),
+        episodes_total: Some(Some(12)),
+        ..Default::default()
+    })?;
+    ListElementMAC::update(&mm, element.id, &ListElementPatch { title: Some("History Test 2".to_string()), ..Default::default() })?;
+    ListElementMAC::set_progress(&mm, element.id, 12)?;
+    ListElementMAC::delete(&mm, element.id)?;
+    ListElementMAC::purge(&mm, element.id)?;
+
+    let history = HistoryMAC::get_for_element(&db, element.id)?;
+    let actions: Vec<HistoryAction> = history.iter().map(|entry| entry.action).collect();
+
+    // newest first, and kept after the element is gone
+    assert_eq!(vec![HistoryAction::Deleted, HistoryAction::Trashed, HistoryAction::Updated, HistoryAction::Updated, HistoryAction::Created], actions, "History Test Actions");
+    assert!(history.iter().all(|entry| entry.source == "gui"), "History Test Source");
+
+    assert_eq!(vec![
+        change("episodes_watched", json!(0), json!(12)),
+        change("status", json!("Plan to Watch"), json!("Completed")),
+    ], history[2].changes, "History Test Progress");
+    assert_eq!(vec![change("title", json!("History Test"), json!("History Test 2"))], history[3].changes, "History Test Title");
+
+    let created = &history[4].changes;
+    assert!(created.contains(&change("title", Value::Null, json!("History Test"))), "History Test Created {:?}", created);
+    assert!(created.iter().all(|change| change.before.is_null()), "History Test Created Before");
+    assert_eq!(vec!["deleted_at"], history[1].changes.iter().map(|change| change.field.as_str()).collect::<Vec<_>>(), "History Test Trashed");
+    assert!(history[0].changes.iter().all(|change| change.after.is_null()), "History Test Deleted After");
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn model_history_unchanged_update_not_recorded() -> Result<(), Box<dyn std::error::Error>> {
+
+    let db = TestDb::seeded()?;
+
+    // the seed is loaded with plain SQL, so it has no history of its own
+    ListElementMAC::update(&db, 10, &ListElementPatch { title: Some("List Element 10".to_string()), ..Default::default() })?;
+    ListElementMAC::update(&db, 10, &ListElementPatch { status: Some(ListElementStatus::Watching), ..Default::default() })?;
+
+    assert!(HistoryMAC::get_for_element(&db, 10)?.is_empty(), "Unchanged Update History");
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn model_history_tags_and_score() -> Result<(), Box<dyn std::error::Error>> {
+
+    let db = TestDb::seeded()?;
+
+    ListElementMAC::add_tag(&db, 10, "qux".to_string())?;
+    ListElementMAC::set_score(&db, 10, Some(5), ScoreScale::Star5)?;
+
+    let history = HistoryMAC::get_for_element(&db, 10)?;
+
+    assert_eq!(vec![change("score", json!(80), json!(100))], history[0].changes, "Tags And Score Score");
+    assert_eq!(vec![change("tags", json!("foo"), json!("foo qux"))], history[1].changes, "Tags And Score Tags");
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn model_history_get_all() -> Result<(), Box<dyn std::error::Error>> {
+
+    let db = TestDb::seeded()?;
+
+    ListElementMAC::delete_from_path(db.path(), 11)?;
+    ListElementMAC::increment_episodes_from_path(db.path(), 10)?;
+
+    let history = HistoryMAC::get_all(&db, Some(1))?;
+
+    assert_eq!(1, history.len(), "Get All Limit");
+    assert_eq!((10, HistoryAction::Updated), (history[0].list_id, history[0].action), "Get All Newest");
+    assert_eq!(DEFAULT_SOURCE, history[0].source, "Get All Default Source");
+    assert_eq!(2, HistoryMAC::get_all(&db, None)?.len(), "Get All Length");
+
+    Ok(())
+}
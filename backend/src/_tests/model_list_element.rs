@@ -1,22 +1,25 @@
 use crate::model;
-use model::db::init_db;
+use model::test_support::TestDb;
+use diesel::connection::SimpleConnection;
+use model::{ListQuery, ScoreScale, TagMatch};
 use super::{ListElementPatch, ListElementStatus, ListElementMAC};
 
 #[tokio::test]
 async fn model_list_element_create_from_path() -> Result<(), Box<dyn std::error::Error>> {
 
-    let _db = init_db(None)?;
+    let db = TestDb::seeded()?;
     let data_fx = ListElementPatch { 
         title: Some("Create Test".to_string()),
-        status: Some(ListElementStatus::Closed.to_string()),
+        status: Some(ListElementStatus::Completed),
         ..Default::default()
     };
 
-    let inserted_row = ListElementMAC::create_from_path("sql/db.tldb", data_fx.clone())?;
-    
-    const EXPECTED_ID : i32 = 13;
+    let inserted_row = ListElementMAC::create_from_path(db.path(), data_fx.clone())?;
+    let fetched_row = ListElementMAC::get_from_path(db.path(), inserted_row.id)?;
 
-    assert_eq!(EXPECTED_ID, inserted_row.id, "Create Test ID");
+    // a fresh id, whatever the fixtures used, that reads back the same element
+    assert_eq!(inserted_row.id, fetched_row.id, "Create Test ID");
+    assert_eq!(inserted_row.title, fetched_row.title, "Create Test Fetched Title");
     assert_eq!(data_fx.title.unwrap(), inserted_row.title, "Create Test Title");
     assert_eq!(data_fx.status.unwrap() , inserted_row.status, "Create Test Status");
 
@@ -26,16 +29,16 @@ async fn model_list_element_create_from_path() -> Result<(), Box<dyn std::error:
 #[tokio::test]
 async fn model_list_element_get_all_from_path() -> Result<(), Box<dyn std::error::Error>> {
 
-    let _db = init_db(None)?;
+    let db = TestDb::seeded()?;
 
-    let rows = ListElementMAC::get_all_from_path("sql/db.tldb", None)?;
+    let rows = ListElementMAC::get_all_from_path(db.path(), &ListQuery::default())?;
     
     // last Element
     let final_row = &rows[rows.len()-1];
 
     assert_eq!(3, rows.len(), "Get Test Length");
     assert_eq!("List Element".to_string(), final_row.title, "Get Test Title");
-    assert_eq!(ListElementStatus::Archived.to_string() , final_row.status, "Get Test Status");
+    assert_eq!(ListElementStatus::Dropped , final_row.status, "Get Test Status");
 
     Ok(())
 }
@@ -43,13 +46,13 @@ async fn model_list_element_get_all_from_path() -> Result<(), Box<dyn std::error
 #[tokio::test]
 async fn model_list_element_get_from_name_from_path_one_result() -> Result<(), Box<dyn std::error::Error>> {
 
-    let _db = init_db(None)?;
+    let db = TestDb::seeded()?;
 
-    let rows = ListElementMAC::get_from_name_from_path("sql/db.tldb", "List Element 11".to_string())?;
+    let rows = ListElementMAC::get_from_name_from_path(db.path(), "List Element 11".to_string(), &ListQuery::default())?;
     
     assert_eq!(1, rows.len(), "Get From Name One Result Test Length");
     assert_eq!("List Element 11".to_string(), rows[0].title, "Get Test Title");
-    assert_eq!(ListElementStatus::Closed.to_string() , rows[0].status, "Get Test Status");
+    assert_eq!(ListElementStatus::Completed , rows[0].status, "Get Test Status");
 
     Ok(())
 }
@@ -57,13 +60,13 @@ async fn model_list_element_get_from_name_from_path_one_result() -> Result<(), B
 #[tokio::test]
 async fn model_list_element_get_from_name_from_path_many_results() -> Result<(), Box<dyn std::error::Error>> {
 
-    let _db = init_db(None)?;
+    let db = TestDb::seeded()?;
 
-    let rows = ListElementMAC::get_from_name_from_path("sql/db.tldb", "List Element 1".to_string())?;
+    let rows = ListElementMAC::get_from_name_from_path(db.path(), "List Element 1".to_string(), &ListQuery::default())?;
     
     assert_eq!(2, rows.len(), "Get From Name Many Results Test Length");
     assert_eq!("List Element 10".to_string(), rows[0].title, "Get From Name Many Results Test Title");
-    assert_eq!(ListElementStatus::Open.to_string() , rows[0].status, "Get From Name Many Results Test Status");
+    assert_eq!(ListElementStatus::Watching , rows[0].status, "Get From Name Many Results Test Status");
 
     Ok(())
 }
@@ -71,13 +74,13 @@ async fn model_list_element_get_from_name_from_path_many_results() -> Result<(),
 #[tokio::test]
 async fn model_list_element_get_from_tags_from_path_one_result() -> Result<(), Box<dyn std::error::Error>> {
 
-    let _db = init_db(None)?;
+    let db = TestDb::seeded()?;
 
-    let rows = ListElementMAC::get_from_tags_from_path("sql/db.tldb", "foo bar baz".to_string())?;
+    let rows = ListElementMAC::get_from_tags_from_path(db.path(), "foo bar baz".to_string(), &ListQuery::default())?;
     
     assert_eq!(1, rows.len(), "Get From Tags One Results Test Length");
     assert_eq!("List Element 11".to_string(), rows[0].title, "Get From Tags One Results Test Title");
-    assert_eq!(ListElementStatus::Closed.to_string() , rows[0].status, "Get From Tags One Results Test Status");
+    assert_eq!(ListElementStatus::Completed , rows[0].status, "Get From Tags One Results Test Status");
 
     Ok(())
 }
@@ -85,13 +88,13 @@ async fn model_list_element_get_from_tags_from_path_one_result() -> Result<(), B
 #[tokio::test]
 async fn model_list_element_get_from_tags_from_path_many_results() -> Result<(), Box<dyn std::error::Error>> {
 
-    let _db = init_db(None)?;
+    let db = TestDb::seeded()?;
 
-    let rows = ListElementMAC::get_from_tags_from_path("sql/db.tldb", "foo".to_string())?;
+    let rows = ListElementMAC::get_from_tags_from_path(db.path(), "foo".to_string(), &ListQuery::default())?;
     
     assert_eq!(3, rows.len(), "Get From Tags Many Results Test Length");
     assert_eq!("List Element".to_string(), rows[rows.len()-1].title, "Get From Tags Many Results Test Title");
-    assert_eq!(ListElementStatus::Archived.to_string() , rows[rows.len()-1].status, "Get From Tags Many Results Test Status");
+    assert_eq!(ListElementStatus::Dropped , rows[rows.len()-1].status, "Get From Tags Many Results Test Status");
     
     Ok(())
 }
@@ -99,20 +102,26 @@ async fn model_list_element_get_from_tags_from_path_many_results() -> Result<(),
 #[tokio::test]
 async fn model_list_element_update() -> Result<(), Box<dyn std::error::Error>> {
 
-    let _db = init_db(None)?;
+    let db = TestDb::seeded()?;
 
     let data_fx = ListElementPatch { 
         title: None, 
-        notes: Some("foo bar baz".to_string()), 
+        notes: Some(Some("foo bar baz".to_string())), 
         tags: Some("fo bar".to_string()), 
-        status: Some(ListElementStatus::Open.to_string()) 
+        status: Some(ListElementStatus::Watching),
+        episodes_watched: None,
+        episodes_total: None,
+        score: None,
+        started_on: None,
+        finished_on: None,
+        mal_id: None,
     };
 
 
-    let updated_row = ListElementMAC::update_from_path("sql/db.tldb", "List Element".to_string() , &data_fx)?;
+    let updated_row = ListElementMAC::update_from_title(&db, "List Element".to_string() , &data_fx)?;
     
     assert_eq!("List Element".to_string(), updated_row.title, "Update Test Title");
-    assert_eq!(data_fx.notes , updated_row.notes, "Update Test Notes");
+    assert_eq!(data_fx.notes.clone().flatten(), updated_row.notes, "Update Test Notes");
     assert_eq!(data_fx.tags , updated_row.tags, "Update Test Tags");
     assert_eq!(data_fx.status.unwrap() , updated_row.status, "Update Test Status");
     
@@ -122,21 +131,27 @@ async fn model_list_element_update() -> Result<(), Box<dyn std::error::Error>> {
 #[tokio::test]
 async fn model_list_element_update_no_change() -> Result<(), Box<dyn std::error::Error>> {
 
-    let _db = init_db(None)?;
+    let db = TestDb::seeded()?;
 
     let data_fx = ListElementPatch { 
         title: None, 
         notes: None, 
         tags: None, 
-        status: None 
+        status: None,
+        episodes_watched: None,
+        episodes_total: None,
+        score: None,
+        started_on: None,
+        finished_on: None,
+        mal_id: None,
     };
 
-    let updated_row = ListElementMAC::update_from_path("sql/db.tldb", "List Element".to_string() , &data_fx)?;
+    let updated_row = ListElementMAC::update_from_title(&db, "List Element".to_string() , &data_fx)?;
     
     assert_eq!("List Element".to_string(), updated_row.title, "Update No Change Test Title");
     assert_eq!("Lipsum Orem".to_string() , updated_row.notes.unwrap(), "Update No Change Test Notes");
     assert_eq!("foo bar".to_string() , updated_row.tags.unwrap(), "Update No Change Test Tags");
-    assert_eq!(ListElementStatus::Archived.to_string() , updated_row.status, "Update No Change Test Status");
+    assert_eq!(ListElementStatus::Dropped , updated_row.status, "Update No Change Test Status");
     
     Ok(())
 }
@@ -144,11 +159,11 @@ async fn model_list_element_update_no_change() -> Result<(), Box<dyn std::error:
 #[tokio::test]
 async fn model_list_element_delete() -> Result<(), Box<dyn std::error::Error>> {
 
-    let _db = init_db(None)?;
+    let db = TestDb::seeded()?;
 
-    ListElementMAC::delete_from_path("sql/db.tldb", "List Element".to_string())?;
+    ListElementMAC::delete_from_title(&db, "List Element".to_string())?;
     
-    let rows = ListElementMAC::get_all_from_path("sql/db.tldb", None)?;
+    let rows = ListElementMAC::get_all(&db, &ListQuery::default())?;
 
     assert_eq!(2, rows.len(), "Delete Test Number of elements");
     
@@ -158,14 +173,522 @@ async fn model_list_element_delete() -> Result<(), Box<dyn std::error::Error>> {
 #[tokio::test]
 async fn model_list_element_delete_nonexistant() -> Result<(), Box<dyn std::error::Error>> {
 
-    let _db = init_db(None)?;
+    let db = TestDb::seeded()?;
 
     //no panic here because the filter on delete will narrow to 0 elements and then do nothing
-    ListElementMAC::delete_from_path("sql/db.tldb", "foo bar".to_string())?;
+    ListElementMAC::delete_from_title(&db, "foo bar".to_string())?;
 
-    let rows = ListElementMAC::get_all_from_path("sql/db.tldb", None)?;
+    let rows = ListElementMAC::get_all(&db, &ListQuery::default())?;
     
     assert_eq!(3, rows.len(), "Delete Non-Existant Test Number of elements");
     
     Ok(())
 }
+
+#[tokio::test]
+async fn model_list_element_increment_episodes_closes() -> Result<(), Box<dyn std::error::Error>> {
+
+    let db = TestDb::seeded()?;
+
+    let updated_row = ListElementMAC::increment_episodes(&db, 10)?;
+
+    assert_eq!(12, updated_row.episodes_watched, "Increment Episodes Test Watched");
+    assert_eq!(ListElementStatus::Completed, updated_row.status, "Increment Episodes Test Status");
+
+    // already on the last episode, stays there
+    let updated_row = ListElementMAC::increment_episodes(&db, 10)?;
+
+    assert_eq!(12, updated_row.episodes_watched, "Increment Episodes Past Total Test Watched");
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn model_list_element_decrement_episodes() -> Result<(), Box<dyn std::error::Error>> {
+
+    let db = TestDb::seeded()?;
+
+    let updated_row = ListElementMAC::decrement_episodes(&db, 10)?;
+
+    assert_eq!(10, updated_row.episodes_watched, "Decrement Episodes Test Watched");
+    assert_eq!(ListElementStatus::Watching, updated_row.status, "Decrement Episodes Test Status");
+
+    // no episodes watched yet, stays at 0
+    let updated_row = ListElementMAC::decrement_episodes(&db, 12)?;
+
+    assert_eq!(0, updated_row.episodes_watched, "Decrement Episodes Below Zero Test Watched");
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn model_list_element_set_progress_unknown_total() -> Result<(), Box<dyn std::error::Error>> {
+
+    let db = TestDb::seeded()?;
+
+    let updated_row = ListElementMAC::set_progress(&db, 12, 100)?;
+
+    assert_eq!(100, updated_row.episodes_watched, "Set Progress Unknown Total Test Watched");
+    assert_eq!(None, updated_row.episodes_total, "Set Progress Unknown Total Test Total");
+    assert_eq!(ListElementStatus::Dropped, updated_row.status, "Set Progress Unknown Total Test Status");
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn model_list_element_update_completes() -> Result<(), Box<dyn std::error::Error>> {
+
+    let db = TestDb::seeded()?;
+
+    // 10 is watching, 11 of 12
+    let completed = ListElementMAC::update(&db, 10, &ListElementPatch { episodes_watched: Some(12), ..Default::default() })?;
+    assert_eq!(ListElementStatus::Completed, completed.status, "Update Completes Test Watched");
+
+    // a status given alongside is kept
+    ListElementMAC::update(&db, 10, &ListElementPatch { episodes_watched: Some(0), status: Some(ListElementStatus::Watching), ..Default::default() })?;
+    let rewatching = ListElementMAC::update(&db, 10, &ListElementPatch { episodes_watched: Some(12), status: Some(ListElementStatus::OnHold), ..Default::default() })?;
+    assert_eq!(ListElementStatus::OnHold, rewatching.status, "Update Completes Test Explicit Status");
+
+    // other fields leave the status alone, even at the last episode
+    let renamed = ListElementMAC::update(&db, 10, &ListElementPatch { title: Some("Renamed".to_string()), ..Default::default() })?;
+    assert_eq!(ListElementStatus::OnHold, renamed.status, "Update Completes Test Unrelated Edit");
+
+    // lowering the total onto the watched count also reaches the end
+    let lowered = ListElementMAC::update(&db, 11, &ListElementPatch { status: Some(ListElementStatus::Watching), episodes_watched: Some(10), ..Default::default() })
+        .and_then(|_| ListElementMAC::update(&db, 11, &ListElementPatch { episodes_total: Some(Some(10)), ..Default::default() }))?;
+    assert_eq!(ListElementStatus::Completed, lowered.status, "Update Completes Test Total");
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn model_list_element_update_clears() -> Result<(), Box<dyn std::error::Error>> {
+
+    let db = TestDb::seeded()?;
+
+    // 10 has a total of 12 and a score of 80
+    let cleared = ListElementMAC::update(&db, 10, &ListElementPatch { episodes_total: Some(None), score: Some(None), notes: Some(None), ..Default::default() })?;
+    assert_eq!((None, None, None), (cleared.episodes_total, cleared.score, cleared.notes), "Update Clears Test Cleared");
+    assert_eq!(11, cleared.episodes_watched, "Update Clears Test Untouched");
+
+    // null in JSON clears, a missing field is left alone
+    let patch: ListElementPatch = serde_json::from_str(r#"{"score": null}"#)?;
+    assert_eq!(ListElementPatch { score: Some(None), ..Default::default() }, patch, "Update Clears Test Null");
+    assert_eq!(ListElementPatch::default(), serde_json::from_str::<ListElementPatch>("{}")?, "Update Clears Test Missing");
+    assert_eq!(patch, serde_json::from_str::<ListElementPatch>(&serde_json::to_string(&patch)?)?, "Update Clears Test Round Trip");
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn model_list_element_set_progress_nonexistant() -> Result<(), Box<dyn std::error::Error>> {
+
+    let db = TestDb::seeded()?;
+
+    let result = ListElementMAC::set_progress(&db, 99, 1);
+
+    assert!(matches!(result, Err(model::Error::EntityNotFound(_, _))), "Set Progress Non-Existant Test Error");
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn model_list_element_get_from_tags_exact_match() -> Result<(), Box<dyn std::error::Error>> {
+
+    let db = TestDb::seeded()?;
+
+    // "fo" is a prefix of "foo" but not a tag, and order does not matter
+    let partial = ListElementMAC::get_from_tags(&db, "fo".to_string(), &ListQuery::default())?;
+    let reordered = ListElementMAC::get_from_tags(&db, "baz FOO".to_string(), &ListQuery::default())?;
+
+    assert_eq!(0, partial.len(), "Get From Tags Exact Match Test Partial");
+    assert_eq!(1, reordered.len(), "Get From Tags Exact Match Test Reordered Length");
+    assert_eq!("List Element 11".to_string(), reordered[0].title, "Get From Tags Exact Match Test Reordered Title");
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn model_list_element_get_from_tag_list_any() -> Result<(), Box<dyn std::error::Error>> {
+
+    let db = TestDb::seeded()?;
+
+    let tags = vec!["baz".to_string(), "bar".to_string(), "qux".to_string()];
+    let any = ListElementMAC::get_from_tag_list(&db, tags.clone(), TagMatch::Any, &ListQuery::default())?;
+    let all = ListElementMAC::get_from_tag_list(&db, tags, TagMatch::All, &ListQuery::default())?;
+
+    assert_eq!(2, any.len(), "Get From Tag List Any Test Length");
+    assert_eq!(0, all.len(), "Get From Tag List All Test Length");
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn model_list_element_get_from_tag_list_duplicates_and_empty() -> Result<(), Box<dyn std::error::Error>> {
+
+    let db = TestDb::seeded()?;
+
+    let duplicates = ListElementMAC::get_from_tag_list(&db, vec!["foo".to_string(), "FOO".to_string(), "baz".to_string()], TagMatch::All, &ListQuery::default())?;
+    let empty = ListElementMAC::get_from_tag_list(&db, Vec::new(), TagMatch::All, &ListQuery::default())?;
+    let blank = ListElementMAC::get_from_tags(&db, "  ".to_string(), &ListQuery::default())?;
+
+    assert_eq!(vec![11], duplicates.iter().map(|e| e.id).collect::<Vec<_>>(), "Tag List Duplicates Test");
+    assert_eq!(3, empty.len(), "Tag List Empty Test");
+    assert_eq!(3, blank.len(), "Tag List Blank Test");
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn model_list_element_add_remove_tag() -> Result<(), Box<dyn std::error::Error>> {
+
+    let db = TestDb::seeded()?;
+
+    let added = ListElementMAC::add_tag(&db, 10, "slice of life".to_string())?;
+    let added_twice = ListElementMAC::add_tag(&db, 10, "Slice-Of-Life".to_string())?;
+
+    assert_eq!(Some("foo slice-of-life".to_string()), added.tags, "Add Tag Test Tags");
+    assert_eq!(added.tags, added_twice.tags, "Add Tag Twice Test Tags");
+
+    let removed = ListElementMAC::remove_tag(&db, 10, "foo".to_string())?;
+    let rows = ListElementMAC::get_from_tags(&db, "foo".to_string(), &ListQuery::default())?;
+
+    assert_eq!(Some("slice-of-life".to_string()), removed.tags, "Remove Tag Test Tags");
+    assert_eq!(2, rows.len(), "Remove Tag Test Query Length");
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn model_list_element_update_syncs_tags() -> Result<(), Box<dyn std::error::Error>> {
+
+    let db = TestDb::seeded()?;
+
+    let data_fx = ListElementPatch {
+        tags: Some("qux  foo qux".to_string()),
+        ..Default::default()
+    };
+
+    let updated_row = ListElementMAC::update(&db, 12, &data_fx)?;
+    let rows = ListElementMAC::get_from_tags(&db, "qux".to_string(), &ListQuery::default())?;
+
+    assert_eq!(Some("qux foo".to_string()), updated_row.tags, "Update Syncs Tags Test Tags");
+    assert_eq!(1, rows.len(), "Update Syncs Tags Test Query Length");
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn model_list_element_get_from_path() -> Result<(), Box<dyn std::error::Error>> {
+
+    let db = TestDb::seeded()?;
+
+    let row = ListElementMAC::get_from_path(db.path(), 11)?;
+    let missing = ListElementMAC::get_from_path(db.path(), 99);
+
+    assert_eq!("List Element 11".to_string(), row.title, "Get By Id Test Title");
+    assert!(matches!(missing, Err(model::Error::EntityNotFound("list", id)) if id == "99"), "Get By Id Non-Existant Test Error");
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn model_list_element_update_by_id_shared_title() -> Result<(), Box<dyn std::error::Error>> {
+
+    let db = TestDb::seeded()?;
+
+    // a remake with the same title as an existing element
+    let remake = ListElementMAC::create(&db, ListElementPatch {
+        title: Some("List Element".to_string()),
+        ..Default::default()
+    })?;
+
+    let data_fx = ListElementPatch {
+        title: Some("List Element (Remake)".to_string()),
+        ..Default::default()
+    };
+
+    let updated_row = ListElementMAC::update(&db, remake.id, &data_fx)?;
+    let original = ListElementMAC::get(&db, 12)?;
+
+    assert_eq!(remake.id, updated_row.id, "Update Shared Title Test Id");
+    assert_eq!(data_fx.title.unwrap(), updated_row.title, "Update Shared Title Test Title");
+    assert_eq!("List Element".to_string(), original.title, "Update Shared Title Test Original Untouched");
+
+    let missing = ListElementMAC::update(&db, 99, &ListElementPatch { notes: Some(Some("foo".to_string())), ..Default::default() });
+    let empty = ListElementMAC::update(&db, 99, &ListElementPatch::default());
+
+    assert!(matches!(missing, Err(model::Error::EntityNotFound(_, _))), "Update Non-Existant Test Error");
+    assert!(matches!(empty, Err(model::Error::EntityNotFound(_, _))), "Update Non-Existant Empty Patch Test Error");
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn model_list_element_title_ambiguous() -> Result<(), Box<dyn std::error::Error>> {
+
+    let db = TestDb::seeded()?;
+
+    ListElementMAC::create(&db, ListElementPatch {
+        title: Some("List Element".to_string()),
+        ..Default::default()
+    })?;
+
+    let get = ListElementMAC::get_from_title(&db, "List Element".to_string());
+    let delete = ListElementMAC::delete_from_title(&db, "List Element".to_string());
+
+    assert!(matches!(get, Err(model::Error::AmbiguousTitle(_))), "Title Ambiguous Get Test Error");
+    assert!(matches!(delete, Err(model::Error::AmbiguousTitle(_))), "Title Ambiguous Delete Test Error");
+    assert_eq!(4, ListElementMAC::get_all(&db, &ListQuery::default())?.len(), "Title Ambiguous Delete Test Number of elements");
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn model_list_element_delete_by_id() -> Result<(), Box<dyn std::error::Error>> {
+
+    let db = TestDb::seeded()?;
+
+    ListElementMAC::delete(&db, 11)?;
+    // already in the trash, nothing changes
+    ListElementMAC::delete(&db, 11)?;
+    let missing = ListElementMAC::delete(&db, 99);
+
+    assert!(matches!(missing, Err(model::Error::EntityNotFound(_, _))), "Delete By Id Non-Existant Test Error");
+    assert_eq!(2, ListElementMAC::get_all(&db, &ListQuery::default())?.len(), "Delete By Id Test Number of elements");
+
+    Ok(())
+}
+
+// UTC ISO-8601, 2022-10-18T20:15:00Z
+fn is_iso_utc(timestamp: &str) -> bool {
+    timestamp.len() == 20 && &timestamp[10..11] == "T" && timestamp.ends_with('Z')
+}
+
+#[tokio::test]
+async fn model_list_element_create_timestamps() -> Result<(), Box<dyn std::error::Error>> {
+
+    let db = TestDb::seeded()?;
+
+    let inserted_row = ListElementMAC::create(&db, ListElementPatch {
+        title: Some("Create Timestamps Test".to_string()),
+        ..Default::default()
+    })?;
+    let seeded_row = ListElementMAC::get(&db, 10)?;
+
+    assert!(is_iso_utc(&inserted_row.ctime), "Create Timestamps Test Ctime {}", inserted_row.ctime);
+    assert!(is_iso_utc(&seeded_row.ctime), "Create Timestamps Test Seeded Ctime {}", seeded_row.ctime);
+    assert_eq!(None, inserted_row.mtime, "Create Timestamps Test Mtime");
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn model_list_element_update_sets_mtime() -> Result<(), Box<dyn std::error::Error>> {
+
+    let db = TestDb::seeded()?;
+
+    let updated_row = ListElementMAC::update(&db, 10, &ListElementPatch {
+        notes: Some(Some("foo".to_string())),
+        ..Default::default()
+    })?;
+    let progressed_row = ListElementMAC::increment_episodes(&db, 12)?;
+    let tagged_row = ListElementMAC::add_tag(&db, 11, "qux".to_string())?;
+    let untouched_row = ListElementMAC::update(&db, 11, &ListElementPatch::default())?;
+
+    for row in [&updated_row, &progressed_row, &tagged_row] {
+        let mtime = row.mtime.clone().unwrap_or_default();
+
+        assert!(is_iso_utc(&mtime), "Update Sets Mtime Test {} Mtime {}", row.title, mtime);
+        assert!(mtime >= row.ctime, "Update Sets Mtime Test {} After Ctime", row.title);
+    }
+    assert_eq!(tagged_row.mtime, untouched_row.mtime, "Update Sets Mtime Test Empty Patch");
+
+    Ok(())
+}
+
+#[test]
+fn model_list_element_status_parse() {
+
+    assert_eq!(Ok(ListElementStatus::PlanToWatch), "plan to watch".parse::<ListElementStatus>(), "Status Parse Test Display");
+    assert_eq!(Ok(ListElementStatus::OnHold), "OnHold".parse::<ListElementStatus>(), "Status Parse Test Variant");
+    assert!("Open".parse::<ListElementStatus>().is_err(), "Status Parse Test Retired");
+    assert_eq!("On Hold", ListElementStatus::OnHold.to_string(), "Status Display Test");
+}
+
+#[tokio::test]
+async fn model_list_element_create_default_status() -> Result<(), Box<dyn std::error::Error>> {
+
+    let db = TestDb::seeded()?;
+
+    let inserted_row = ListElementMAC::create(&db, ListElementPatch {
+        title: Some("Create Default Status Test".to_string()),
+        ..Default::default()
+    })?;
+
+    assert_eq!(ListElementStatus::PlanToWatch, inserted_row.status, "Create Default Status Test Status");
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn model_list_element_invalid_status() -> Result<(), Box<dyn std::error::Error>> {
+
+    let db = TestDb::seeded()?;
+
+    // written behind the model's back with foreign keys off
+    db.db().get()?.batch_execute("PRAGMA foreign_keys = OFF; UPDATE list SET status = 'Rewatching' WHERE id = 10;")?;
+
+    let result = ListElementMAC::get(&db, 10);
+
+    assert!(matches!(result, Err(model::Error::DieselResultError(_))), "Invalid Status Test Error");
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn model_list_element_set_score() -> Result<(), Box<dyn std::error::Error>> {
+
+    let db = TestDb::seeded()?;
+
+    let scored_row = ListElementMAC::set_score(&db, 12, Some(3), ScoreScale::Star5)?;
+    let cleared_row = ListElementMAC::set_score(&db, 10, None, ScoreScale::Star5)?;
+    let invalid = ListElementMAC::set_score(&db, 11, Some(11), ScoreScale::Point10);
+
+    assert_eq!(Some(60), scored_row.score, "Set Score Test Score");
+    assert!(scored_row.mtime.is_some(), "Set Score Test Mtime");
+    assert_eq!(None, cleared_row.score, "Set Score Clear Test Score");
+    assert!(matches!(invalid, Err(model::Error::InvalidValue(_, _))), "Set Score Invalid Test Error");
+    assert_eq!(Some(90), ListElementMAC::get(&db, 11)?.score, "Set Score Invalid Test Unchanged");
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn model_list_element_get_top_rated() -> Result<(), Box<dyn std::error::Error>> {
+
+    let db = TestDb::seeded()?;
+
+    ListElementMAC::create(&db, ListElementPatch {
+        title: Some("Unscored".to_string()),
+        ..Default::default()
+    })?;
+
+    let rows = ListElementMAC::get_top_rated(&db, None)?;
+    let limited = ListElementMAC::get_top_rated(&db, Some(1))?;
+
+    let titles: Vec<&str> = rows.iter().map(|row| row.title.as_str()).collect();
+
+    assert_eq!(vec!["List Element 11", "List Element 10", "List Element"], titles, "Top Rated Test Order");
+    assert_eq!(1, limited.len(), "Top Rated Test Limit");
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn model_list_element_invalid_patch() -> Result<(), Box<dyn std::error::Error>> {
+
+    let db = TestDb::seeded()?;
+
+    let no_title = ListElementMAC::create(&db, ListElementPatch {
+        notes: Some(Some("foo".to_string())),
+        ..Default::default()
+    });
+    let past_total = ListElementMAC::create(&db, ListElementPatch {
+        title: Some("List Element 13".to_string()),
+        episodes_watched: Some(13),
+        episodes_total: Some(Some(12)),
+        ..Default::default()
+    });
+    let blank_title = ListElementMAC::update(&db, 10, &ListElementPatch { title: Some("  ".to_string()), ..Default::default() });
+    let bad_score = ListElementMAC::update(&db, 10, &ListElementPatch { score: Some(Some(101)), ..Default::default() });
+    let negative = ListElementMAC::update(&db, 10, &ListElementPatch { episodes_watched: Some(-1), ..Default::default() });
+    // 10 is at 11 of 12, each side alone is checked against the stored other
+    let watched_past_total = ListElementMAC::update(&db, 10, &ListElementPatch { episodes_watched: Some(30), ..Default::default() });
+    let total_below_watched = ListElementMAC::update(&db, 10, &ListElementPatch { episodes_total: Some(Some(5)), ..Default::default() });
+
+    assert!(matches!(no_title, Err(model::Error::InvalidValue("title", _))), "Invalid Patch Test No Title");
+    assert!(matches!(past_total, Err(model::Error::InvalidValue("episodes_watched", _))), "Invalid Patch Test Past Total");
+    assert!(matches!(blank_title, Err(model::Error::InvalidValue("title", _))), "Invalid Patch Test Blank Title");
+    assert!(matches!(bad_score, Err(model::Error::InvalidValue("score", _))), "Invalid Patch Test Score");
+    assert!(matches!(negative, Err(model::Error::InvalidValue("episodes_watched", _))), "Invalid Patch Test Negative");
+    assert!(matches!(watched_past_total, Err(model::Error::InvalidValue("episodes_watched", _))), "Invalid Patch Test Watched Past Stored Total");
+    assert!(matches!(total_below_watched, Err(model::Error::InvalidValue("episodes_watched", _))), "Invalid Patch Test Total Below Stored Watched");
+
+    let unchanged = ListElementMAC::get(&db, 10)?;
+    assert_eq!(("List Element 10".to_string(), Some(80), 11, Some(12)), (unchanged.title, unchanged.score, unchanged.episodes_watched, unchanged.episodes_total), "Invalid Patch Test Unchanged");
+    assert_eq!(3, ListElementMAC::get_all(&db, &ListQuery::default())?.len(), "Invalid Patch Test Nothing Created");
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn model_list_element_trash_and_restore() -> Result<(), Box<dyn std::error::Error>> {
+
+    let db = TestDb::seeded()?;
+
+    ListElementMAC::delete(&db, 11)?;
+
+    let ids = |elements: Vec<model::ListElement>| elements.iter().map(|element| element.id).collect::<Vec<_>>();
+    let trashed = ListElementMAC::get(&db, 11)?;
+
+    // still there for get, left out of everything listing the elements
+    assert!(trashed.deleted_at.as_deref().is_some_and(is_iso_utc), "Trash Test Deleted At {:?}", trashed.deleted_at);
+    assert_eq!(2, ListElementMAC::get_all(&db, &ListQuery::default())?.len(), "Trash Test Get All");
+    assert!(ListElementMAC::get_from_name(&db, "11".to_string(), &ListQuery::default())?.is_empty(), "Trash Test Get From Name");
+    assert!(ListElementMAC::get_from_tags(&db, "baz".to_string(), &ListQuery::default())?.is_empty(), "Trash Test Get From Tags");
+    assert!(ListElementMAC::get_from_title(&db, "List Element 11".to_string()).is_err(), "Trash Test Get From Title");
+    assert!(!ListElementMAC::get_top_rated(&db, None)?.iter().any(|element| element.id == 11), "Trash Test Top Rated");
+    assert!(!model::TagMAC::get_all(&db)?.iter().any(|tag| tag.name == "baz"), "Trash Test Tag Counts");
+    assert_eq!(2, ListElementMAC::stats(&db)?.total, "Trash Test Stats");
+    assert_eq!(vec![11], ids(ListElementMAC::get_trash(&db, &ListQuery::default())?), "Trash Test Get Trash");
+
+    let restored = ListElementMAC::restore(&db, 11)?;
+
+    assert_eq!(None, restored.deleted_at, "Trash Test Restored");
+    assert_eq!(Some("foo bar baz".to_string()), restored.tags, "Trash Test Restored Tags");
+    assert_eq!(3, ListElementMAC::get_all(&db, &ListQuery::default())?.len(), "Trash Test Restored Get All");
+    assert!(ListElementMAC::get_trash(&db, &ListQuery::default())?.is_empty(), "Trash Test Restored Trash");
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn model_list_element_purge() -> Result<(), Box<dyn std::error::Error>> {
+
+    let db = TestDb::seeded()?;
+
+    let on_list = ListElementMAC::purge(&db, 10);
+    assert!(matches!(on_list, Err(model::Error::InvalidValue("purge", _))), "Purge Test On List");
+
+    ListElementMAC::delete(&db, 10)?;
+    ListElementMAC::purge(&db, 10)?;
+    assert!(ListElementMAC::get(&db, 10).is_err(), "Purge Test Gone");
+
+    ListElementMAC::delete(&db, 11)?;
+    ListElementMAC::delete(&db, 12)?;
+    assert_eq!(2, ListElementMAC::empty_trash(&db)?, "Purge Test Empty Trash");
+    assert!(ListElementMAC::get_all(&db, &ListQuery::default())?.is_empty() && ListElementMAC::get_trash(&db, &ListQuery::default())?.is_empty(), "Purge Test Nothing Left");
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn model_list_element_purge_expired() -> Result<(), Box<dyn std::error::Error>> {
+
+    let db = TestDb::seeded()?;
+
+    ListElementMAC::delete(&db, 10)?;
+    ListElementMAC::delete(&db, 11)?;
+    // 11 went to the trash 40 days ago
+    db.db().get()?.batch_execute("UPDATE list SET deleted_at = strftime('%Y-%m-%dT%H:%M:%SZ', 'now', '-40 days') WHERE id = 11")?;
+
+    assert_eq!(0, ListElementMAC::purge_expired(&db, 0)?, "Purge Expired Test Keep Forever");
+    assert_eq!(0, ListElementMAC::purge_expired(&db, 60)?, "Purge Expired Test Not Yet");
+    assert_eq!(1, ListElementMAC::purge_expired(&db, 30)?, "Purge Expired Test Purged");
+
+    assert!(ListElementMAC::get(&db, 11).is_err(), "Purge Expired Test Gone");
+    assert!(ListElementMAC::get(&db, 10)?.deleted_at.is_some(), "Purge Expired Test Kept");
+
+    Ok(())
+}
@@ -0,0 +1,178 @@
+use crate::model;
+use model::test_support::TestDb;
+use model::element::{ListElementMAC, ListElementPatch, ListElementStatus};
+use model::query::ListQuery;
+use model::import::ImportIssue;
+use super::parse_mal;
+
+const MAL_EXPORT: &str = r#"<?xml version="1.0" encoding="UTF-8" ?>
+<myanimelist>
+    <myinfo>
+        <user_id>1</user_id>
+        <user_name>someone</user_name>
+        <user_export_type>1</user_export_type>
+    </myinfo>
+    <anime>
+        <series_animedb_id>1</series_animedb_id>
+        <series_title><![CDATA[Cowboy Bebop]]></series_title>
+        <series_type>TV</series_type>
+        <series_episodes>26</series_episodes>
+        <my_watched_episodes>26</my_watched_episodes>
+        <my_start_date>2019-01-05</my_start_date>
+        <my_finish_date>2019-02-10</my_finish_date>
+        <my_score>9</my_score>
+        <my_status>Completed</my_status>
+        <my_comments><![CDATA[See you space cowboy & co]]></my_comments>
+        <my_tags><![CDATA[space, jazz music]]></my_tags>
+    </anime>
+    <anime>
+        <series_animedb_id>30</series_animedb_id>
+        <series_title><![CDATA[Neon Genesis Evangelion]]></series_title>
+        <series_episodes>0</series_episodes>
+        <my_watched_episodes>3</my_watched_episodes>
+        <my_start_date>2020-04-00</my_start_date>
+        <my_finish_date>0000-00-00</my_finish_date>
+        <my_score>0</my_score>
+        <my_status>Rewatching</my_status>
+        <my_comments><![CDATA[]]></my_comments>
+        <my_tags><![CDATA[]]></my_tags>
+    </anime>
+    <anime>
+        <series_animedb_id>2</series_animedb_id>
+        <series_title><![CDATA[List Element 10]]></series_title>
+        <series_episodes>12</series_episodes>
+        <my_watched_episodes>12</my_watched_episodes>
+        <my_score>7</my_score>
+        <my_status>On-Hold</my_status>
+    </anime>
+    <anime>
+        <series_animedb_id>1</series_animedb_id>
+        <series_title><![CDATA[cowboy bebop]]></series_title>
+        <my_status>Watching</my_status>
+    </anime>
+    <anime>
+        <my_status>Watching</my_status>
+    </anime>
+</myanimelist>"#;
+
+#[test]
+fn model_mal_parse_fields() -> Result<(), Box<dyn std::error::Error>> {
+
+    let (records, issues) = parse_mal(MAL_EXPORT)?;
+
+    assert_eq!(4, records.len(), "MAL Parse Test Records");
+
+    let (_, bebop) = &records[0];
+    assert_eq!(Some("Cowboy Bebop".to_string()), bebop.title, "MAL Parse Test Title");
+    assert_eq!(Some(1), bebop.mal_id.flatten(), "MAL Parse Test Id");
+    assert_eq!(Some(ListElementStatus::Completed), bebop.status, "MAL Parse Test Status");
+    assert_eq!((Some(26), Some(26)), (bebop.episodes_watched, bebop.episodes_total.flatten()), "MAL Parse Test Episodes");
+    assert_eq!(Some(90), bebop.score.flatten(), "MAL Parse Test Score");
+    assert_eq!(Some("2019-01-05".to_string()), bebop.started_on.clone().flatten(), "MAL Parse Test Start Date");
+    assert_eq!(Some("2019-02-10".to_string()), bebop.finished_on.clone().flatten(), "MAL Parse Test Finish Date");
+    assert_eq!(Some("space jazz-music".to_string()), bebop.tags, "MAL Parse Test Tags");
+    assert_eq!(Some("See you space cowboy & co".to_string()), bebop.notes.clone().flatten(), "MAL Parse Test Comments");
+
+    let (_, eva) = &records[1];
+    assert_eq!((None, None, None), (eva.episodes_total, eva.score, eva.notes.clone()), "MAL Parse Test Unknowns");
+    assert_eq!(Some(ListElementStatus::PlanToWatch), eva.status, "MAL Parse Test Unknown Status");
+    assert_eq!(Some(ListElementStatus::OnHold), records[2].1.status, "MAL Parse Test On-Hold");
+
+    assert!(issues.contains(&ImportIssue::Unmapped {
+        title: "Neon Genesis Evangelion".to_string(), field: "my_status", value: "Rewatching".to_string() }), "MAL Parse Test Status Issue");
+    assert!(issues.contains(&ImportIssue::Unmapped {
+        title: "Neon Genesis Evangelion".to_string(), field: "my_start_date", value: "2020-04-00".to_string() }), "MAL Parse Test Date Issue");
+    assert!(issues.contains(&ImportIssue::Skipped { index: 4, reason: "no series_title".to_string() }), "MAL Parse Test Skipped");
+
+    Ok(())
+}
+
+#[test]
+fn model_mal_parse_not_mal() {
+
+    let result = parse_mal("<anime-list><entry/></anime-list>");
+
+    assert!(matches!(result, Err(model::Error::InvalidValue("mal", _))), "MAL Parse Not MAL Test");
+}
+
+#[tokio::test]
+async fn model_mal_import_dry_run() -> Result<(), Box<dyn std::error::Error>> {
+
+    let db = TestDb::seeded()?;
+
+    let report = ListElementMAC::import_mal(&db, MAL_EXPORT, true)?;
+
+    assert_eq!(2, report.entries.len(), "MAL Dry Run Test Entries");
+    assert!(report.created.is_empty(), "MAL Dry Run Test Nothing Created");
+    assert!(report.issues.contains(&ImportIssue::Duplicate { title: "List Element 10".to_string(), existing_id: 10 }), "MAL Dry Run Test Duplicate");
+    assert!(report.issues.contains(&ImportIssue::DuplicateInFile { title: "cowboy bebop".to_string() }), "MAL Dry Run Test Duplicate In File");
+    assert_eq!(2, report.duplicates().count(), "MAL Dry Run Test Duplicate Count");
+    assert_eq!(3, ListElementMAC::get_all(&db, &ListQuery::default())?.len(), "MAL Dry Run Test Db Untouched");
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn model_mal_import() -> Result<(), Box<dyn std::error::Error>> {
+
+    let db = TestDb::seeded()?;
+
+    let report = ListElementMAC::import_mal(&db, MAL_EXPORT, false)?;
+    assert_eq!(2, report.created.len(), "MAL Import Test Created");
+
+    let bebop = ListElementMAC::get_from_title(&db, "Cowboy Bebop".to_string())?;
+    assert_eq!(Some("space jazz-music".to_string()), bebop.tags, "MAL Import Test Tags");
+    assert_eq!(Some("2019-02-10".to_string()), bebop.finished_on, "MAL Import Test Finish Date");
+
+    // a second import finds everything already there
+    let again = ListElementMAC::import_mal(&db, MAL_EXPORT, false)?;
+    assert!(again.created.is_empty(), "MAL Import Test Reimport");
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn model_mal_export_round_trip() -> Result<(), Box<dyn std::error::Error>> {
+
+    let db = TestDb::seeded()?;
+
+    let xml = ListElementMAC::export_mal(&db)?;
+    assert!(xml.contains("<user_total_anime>3</user_total_anime>"), "MAL Export Test Totals");
+
+    let (records, issues) = parse_mal(&xml)?;
+    let elements = ListElementMAC::get_all(&db, &ListQuery::default())?;
+
+    assert!(issues.is_empty(), "MAL Export Test No Issues");
+    assert_eq!(elements.len(), records.len(), "MAL Export Test Length");
+
+    for (element, (_, record)) in elements.iter().zip(records) {
+        assert_eq!(Some(element.title.clone()), record.title, "MAL Export Test Title");
+        assert_eq!(Some(element.status), record.status, "MAL Export Test Status");
+        assert_eq!(element.tags, record.tags, "MAL Export Test Tags");
+        assert_eq!(element.score, record.score.flatten(), "MAL Export Test Score");
+        assert_eq!(Some(element.episodes_watched), record.episodes_watched, "MAL Export Test Episodes");
+    }
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn model_mal_export_cdata_and_zero_score() -> Result<(), Box<dyn std::error::Error>> {
+
+    let db = TestDb::seeded()?;
+    ListElementMAC::create(&db, ListElementPatch {
+        title: Some("Ends ]]> early".to_string()),
+        notes: Some(Some("a]]>b ]]]]> & <c>".to_string())),
+        score: Some(Some(0)),
+        ..Default::default()
+    })?;
+
+    let xml = ListElementMAC::export_mal(&db)?;
+    let (records, _) = parse_mal(&xml)?;
+    let (_, record) = records.iter().find(|(_, r)| r.title.as_deref() == Some("Ends ]]> early")).ok_or("missing element")?;
+
+    assert_eq!(Some("a]]>b ]]]]> & <c>".to_string()), record.notes.clone().flatten(), "MAL CDATA Test Notes");
+    assert!(xml.contains("<my_score>0</my_score>"), "MAL CDATA Test Zero Score");
+
+    Ok(())
+}
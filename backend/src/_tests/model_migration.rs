@@ -0,0 +1,196 @@
+use crate::model;
+use crate::model::schema::list;
+use super::{current_version, latest_version, migrate, migrate_with, UpTo, MIGRATIONS};
+use diesel::{connection::SimpleConnection, dsl::sql, prelude::*, sql_types::{BigInt, Bool}, SqliteConnection};
+use diesel_migrations::FileBasedMigrations;
+
+// migration sets under sql/test-migrations
+fn fixtures(name: &str) -> Result<FileBasedMigrations, Box<dyn std::error::Error>> {
+    Ok(FileBasedMigrations::from_path(format!("sql/test-migrations/{}", name))?)
+}
+
+fn memory_db() -> ConnectionResult<SqliteConnection> {
+    SqliteConnection::establish(":memory:")
+}
+
+fn count(conn: &mut SqliteConnection, from: &str) -> QueryResult<i64> {
+    diesel::select(sql::<BigInt>(&format!("(SELECT COUNT(*) FROM {})", from))).get_result(conn)
+}
+
+#[tokio::test]
+async fn model_migration_migrate_fresh_db() -> Result<(), Box<dyn std::error::Error>> {
+
+    let mut conn = memory_db()?;
+
+    let applied = migrate(&mut conn)?;
+
+    assert_eq!(latest_version()?, current_version(&mut conn)?, "Migrate Fresh Db Version");
+    assert_eq!(latest_version()? as usize, applied.len(), "Migrate Fresh Db Applied");
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn model_migration_migrate_up_to_date() -> Result<(), Box<dyn std::error::Error>> {
+
+    let mut conn = memory_db()?;
+
+    migrate_with(&mut conn, UpTo(fixtures("fx")?, 1))?;
+    let applied = migrate_with(&mut conn, fixtures("fx")?)?;
+
+    // only the pending one ran, so the table was not recreated
+    assert_eq!(vec![2], applied, "Migrate Up To Date Applied");
+    assert!(migrate_with(&mut conn, fixtures("fx")?)?.is_empty(), "Migrate Up To Date Rerun");
+
+    assert_eq!(1, count(&mut conn, "foo")?, "Migrate Up To Date Rows");
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn model_migration_migrate_failure_rolls_back() -> Result<(), Box<dyn std::error::Error>> {
+
+    let mut conn = memory_db()?;
+
+    assert!(migrate_with(&mut conn, fixtures("broken")?).is_err(), "Migrate Failure Error");
+
+    assert_eq!(1, current_version(&mut conn)?, "Migrate Failure Version");
+    assert_eq!(0, count(&mut conn, "foo")?, "Migrate Failure Rows");
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn model_migration_migrate_db_too_new() -> Result<(), Box<dyn std::error::Error>> {
+
+    let mut conn = memory_db()?;
+
+    migrate_with(&mut conn, fixtures("fx")?)?;
+    let result = migrate_with(&mut conn, UpTo(fixtures("fx")?, 1));
+
+    assert!(matches!(result, Err(model::Error::DbVersionTooNew(2, 1))), "Migrate Db Too New");
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn model_migration_adopts_schema_version() -> Result<(), Box<dyn std::error::Error>> {
+
+    let mut conn = memory_db()?;
+
+    // a db migrated to version 2 before diesel migrations
+    migrate_with(&mut conn, UpTo(MIGRATIONS, 2))?;
+    conn.batch_execute("
+        CREATE TABLE schema_version (
+            version INTEGER PRIMARY KEY,
+            name TEXT NOT NULL,
+            applied_at TEXT NOT NULL DEFAULT (datetime('now'))
+        );
+        INSERT INTO schema_version (version, name) VALUES (1, 'create-schema'), (2, 'episode-progress');
+        DELETE FROM __diesel_schema_migrations;")?;
+
+    let applied = migrate(&mut conn)?;
+    let legacy = count(&mut conn, "sqlite_master WHERE name = 'schema_version'")?;
+
+    assert_eq!((3..=latest_version()?).collect::<Vec<_>>(), applied, "Adopt Schema Version Applied");
+    assert_eq!(latest_version()?, current_version(&mut conn)?, "Adopt Schema Version Version");
+    assert_eq!(0, legacy, "Adopt Schema Version Dropped");
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn model_migration_normalize_tags_backfill() -> Result<(), Box<dyn std::error::Error>> {
+
+    let mut conn = memory_db()?;
+
+    // a db from before tags were normalized
+    migrate_with(&mut conn, UpTo(MIGRATIONS, 2))?;
+    conn.batch_execute("
+        INSERT INTO list (id, title, tags) VALUES (1, 'one', ' foo  bar ');
+        INSERT INTO list (id, title, tags) VALUES (2, 'two', 'bar baz');
+        INSERT INTO list (id, title) VALUES (3, 'three');")?;
+
+    migrate(&mut conn)?;
+
+    assert_eq!(3, count(&mut conn, "tag")?, "Normalize Tags Backfill Tags");
+    assert_eq!(4, count(&mut conn, "list_tag")?, "Normalize Tags Backfill Links");
+
+    // the display copy is rebuilt from list_tag on the next change
+    conn.batch_execute("DELETE FROM list_tag WHERE list_id = 1 AND tag_id = (SELECT id FROM tag WHERE name = 'bar')")?;
+    let tags = list::table.find(1).select(list::tags).first::<Option<String>>(&mut conn)?;
+
+    assert_eq!(Some("foo".to_string()), tags, "Normalize Tags Backfill Display");
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn model_migration_iso_timestamps() -> Result<(), Box<dyn std::error::Error>> {
+
+    let mut conn = memory_db()?;
+
+    migrate_with(&mut conn, UpTo(MIGRATIONS, 4))?;
+    conn.batch_execute("INSERT INTO list (id, title, ctime, mtime) VALUES (1, 'one', '2022-10-18 20:15:00', '2022-10-19 08:00:00');")?;
+
+    migrate(&mut conn)?;
+
+    let (ctime, mtime) = list::table.find(1).select((list::ctime, list::mtime)).first::<(String, Option<String>)>(&mut conn)?;
+
+    assert_eq!("2022-10-18T20:15:00Z", ctime, "Iso Timestamps Ctime");
+    assert_eq!(Some("2022-10-19T08:00:00Z".to_string()), mtime, "Iso Timestamps Mtime");
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn model_migration_anime_statuses() -> Result<(), Box<dyn std::error::Error>> {
+
+    let mut conn = memory_db()?;
+
+    migrate_with(&mut conn, UpTo(MIGRATIONS, 5))?;
+    conn.batch_execute("
+        INSERT INTO list (id, title, status) VALUES (1, 'one', 'Open');
+        INSERT INTO list (id, title, status, tags) VALUES (2, 'two', 'Closed', 'foo');
+        INSERT INTO list (id, title, status) VALUES (7, 'three', 'Archived');
+        DELETE FROM list WHERE id = 7;
+        INSERT INTO list_tag (list_id, tag_id) SELECT 2, id FROM tag;")?;
+
+    migrate(&mut conn)?;
+
+    let statuses = list::table.order(list::id).select(list::status).load::<String>(&mut conn)?;
+    conn.batch_execute("INSERT INTO list (title) VALUES ('four')")?;
+    let (id, status, ctime) = list::table
+        .filter(list::title.eq("four"))
+        .select((list::id, list::status, list::ctime))
+        .first::<(i32, String, String)>(&mut conn)?;
+
+    assert_eq!(vec!["Watching", "Completed"], statuses, "Anime Statuses Mapping");
+    assert_eq!(8, id, "Anime Statuses Keeps Sequence");
+    assert_eq!("Plan to Watch", status, "Anime Statuses Default");
+    assert!(ctime.ends_with('Z'), "Anime Statuses Ctime Default {}", ctime);
+
+    // triggers still fire on the rebuilt table
+    conn.batch_execute("DELETE FROM list WHERE id = 2")?;
+
+    assert_eq!(0, count(&mut conn, "list_tag")?, "Anime Statuses Tag Trigger");
+    assert_eq!(0, count(&mut conn, "list_fts WHERE list_fts MATCH 'two'")?, "Anime Statuses Fts Trigger");
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn model_migration_foreign_key_check() -> Result<(), Box<dyn std::error::Error>> {
+
+    let mut conn = memory_db()?;
+    conn.batch_execute("PRAGMA foreign_keys = ON;")?;
+
+    let result = migrate_with(&mut conn, fixtures("dangling")?);
+    let foreign_keys = diesel::select(sql::<Bool>("(SELECT foreign_keys FROM pragma_foreign_keys)")).get_result::<bool>(&mut conn)?;
+
+    assert!(matches!(result, Err(model::Error::MigrationFailed(2, _))), "Foreign Key Check Error");
+    assert_eq!(1, current_version(&mut conn)?, "Foreign Key Check Version");
+    assert!(foreign_keys, "Foreign Key Check Restores Pragma");
+
+    Ok(())
+}
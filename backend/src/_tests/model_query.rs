@@ -0,0 +1,171 @@
+use crate::model;
+use model::test_support::TestDb;
+use diesel::RunQueryDsl;
+use model::{ListElement, ListElementMAC, ListElementPatch, ListElementStatus, TagMatch, UndoMAC};
+use super::{ListQuery, SortDirection, SortField};
+
+fn ids(elements: Vec<ListElement>) -> Vec<i32> {
+    elements.iter().map(|element| element.id).collect()
+}
+
+#[tokio::test]
+async fn model_query_order_by() -> Result<(), Box<dyn std::error::Error>> {
+
+    let db = TestDb::seeded()?;
+
+    // the seed goes in within the same second, ties are in id order
+    assert_eq!(vec![10, 11, 12], ids(ListElementMAC::get_all(&db, &ListQuery::default())?), "Order By Test Default");
+    assert_eq!(vec![12, 10, 11], ids(ListElementMAC::get_all(&db, &ListQuery::new(SortField::Title, SortDirection::Ascending))?), "Order By Test Title");
+    assert_eq!(vec![11, 10, 12], ids(ListElementMAC::get_all(&db, &ListQuery::new(SortField::Title, SortDirection::Descending))?), "Order By Test Title Desc");
+    assert_eq!(vec![10, 11, 12], ids(ListElementMAC::get_all(&db, &ListQuery::new(SortField::Status, SortDirection::Ascending))?), "Order By Test Status");
+    assert_eq!(vec![12, 11, 10], ids(ListElementMAC::get_all(&db, &ListQuery::new(SortField::Ctime, SortDirection::Descending))?), "Order By Test Ctime Desc");
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn model_query_order_by_nullable() -> Result<(), Box<dyn std::error::Error>> {
+
+    let db = TestDb::seeded()?;
+    let unscored = ListElementMAC::create(&db, ListElementPatch { title: Some("Unscored".to_string()), ..Default::default() })?;
+
+    assert_eq!(vec![11, 10, 12, unscored.id], ids(ListElementMAC::get_all(&db, &ListQuery::new(SortField::Score, SortDirection::Descending))?), "Order By Nullable Test Score Desc");
+    assert_eq!(vec![unscored.id, 12, 10, 11], ids(ListElementMAC::get_all(&db, &ListQuery::new(SortField::Score, SortDirection::Ascending))?), "Order By Nullable Test Score");
+
+    // never edited elements sort by when they were added
+    diesel::sql_query("UPDATE list SET mtime = '2099-01-01T00:00:00Z' WHERE id = 11").execute(&mut *db.db().get()?)?;
+    let modified = ids(ListElementMAC::get_all(&db, &ListQuery::new(SortField::Mtime, SortDirection::Descending))?);
+
+    assert_eq!(11, modified[0], "Order By Nullable Test Mtime");
+    assert_eq!(4, modified.len(), "Order By Nullable Test Mtime Length");
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn model_query_offset_limit() -> Result<(), Box<dyn std::error::Error>> {
+
+    let db = TestDb::seeded()?;
+    let by_title = ListQuery::new(SortField::Title, SortDirection::Ascending);
+
+    assert_eq!(vec![10], ids(ListElementMAC::get_all(&db, &ListQuery { offset: Some(1), limit: Some(1), ..by_title.clone() })?), "Offset Limit Test Page");
+    assert_eq!(vec![10, 11], ids(ListElementMAC::get_all(&db, &ListQuery { offset: Some(1), ..by_title.clone() })?), "Offset Limit Test Offset Only");
+    assert_eq!(vec![12], ids(ListElementMAC::get_all(&db, &ListQuery::limited(Some(1)).after(&ListElementMAC::get(&db, 11)?))?), "Offset Limit Test Limited");
+    assert!(ListElementMAC::get_all(&db, &ListQuery { offset: Some(3), ..by_title })?.is_empty(), "Offset Limit Test Past The End");
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn model_query_cursor() -> Result<(), Box<dyn std::error::Error>> {
+
+    let db = TestDb::seeded()?;
+    let query = ListQuery { limit: Some(2), ..ListQuery::new(SortField::Score, SortDirection::Descending) };
+
+    let first = ListElementMAC::get_all(&db, &query)?;
+    assert_eq!(vec![11, 10], ids(first.clone()), "Cursor Test First Page");
+
+    // added ahead of the cursor, the next page does not shift onto what was already read
+    ListElementMAC::create(&db, ListElementPatch { title: Some("Top".to_string()), score: Some(Some(100)), ..Default::default() })?;
+
+    let next = query.after(first.last().unwrap());
+    assert_eq!(Some(10), next.cursor.as_ref().map(|cursor| cursor.id()), "Cursor Test Cursor");
+    assert_eq!(vec![12], ids(ListElementMAC::get_all(&db, &next)?), "Cursor Test Next Page");
+
+    // a cursor holds its element's key, changing the element afterwards does not move it
+    ListElementMAC::update(&db, 10, &ListElementPatch { score: Some(Some(10)), ..Default::default() })?;
+    assert_eq!(vec![12, 10], ids(ListElementMAC::get_all(&db, &next)?), "Cursor Test Moved Element");
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn model_query_cursor_other_order() -> Result<(), Box<dyn std::error::Error>> {
+
+    let db = TestDb::seeded()?;
+    let element = ListElementMAC::get(&db, 10)?;
+
+    let query = ListQuery {
+        order_by: SortField::Title,
+        ..ListQuery::new(SortField::Score, SortDirection::Ascending).after(&element)
+    };
+
+    assert!(matches!(ListElementMAC::get_all(&db, &query), Err(model::Error::InvalidValue("cursor", _))), "Cursor Other Order Test");
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn model_query_filtered_from_path() -> Result<(), Box<dyn std::error::Error>> {
+
+    let db = TestDb::seeded()?;
+    let by_title = ListQuery::new(SortField::Title, SortDirection::Descending);
+
+    let named = ListElementMAC::get_from_name_from_path(db.path(), "List Element 1".to_string(), &by_title)?;
+    let tagged = ListElementMAC::get_from_tag_list_from_path(db.path(), vec!["foo".to_string()], TagMatch::All, &ListQuery { limit: Some(2), ..by_title.clone() })?;
+
+    assert_eq!(vec![11, 10], ids(named), "Filtered Test Name");
+    assert_eq!(vec![11, 10], ids(tagged.clone()), "Filtered Test Tags");
+    assert_eq!(vec![12], ids(ListElementMAC::get_from_tags_from_path(db.path(), "foo".to_string(), &by_title.after(&tagged[1]))?), "Filtered Test Tags Next Page");
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn model_query_filters() -> Result<(), Box<dyn std::error::Error>> {
+
+    let db = TestDb::seeded()?;
+    let query = |status, tags: &[&str], tag_match| ListQuery {
+        status,
+        tags: tags.iter().map(|tag| tag.to_string()).collect(),
+        tag_match,
+        ..Default::default()
+    };
+
+    assert_eq!(vec![12], ids(ListElementMAC::get_all(&db, &query(Some(ListElementStatus::Dropped), &[], TagMatch::All))?), "Filters Test Status");
+    assert_eq!(vec![11, 12], ids(ListElementMAC::get_all(&db, &query(None, &["bar", "BAR", "foo"], TagMatch::All))?), "Filters Test Tags All");
+    assert_eq!(vec![11, 12], ids(ListElementMAC::get_all(&db, &query(None, &["baz", "bar", "qux"], TagMatch::Any))?), "Filters Test Tags Any");
+    assert_eq!(vec![11], ids(ListElementMAC::get_all(&db, &query(Some(ListElementStatus::Completed), &["bar"], TagMatch::All))?), "Filters Test Both");
+
+    // the filters narrow before the page is cut, a limited page is still full
+    let limited = ListQuery { limit: Some(1), ..query(Some(ListElementStatus::Dropped), &["foo"], TagMatch::All) };
+    assert_eq!(vec![12], ids(ListElementMAC::get_all(&db, &limited)?), "Filters Test Limited");
+    assert_eq!(1, ListElementMAC::count(&db, &ListQuery { limit: None, ..limited })?, "Filters Test Count");
+    assert_eq!(2, ListElementMAC::count(&db, &ListQuery { name: Some("element 1".to_string()), ..Default::default() })?, "Filters Test Count Name");
+
+    // % and _ in the name are themselves, not wildcards
+    for name in ["element_1", "element%1", "element\\"] {
+        assert_eq!(0, ListElementMAC::count(&db, &ListQuery { name: Some(name.to_string()), ..Default::default() })?, "Filters Test Name Wildcards {}", name);
+    }
+    let underscored = ListElementMAC::create(&db, ListElementPatch { title: Some("snake_case 100%".to_string()), ..Default::default() })?;
+    assert_eq!(vec![underscored.id], ids(ListElementMAC::get_all(&db, &ListQuery { name: Some("e_case 100%".to_string()), ..Default::default() })?), "Filters Test Name Literal");
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn model_query_trash_and_search() -> Result<(), Box<dyn std::error::Error>> {
+
+    let db = TestDb::seeded()?;
+
+    UndoMAC::delete(&db, 12)?;
+    UndoMAC::delete(&db, 10)?;
+    // trashed within the same second, ties are in id order
+    diesel::sql_query("UPDATE list SET deleted_at = '2099-01-01T00:00:00Z' WHERE id = 12").execute(&mut *db.db().get()?)?;
+
+    let trash = ListElementMAC::get_trash(&db, &ListQuery::new(SortField::Trashed, SortDirection::Descending))?;
+    assert_eq!(vec![12, 10], ids(trash), "Trash And Search Test Trash Order");
+
+    // every seeded title holds "element", the filters pick from all of them before the limit
+    let completed = ListQuery { status: Some(ListElementStatus::Completed), limit: Some(1), ..Default::default() };
+    let hits = ListElementMAC::search(&db, "element".to_string(), &completed)?;
+    assert_eq!(vec![11], hits.iter().map(|hit| hit.element.id).collect::<Vec<_>>(), "Trash And Search Test Search Status");
+
+    let wildcard = ListQuery { name: Some("element_1".to_string()), ..Default::default() };
+    assert!(ListElementMAC::search(&db, "element".to_string(), &wildcard)?.is_empty(), "Trash And Search Test Search Name Wildcard");
+
+    let tagged = ListQuery { tags: vec!["baz".to_string()], ..Default::default() };
+    assert_eq!(1, ListElementMAC::search(&db, "element".to_string(), &tagged)?.len(), "Trash And Search Test Search Tags");
+
+    Ok(())
+}
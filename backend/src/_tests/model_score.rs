@@ -0,0 +1,52 @@
+use crate::model;
+use super::ScoreScale;
+
+#[test]
+fn model_score_to_score() -> Result<(), Box<dyn std::error::Error>> {
+
+    assert_eq!(70, ScoreScale::Point10.to_score(7)?, "To Score Point10");
+    assert_eq!(73, ScoreScale::Point100.to_score(73)?, "To Score Point100");
+    assert_eq!(80, ScoreScale::Star5.to_score(4)?, "To Score Star5");
+    assert_eq!(85, ScoreScale::Smiley.to_score(3)?, "To Score Smiley");
+
+    assert!(matches!(ScoreScale::Star5.to_score(6), Err(model::Error::InvalidValue("score", _))), "To Score Out Of Range");
+    assert!(matches!(ScoreScale::Point10.to_score(0), Err(model::Error::InvalidValue("score", _))), "To Score Zero");
+
+    Ok(())
+}
+
+#[test]
+fn model_score_from_score() {
+
+    assert_eq!(7, ScoreScale::Point10.from_score(73), "From Score Point10");
+    assert_eq!(8, ScoreScale::Point10.from_score(75), "From Score Point10 Rounds Half Up");
+    assert_eq!(4, ScoreScale::Star5.from_score(73), "From Score Star5");
+    assert_eq!(1, ScoreScale::Star5.from_score(0), "From Score Star5 Minimum");
+    assert_eq!(2, ScoreScale::Smiley.from_score(60), "From Score Smiley");
+}
+
+#[test]
+fn model_score_convert() -> Result<(), Box<dyn std::error::Error>> {
+
+    assert_eq!(4, ScoreScale::convert(8, ScoreScale::Point10, ScoreScale::Star5)?, "Convert Point10 To Star5");
+    assert_eq!(3, ScoreScale::convert(9, ScoreScale::Point10, ScoreScale::Smiley)?, "Convert Point10 To Smiley");
+    assert_eq!(60, ScoreScale::convert(3, ScoreScale::Star5, ScoreScale::Point100)?, "Convert Star5 To Point100");
+
+    // every value survives a round trip through the finer 100 point scale
+    for scale in [ScoreScale::Point10, ScoreScale::Star5, ScoreScale::Smiley] {
+        for value in 1..=scale.max() {
+            let there = ScoreScale::convert(value, scale, ScoreScale::Point100)?;
+            assert_eq!(value, ScoreScale::convert(there, ScoreScale::Point100, scale)?, "Convert Round Trip {} {}", scale, value);
+        }
+    }
+
+    Ok(())
+}
+
+#[test]
+fn model_score_format() {
+
+    assert_eq!("8/10", ScoreScale::Point10.format(80), "Format Point10");
+    assert_eq!("\u{2605}\u{2605}\u{2605}\u{2605}\u{2606}", ScoreScale::Star5.format(80), "Format Star5");
+    assert_eq!(":)", ScoreScale::Smiley.format(80), "Format Smiley");
+}
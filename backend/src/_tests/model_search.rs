@@ -0,0 +1,105 @@
+use crate::model;
+use model::test_support::{Fixture, TestDb};
+use model::element::ListElementPatch;
+use model::ListQuery;
+use super::{build_fts_query, split_highlights, ListElementMAC};
+
+#[tokio::test]
+async fn model_search_rank_title_first() -> Result<(), Box<dyn std::error::Error>> {
+
+    let db = TestDb::new(&[Fixture::Seed, Fixture::Search])?;
+
+    let hits = ListElementMAC::search(&db, "bebop".to_string(), &ListQuery::default())?;
+
+    assert_eq!(2, hits.len(), "Search Rank Test Length");
+    assert_eq!("Cowboy Bebop".to_string(), hits[0].element.title, "Search Rank Test Title Match First");
+    assert_eq!("Samurai Champloo".to_string(), hits[1].element.title, "Search Rank Test Notes Match Second");
+    assert!(hits[0].rank < hits[1].rank, "Search Rank Test Order");
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn model_search_prefix_and_phrase() -> Result<(), Box<dyn std::error::Error>> {
+
+    let db = TestDb::new(&[Fixture::Seed, Fixture::Search])?;
+
+    let prefix = ListElementMAC::search(&db, "spa".to_string(), &ListQuery::default())?;
+    let phrase = ListElementMAC::search(&db, "\"dandy guy\"".to_string(), &ListQuery::default())?;
+    let not_phrase = ListElementMAC::search(&db, "\"guy dandy\"".to_string(), &ListQuery::default())?;
+    let limited = ListElementMAC::search(&db, "spa".to_string(), &ListQuery::limited(Some(1)))?;
+
+    assert_eq!(2, prefix.len(), "Search Prefix Test Length");
+    assert_eq!(1, phrase.len(), "Search Phrase Test Length");
+    assert_eq!("Space Dandy".to_string(), phrase[0].element.title, "Search Phrase Test Title");
+    assert_eq!(0, not_phrase.len(), "Search Phrase Order Test Length");
+    assert_eq!(1, limited.len(), "Search Limit Test Length");
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn model_search_highlight() -> Result<(), Box<dyn std::error::Error>> {
+
+    let db = TestDb::new(&[Fixture::Seed, Fixture::Search])?;
+
+    let hits = ListElementMAC::search(&db, "jazz cowboy".to_string(), &ListQuery::default())?;
+
+    assert_eq!(1, hits.len(), "Search Highlight Test Length");
+    assert_eq!(vec![("Cowboy", true), (" Bebop", false)], split_highlights(&hits[0].title), "Search Highlight Test Title");
+    assert!(split_highlights(&hits[0].snippet).contains(&("jazz", true)), "Search Highlight Test Snippet");
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn model_search_follows_updates() -> Result<(), Box<dyn std::error::Error>> {
+
+    let db = TestDb::new(&[Fixture::Seed, Fixture::Search])?;
+
+    ListElementMAC::update_from_title(&db, "Space Dandy".to_string(), &ListElementPatch {
+        notes: Some(Some("Baby".to_string())),
+        ..Default::default()
+    })?;
+    ListElementMAC::delete_from_title(&db, "Cowboy Bebop".to_string())?;
+
+    let old_notes = ListElementMAC::search(&db, "guy".to_string(), &ListQuery::default())?;
+    let new_notes = ListElementMAC::search(&db, "baby".to_string(), &ListQuery::default())?;
+    let deleted = ListElementMAC::search(&db, "jazz".to_string(), &ListQuery::default())?;
+
+    assert_eq!(0, old_notes.len(), "Search Updates Test Old Notes");
+    assert_eq!(1, new_notes.len(), "Search Updates Test New Notes");
+    assert_eq!(0, deleted.len(), "Search Updates Test Deleted");
+
+    Ok(())
+}
+
+#[test]
+fn model_search_build_fts_query() {
+
+    assert_eq!(r#""foo"* "bar baz" "qux"*"#, build_fts_query(r#"foo "bar   baz" qux*"#), "Build Fts Query Test");
+    assert_eq!(r#""AND"* "c"*"#, build_fts_query(r#"AND c"# ), "Build Fts Query Operators Test");
+    assert_eq!(r#""open""#, build_fts_query(r#""open"#), "Build Fts Query Unclosed Test");
+    assert_eq!("", build_fts_query("  \"\" "), "Build Fts Query Empty Test");
+}
+
+#[tokio::test]
+async fn model_search_equal_rank_pages() -> Result<(), Box<dyn std::error::Error>> {
+
+    let db = TestDb::empty()?;
+    let mut created = Vec::new();
+    for _ in 0..4 {
+        created.push(ListElementMAC::create(&db, ListElementPatch { title: Some("Twin Peaks".to_string()), ..Default::default() })?.id);
+    }
+
+    // every hit ranks the same, one page at a time still gives each of them once
+    let mut paged = Vec::new();
+    for offset in 0..4 {
+        let page = ListQuery { offset: Some(offset), limit: Some(1), ..Default::default() };
+        paged.extend(ListElementMAC::search(&db, "twin".to_string(), &page)?.into_iter().map(|hit| hit.element.id));
+    }
+
+    assert_eq!(created, paged, "Search Equal Rank Test Id Order");
+
+    Ok(())
+}
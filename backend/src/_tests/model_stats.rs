@@ -0,0 +1,38 @@
+use crate::model;
+use model::test_support::TestDb;
+use model::element::{ListElementMAC, ListElementStatus};
+
+#[tokio::test]
+async fn model_stats_from_path() -> Result<(), Box<dyn std::error::Error>> {
+
+    let db = TestDb::seeded()?;
+
+    let stats = ListElementMAC::stats_from_path(db.path())?;
+
+    assert_eq!(3, stats.total, "Stats Test Total");
+    assert!(stats.by_status.contains(&(ListElementStatus::Dropped, 1)), "Stats Test Dropped");
+    assert!(stats.by_status.contains(&(ListElementStatus::OnHold, 0)), "Stats Test Zero Count");
+    assert_eq!(35, stats.episodes_watched, "Stats Test Episodes");
+    assert_eq!(3, stats.scored, "Stats Test Scored");
+    assert_eq!(Some(70.0), stats.mean_score, "Stats Test Mean");
+    assert_eq!(Some("foo".to_string()), stats.top_tags.first().map(|tag| tag.name.clone()), "Stats Test Top Tag");
+
+    let json = serde_json::to_value(&stats)?;
+    assert_eq!(serde_json::json!(1), json["by_status"]["Watching"], "Stats Test JSON By Status");
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn model_stats_empty() -> Result<(), Box<dyn std::error::Error>> {
+
+    let db = TestDb::empty()?;
+
+    let stats = ListElementMAC::stats(&db)?;
+
+    assert_eq!(0, stats.total, "Stats Empty Test Total");
+    assert_eq!(None, stats.mean_score, "Stats Empty Test Mean");
+    assert!(stats.top_tags.is_empty(), "Stats Empty Test Top Tags");
+
+    Ok(())
+}
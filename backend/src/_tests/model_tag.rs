@@ -0,0 +1,69 @@
+use crate::model;
+use model::test_support::TestDb;
+use model::ListElementMAC;
+use super::{normalize_tag, parse_tags, TagCount, TagMAC, TagScore};
+
+#[tokio::test]
+async fn model_tag_get_all_from_path() -> Result<(), Box<dyn std::error::Error>> {
+
+    let db = TestDb::seeded()?;
+
+    let tags = TagMAC::get_all_from_path(db.path())?;
+
+    let expected = vec![
+        TagCount { name: "foo".to_string(), count: 3 },
+        TagCount { name: "bar".to_string(), count: 2 },
+        TagCount { name: "baz".to_string(), count: 1 },
+    ];
+
+    assert_eq!(expected, tags, "Tag Get All Test Counts");
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn model_tag_get_all_drops_unused() -> Result<(), Box<dyn std::error::Error>> {
+
+    let db = TestDb::seeded()?;
+
+    ListElementMAC::remove_tag(&db, 11, "baz".to_string())?;
+    let tags = TagMAC::get_all(&db)?;
+
+    assert_eq!(2, tags.len(), "Tag Drops Unused Test Length");
+    assert!(tags.iter().all(|t| t.name != "baz"), "Tag Drops Unused Test Names");
+
+    Ok(())
+}
+
+#[test]
+fn model_tag_parse_tags() {
+
+    assert_eq!(vec!["foo", "bar"], parse_tags("  foo bar\tFOO "), "Parse Tags Test");
+    assert!(parse_tags("   ").is_empty(), "Parse Tags Empty Test");
+}
+
+#[test]
+fn model_tag_normalize_tag() {
+
+    assert_eq!("slice-of-life", normalize_tag(" slice of  life "), "Normalize Tag Test");
+}
+
+#[tokio::test]
+async fn model_tag_get_average_scores_from_path() -> Result<(), Box<dyn std::error::Error>> {
+
+    let db = TestDb::seeded()?;
+
+    // unscored elements are left out of the average
+    ListElementMAC::set_score_from_path(db.path(), 10, None, model::ScoreScale::Point10)?;
+    let scores = TagMAC::get_average_scores_from_path(db.path())?;
+
+    let expected = vec![
+        TagScore { name: "baz".to_string(), average: 90.0, count: 1 },
+        TagScore { name: "bar".to_string(), average: 65.0, count: 2 },
+        TagScore { name: "foo".to_string(), average: 65.0, count: 2 },
+    ];
+
+    assert_eq!(expected, scores, "Tag Average Scores Test");
+
+    Ok(())
+}
@@ -0,0 +1,184 @@
+use crate::model;
+use model::test_support::TestDb;
+use diesel::RunQueryDsl;
+use model::element::{ListElementMAC, ListElementPatch, ListElementStatus};
+use model::query::ListQuery;
+use model::import::ImportIssue;
+use super::{Column, TransferFormat, TransferOptions};
+
+#[tokio::test]
+async fn model_transfer_export_csv() -> Result<(), Box<dyn std::error::Error>> {
+
+    let db = TestDb::seeded()?;
+
+    let options = TransferOptions {
+        columns: vec![Column::Id, Column::Title, Column::Tags, Column::Score, Column::EpisodesTotal],
+        tag_delimiter: ";".to_string(),
+        ..Default::default()
+    };
+    let mut out = Vec::new();
+    let count = ListElementMAC::export_to(&db, &mut out, TransferFormat::Csv, &options)?;
+
+    let csv = String::from_utf8(out)?;
+    let lines: Vec<&str> = csv.lines().collect();
+
+    assert_eq!(3, count, "Transfer Export CSV Test Count");
+    assert_eq!("id,title,tags,score,episodes_total", lines[0], "Transfer Export CSV Test Header");
+    assert_eq!("11,List Element 11,foo;bar;baz,90,24", lines[2], "Transfer Export CSV Test Row");
+    assert_eq!("12,List Element,foo;bar,40,", lines[3], "Transfer Export CSV Test Empty Cell");
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn model_transfer_export_json_date_format() -> Result<(), Box<dyn std::error::Error>> {
+
+    let db = TestDb::seeded()?;
+
+    let options = TransferOptions {
+        columns: vec![Column::Title, Column::Tags, Column::Ctime, Column::EpisodesTotal],
+        date_format: Some("%d/%m/%Y".to_string()),
+        ..Default::default()
+    };
+    let mut out = Vec::new();
+    ListElementMAC::export_to(&db, &mut out, TransferFormat::Json, &options)?;
+
+    let rows: Vec<serde_json::Value> = serde_json::from_slice(&out)?;
+    let ctime = rows[0]["ctime"].as_str().ok_or("no ctime")?;
+
+    assert_eq!(3, rows.len(), "Transfer Export JSON Test Length");
+    assert_eq!(serde_json::json!(["foo", "bar", "baz"]), rows[1]["tags"], "Transfer Export JSON Test Tags Array");
+    assert_eq!(serde_json::Value::Null, rows[2]["episodes_total"], "Transfer Export JSON Test Null");
+    assert!(chrono::NaiveDate::parse_from_str(ctime, "%d/%m/%Y").is_ok(), "Transfer Export JSON Test Date Format");
+
+    // written in the order asked for, and nothing else
+    let json = String::from_utf8(out)?;
+    let positions: Vec<usize> = ["\"title\"", "\"tags\"", "\"ctime\"", "\"episodes_total\""].iter().filter_map(|key| json.find(key)).collect();
+    assert!(positions.len() == 4 && positions.windows(2).all(|pair| pair[0] < pair[1]), "Transfer Export JSON Test Column Order");
+    assert!(!json.contains("\"notes\""), "Transfer Export JSON Test Columns Only");
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn model_transfer_import_csv() -> Result<(), Box<dyn std::error::Error>> {
+
+    let db = TestDb::seeded()?;
+
+    let csv = "id,title,status,tags,score,started_on,unknown_column
+10,,Completed,a|b,,,x
+,Cowboy Bebop,watching,space | jazz music,85,05/01/2019,
+,Bad Score,Watching,,150,,
+,,Dropped,,,,
+";
+    let options = TransferOptions {
+        tag_delimiter: "|".to_string(),
+        date_format: Some("%d/%m/%Y".to_string()),
+        ..Default::default()
+    };
+    let report = ListElementMAC::import_from(&db, csv.as_bytes(), TransferFormat::Csv, &options)?;
+
+    assert_eq!((1, 1), (report.created, report.updated), "Transfer Import CSV Test Counts");
+    assert!(matches!(&report.issues[0], ImportIssue::Skipped { index: 2, .. }), "Transfer Import CSV Test Bad Score");
+    assert_eq!(ImportIssue::Skipped { index: 3, reason: "no title".to_string() }, report.issues[1], "Transfer Import CSV Test No Title");
+
+    let updated = ListElementMAC::get(&db, 10)?;
+    assert_eq!("List Element 10".to_string(), updated.title, "Transfer Import CSV Test Title Kept");
+    assert_eq!(ListElementStatus::Completed, updated.status, "Transfer Import CSV Test Status");
+    assert_eq!(Some("a b".to_string()), updated.tags, "Transfer Import CSV Test Tags");
+
+    let created = ListElementMAC::get_from_title(&db, "Cowboy Bebop".to_string())?;
+    assert_eq!(Some("space jazz-music".to_string()), created.tags, "Transfer Import CSV Test Delimiter");
+    assert_eq!(Some("2019-01-05".to_string()), created.started_on, "Transfer Import CSV Test Date");
+    assert_eq!(Some(85), created.score, "Transfer Import CSV Test Score");
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn model_transfer_json_round_trip() -> Result<(), Box<dyn std::error::Error>> {
+
+    let source_db = TestDb::seeded()?;
+    let target_db = TestDb::seeded()?;
+
+    // without ids every row is new to the target
+    let options = TransferOptions {
+        columns: vec![Column::Title, Column::Notes, Column::Tags, Column::Status, Column::EpisodesWatched, Column::EpisodesTotal, Column::Score],
+        ..Default::default()
+    };
+    let mut out = Vec::new();
+    ListElementMAC::export_to(&source_db, &mut out, TransferFormat::Json, &options)?;
+
+    let report = ListElementMAC::import_from(&target_db, out.as_slice(), TransferFormat::Json, &options)?;
+    assert_eq!(3, report.created, "Transfer JSON Round Trip Test Created");
+    assert!(report.issues.is_empty(), "Transfer JSON Round Trip Test Issues");
+
+    let source = ListElementMAC::get_all(&source_db, &ListQuery::default())?;
+    let target = ListElementMAC::get_all(&target_db, &ListQuery::default())?;
+    for (original, copy) in source.iter().zip(target.iter().skip(source.len())) {
+        assert_eq!(original.title, copy.title, "Transfer JSON Round Trip Test Title");
+        assert_eq!(original.tags, copy.tags, "Transfer JSON Round Trip Test Tags");
+        assert_eq!(original.status, copy.status, "Transfer JSON Round Trip Test Status");
+        assert_eq!(original.score, copy.score, "Transfer JSON Round Trip Test Score");
+    }
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn model_transfer_import_json_invalid() -> Result<(), Box<dyn std::error::Error>> {
+
+    let db = TestDb::seeded()?;
+
+    let json = r#"[{ "title": "One" }, { "title": "Two" "#;
+    let result = ListElementMAC::import_from(&db, json.as_bytes(), TransferFormat::Json, &TransferOptions::default());
+
+    assert!(matches!(result, Err(model::Error::JsonError(_))), "Transfer Import JSON Invalid Test Error");
+    // one transaction, the first row is rolled back too
+    assert_eq!(3, ListElementMAC::get_all(&db, &ListQuery::default())?.len(), "Transfer Import JSON Invalid Test Rolled Back");
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn model_transfer_csv_empty_cells() -> Result<(), Box<dyn std::error::Error>> {
+
+    let db = TestDb::seeded()?;
+    let options = TransferOptions {
+        columns: vec![Column::Id, Column::Title, Column::Notes, Column::EpisodesTotal, Column::Score, Column::StartedOn],
+        ..Default::default()
+    };
+
+    // NULL notes come back NULL, not as ""
+    let unnoted = ListElementMAC::create(&db, ListElementPatch { title: Some("No Notes".to_string()), ..Default::default() })?;
+    let mut out = Vec::new();
+    ListElementMAC::export_to(&db, &mut out, TransferFormat::Csv, &options)?;
+    ListElementMAC::import_from(&db, out.as_slice(), TransferFormat::Csv, &options)?;
+    assert_eq!(None, ListElementMAC::get(&db, unnoted.id)?.notes, "Transfer Empty Cells Test Notes Stay Null");
+
+    let csv = "id,title,episodes_total,score,started_on\n11,,,,\n";
+    ListElementMAC::import_from(&db, csv.as_bytes(), TransferFormat::Csv, &options)?;
+
+    let cleared = ListElementMAC::get(&db, 11)?;
+    assert_eq!("List Element 11".to_string(), cleared.title, "Transfer Empty Cells Test Title Kept");
+    assert_eq!((None, None, None), (cleared.episodes_total, cleared.score, cleared.started_on), "Transfer Empty Cells Test Cleared");
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn model_transfer_import_db_error() -> Result<(), Box<dyn std::error::Error>> {
+
+    let db = TestDb::seeded()?;
+    // a row the checks let through but the db refuses
+    diesel::sql_query("CREATE TRIGGER refuse BEFORE INSERT ON list WHEN NEW.title = 'Refused' BEGIN SELECT RAISE(ABORT, 'refused'); END")
+        .execute(&mut *db.db().get()?)?;
+
+    let csv = "title\nFine\nRefused\n";
+    let result = ListElementMAC::import_from(&db, csv.as_bytes(), TransferFormat::Csv, &TransferOptions::default());
+
+    assert!(matches!(result, Err(model::Error::DieselResultError(_))), "Transfer Import Db Error Test Error");
+    assert_eq!(3, ListElementMAC::get_all(&db, &ListQuery::default())?.len(), "Transfer Import Db Error Test Rolled Back");
+
+    Ok(())
+}
@@ -0,0 +1,183 @@
+use crate::model;
+use model::test_support::TestDb;
+use model::{ListElementMAC, ListElementPatch, ListElementStatus, ListQuery, ModelManager, ScoreScale};
+use super::UndoMAC;
+
+#[tokio::test]
+async fn model_undo_delete() -> Result<(), Box<dyn std::error::Error>> {
+
+    let db = TestDb::seeded()?;
+    let element = ListElementMAC::get(&db, 11)?;
+
+    UndoMAC::delete(&db, 11)?;
+    let undone = UndoMAC::undo(&db)?;
+    let restored = ListElementMAC::get(&db, 11)?;
+
+    assert_eq!(Some("delete List Element 11".to_string()), undone.map(|operation| operation.to_string()), "Undo Delete Operation");
+    assert_eq!(None, restored.deleted_at, "Undo Delete Restored");
+    assert_eq!(element.title, restored.title, "Undo Delete Title");
+    assert_eq!(element.ctime, restored.ctime, "Undo Delete Ctime");
+    assert_eq!(Some("foo bar baz".to_string()), restored.tags, "Undo Delete Tags");
+    assert_eq!(vec![11], ListElementMAC::get_from_tags(&db, "baz".to_string(), &ListQuery::default())?.iter().map(|e| e.id).collect::<Vec<_>>(), "Undo Delete Tag Links");
+
+    UndoMAC::redo(&db)?;
+
+    assert!(ListElementMAC::get(&db, 11)?.deleted_at.is_some(), "Redo Delete");
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn model_undo_update() -> Result<(), Box<dyn std::error::Error>> {
+
+    let db = TestDb::seeded()?;
+    let patch = ListElementPatch {
+        status: Some(ListElementStatus::OnHold),
+        started_on: Some(Some("2022-10-01".to_string())),
+        tags: Some("qux".to_string()),
+        ..Default::default()
+    };
+
+    UndoMAC::update(&db, 10, &patch)?;
+    UndoMAC::undo(&db)?;
+    let undone = ListElementMAC::get(&db, 10)?;
+
+    // fields the edit filled in are empty again
+    assert_eq!(ListElementStatus::Watching, undone.status, "Undo Update Status");
+    assert_eq!(None, undone.started_on, "Undo Update Started");
+    assert_eq!(Some("foo".to_string()), undone.tags, "Undo Update Tags");
+
+    UndoMAC::redo(&db)?;
+    let redone = ListElementMAC::get(&db, 10)?;
+
+    assert_eq!(ListElementStatus::OnHold, redone.status, "Redo Update Status");
+    assert_eq!(Some("2022-10-01".to_string()), redone.started_on, "Redo Update Started");
+    assert_eq!(Some("qux".to_string()), redone.tags, "Redo Update Tags");
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn model_undo_create_across_managers() -> Result<(), Box<dyn std::error::Error>> {
+
+    let db = TestDb::empty()?;
+
+    let element = UndoMAC::create(&db, ListElementPatch { title: Some("Undo Test".to_string()), ..Default::default() })?;
+
+    // a fresh manager, the stack is kept in the db
+    let other = ModelManager::new(db.path())?;
+    UndoMAC::undo(&other)?;
+    assert!(ListElementMAC::get(&db, element.id).is_err(), "Undo Create");
+
+    UndoMAC::redo(&other)?;
+    let redone = ListElementMAC::get(&db, element.id)?;
+    assert_eq!(element.title, redone.title, "Redo Create Title");
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn model_undo_stack_order() -> Result<(), Box<dyn std::error::Error>> {
+
+    let db = TestDb::seeded()?;
+
+    assert!(UndoMAC::undo(&db)?.is_none(), "Stack Order Empty Undo");
+
+    UndoMAC::update(&db, 10, &ListElementPatch { score: Some(Some(10)), ..Default::default() })?;
+    UndoMAC::update(&db, 10, &ListElementPatch { score: Some(Some(20)), ..Default::default() })?;
+    UndoMAC::update(&db, 10, &ListElementPatch { score: Some(Some(30)), ..Default::default() })?;
+
+    UndoMAC::undo(&db)?;
+    UndoMAC::undo(&db)?;
+    assert_eq!(Some(10), ListElementMAC::get(&db, 10)?.score, "Stack Order Undo");
+
+    UndoMAC::redo(&db)?;
+    assert_eq!(Some(20), ListElementMAC::get(&db, 10)?.score, "Stack Order Redo");
+
+    // a new edit drops what is left to redo
+    UndoMAC::update(&db, 10, &ListElementPatch { score: Some(Some(40)), ..Default::default() })?;
+    assert!(UndoMAC::redo(&db)?.is_none(), "Stack Order Redo Cleared");
+
+    UndoMAC::undo(&db)?;
+    assert_eq!(Some(20), ListElementMAC::get(&db, 10)?.score, "Stack Order After Edit");
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn model_undo_conflict() -> Result<(), Box<dyn std::error::Error>> {
+
+    let db = TestDb::seeded()?;
+
+    UndoMAC::update(&db, 10, &ListElementPatch { notes: Some(Some("first".to_string())), ..Default::default() })?;
+    UndoMAC::update(&db, 12, &ListElementPatch { notes: Some(Some("second".to_string())), ..Default::default() })?;
+    // changed outside the undo stack
+    ListElementMAC::update(&db, 12, &ListElementPatch { notes: Some(Some("elsewhere".to_string())), ..Default::default() })?;
+
+    let result = UndoMAC::undo(&db);
+
+    assert!(matches!(result, Err(model::Error::UndoConflict(12))), "Conflict Error");
+    assert_eq!(Some("elsewhere".to_string()), ListElementMAC::get(&db, 12)?.notes, "Conflict Kept");
+
+    // the conflicting step is gone, the one before it still undoes
+    UndoMAC::undo(&db)?;
+    assert_eq!(Some("Lipsum Orem".to_string()), ListElementMAC::get(&db, 10)?.notes, "Conflict Next Undo");
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn model_undo_element_edits() -> Result<(), Box<dyn std::error::Error>> {
+
+    let db = TestDb::seeded()?;
+
+    // 10 is at 11 of 12 with a score of 80 and the tag foo
+    UndoMAC::increment_episodes(&db, 10)?;
+    UndoMAC::set_score(&db, 10, Some(5), ScoreScale::Point10)?;
+    UndoMAC::add_tag(&db, 10, "qux".to_string())?;
+    UndoMAC::remove_tag(&db, 10, "foo".to_string())?;
+
+    let edited = ListElementMAC::get(&db, 10)?;
+    assert_eq!((12, ListElementStatus::Completed, Some(50), Some("qux".to_string())), (edited.episodes_watched, edited.status, edited.score, edited.tags), "Element Edits Done");
+
+    for _ in 0..4 {
+        UndoMAC::undo(&db)?;
+    }
+    let undone = ListElementMAC::get(&db, 10)?;
+    assert_eq!((11, ListElementStatus::Watching, Some(80), Some("foo".to_string())), (undone.episodes_watched, undone.status, undone.score, undone.tags), "Element Edits Undone");
+
+    // purging for good can be taken back into the trash
+    UndoMAC::delete(&db, 12)?;
+    UndoMAC::purge(&db, 12)?;
+    assert!(ListElementMAC::get(&db, 12).is_err(), "Element Edits Purged");
+
+    UndoMAC::undo(&db)?;
+    assert!(ListElementMAC::get(&db, 12)?.deleted_at.is_some(), "Element Edits Purge Undone");
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn model_undo_interleaved() -> Result<(), Box<dyn std::error::Error>> {
+
+    let db = TestDb::seeded()?;
+
+    UndoMAC::set_progress(&db, 10, 5)?;
+    // a write outside the stack on another element is not in the way
+    ListElementMAC::set_score(&db, 11, Some(1), ScoreScale::Point10)?;
+    UndoMAC::add_tag(&db, 10, "qux".to_string())?;
+
+    UndoMAC::undo(&db)?;
+    UndoMAC::undo(&db)?;
+    assert_eq!(11, ListElementMAC::get(&db, 10)?.episodes_watched, "Interleaved Undone");
+    assert_eq!(Some(10), ListElementMAC::get(&db, 11)?.score, "Interleaved Other Element Kept");
+
+    // one on the same element since the step was undone blocks its redo
+    UndoMAC::redo(&db)?;
+    ListElementMAC::add_tag(&db, 10, "elsewhere".to_string())?;
+
+    assert!(matches!(UndoMAC::undo(&db), Err(model::Error::UndoConflict(10))), "Interleaved Conflict");
+    assert_eq!(5, ListElementMAC::get(&db, 10)?.episodes_watched, "Interleaved Conflict Kept");
+
+    Ok(())
+}
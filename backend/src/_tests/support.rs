@@ -0,0 +1,73 @@
+use crate::model::{self, db::init_db, migration, ModelManager};
+use diesel::connection::SimpleConnection;
+use std::ops::Deref;
+use tempfile::TempDir;
+
+// Data a test db starts with, loaded in the order given
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Fixture {
+    // the dev seed: ids 10, 11 and 12 tagged foo / bar / baz
+    Seed,
+    // three shows with notes for full text search, ids 13 to 15 after the seed
+    Search,
+}
+
+impl Fixture {
+    fn sql(&self) -> &'static str {
+        match self {
+            Fixture::Seed => migration::DEV_SEED,
+            Fixture::Search => include_str!("../../sql/fixtures/search.sql"),
+        }
+    }
+}
+
+// A migrated db in a temp dir of its own, so tests run in parallel. Removed on drop.
+// Derefs to its ModelManager, `path` is there for the `*_from_path` twins.
+pub struct TestDb {
+    mm: ModelManager,
+    _dir: TempDir,
+}
+
+impl TestDb {
+    pub fn new(fixtures: &[Fixture]) -> Result<Self, model::Error> {
+        let dir = tempfile::tempdir()?;
+        let mm = init_db(Some(&dir.path().join("test.tldb").to_string_lossy()))?;
+
+        {
+            let conn = &mut *mm.db().get()?;
+            for fixture in fixtures {
+                conn.batch_execute(fixture.sql())?;
+            }
+        }
+
+        Ok(Self { mm, _dir: dir })
+    }
+
+    pub fn empty() -> Result<Self, model::Error> {
+        Self::new(&[])
+    }
+
+    pub fn seeded() -> Result<Self, model::Error> {
+        Self::new(&[Fixture::Seed])
+    }
+
+    pub fn path(&self) -> &str {
+        self.mm.path()
+    }
+}
+
+// a `*_from_path` call caches a pool over the file, it goes with the dir
+impl Drop for TestDb {
+    fn drop(&mut self) {
+        ModelManager::close_cached(self.mm.path());
+    }
+}
+
+impl Deref for TestDb {
+    type Target = ModelManager;
+
+    fn deref(&self) -> &ModelManager {
+        &self.mm
+    }
+}
+
@@ -1,8 +1 @@
 pub mod model;
-
-#[allow(dead_code)]
-
-fn main() {
-
-}
-
@@ -0,0 +1,399 @@
+#![allow(dead_code)]
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::model;
+use crate::model::db::ModelManager;
+use crate::model::element::{ListElement, ListElementMAC, ListElementPatch, ListElementStatus};
+use crate::model::import::{self, ImportIssue, ImportReport, ParsedExport};
+use crate::model::query::ListQuery;
+use crate::model::score::{ScoreScale, SCORE_MAX};
+use crate::model::tag;
+
+// region: Types
+// The shape of AniList's MediaListCollection query, with or without the `data` wrapper
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AniListExport {
+    #[serde(rename = "MediaListCollection")]
+    pub collection: AniListCollection,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct AniListData {
+    data: AniListExport,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AniListCollection {
+    #[serde(default)]
+    pub user: Option<AniListUser>,
+    #[serde(default)]
+    pub lists: Vec<AniListList>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AniListUser {
+    pub media_list_options: AniListListOptions,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AniListListOptions {
+    pub score_format: AniListScoreFormat,
+}
+
+// How the user scores on AniList, entry scores are in this format
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum AniListScoreFormat {
+    #[serde(rename = "POINT_100")]
+    Point100,
+    // AniList's default
+    #[default]
+    #[serde(rename = "POINT_10_DECIMAL")]
+    Point10Decimal,
+    #[serde(rename = "POINT_10")]
+    Point10,
+    #[serde(rename = "POINT_5")]
+    Point5,
+    #[serde(rename = "POINT_3")]
+    Point3,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AniListList {
+    pub name: String,
+    #[serde(default)]
+    pub is_custom_list: bool,
+    #[serde(default)]
+    pub status: Option<AniListStatus>,
+    #[serde(default)]
+    pub entries: Vec<AniListEntry>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum AniListStatus {
+    Current,
+    Planning,
+    Completed,
+    Dropped,
+    Paused,
+    Repeating,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AniListEntry {
+    #[serde(default)]
+    pub media_id: Option<i32>,
+    #[serde(default)]
+    pub status: Option<AniListStatus>,
+    #[serde(default)]
+    pub score: f64,
+    #[serde(default)]
+    pub progress: i32,
+    #[serde(default)]
+    pub repeat: i32,
+    #[serde(default)]
+    pub private: bool,
+    #[serde(default)]
+    pub notes: Option<String>,
+    // custom list name -> whether the entry is on it
+    #[serde(default)]
+    pub custom_lists: BTreeMap<String, bool>,
+    #[serde(default)]
+    pub advanced_scores: BTreeMap<String, f64>,
+    #[serde(default)]
+    pub started_at: AniListDate,
+    #[serde(default)]
+    pub completed_at: AniListDate,
+    pub media: AniListMedia,
+}
+
+// AniList dates can be partial, every part is optional
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AniListDate {
+    pub year: Option<i32>,
+    pub month: Option<u32>,
+    pub day: Option<u32>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AniListMedia {
+    #[serde(default)]
+    pub id: Option<i32>,
+    #[serde(default)]
+    pub id_mal: Option<i32>,
+    pub title: AniListTitle,
+    #[serde(default)]
+    pub episodes: Option<i32>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AniListTitle {
+    #[serde(default)]
+    pub user_preferred: Option<String>,
+    #[serde(default)]
+    pub romaji: Option<String>,
+    #[serde(default)]
+    pub english: Option<String>,
+    #[serde(default)]
+    pub native: Option<String>,
+}
+// endregion: Types
+
+impl ListElementMAC {
+    // Read an AniList list export. A dry run only reports, nothing is written.
+    pub fn import_anilist(mm: &ModelManager, json: &str, dry_run: bool) -> Result<ImportReport, model::Error> {
+        let (records, issues) = parse_anilist(json)?;
+
+        import::apply(mm, records, issues, dry_run)
+    }
+
+    // The whole list as an AniList MediaListCollection, scores in POINT_100
+    pub fn export_anilist(mm: &ModelManager) -> Result<String, model::Error> {
+        write_anilist(&Self::get_all(mm, &ListQuery::default())?)
+    }
+
+    pub fn import_anilist_from_path(db_path: &str, json: &str, dry_run: bool) -> Result<ImportReport, model::Error> {
+        let mm = ModelManager::cached(db_path)?;
+
+        Self::import_anilist(&mm, json, dry_run)
+    }
+
+    pub fn export_anilist_from_path(db_path: &str) -> Result<String, model::Error> {
+        let mm = ModelManager::cached(db_path)?;
+
+        Self::export_anilist(&mm)
+    }
+}
+
+// Every entry of every list as a patch. An entry on a custom list shows up once per list
+// it is on, those are merged and the custom list names become tags.
+pub fn parse_anilist(json: &str) -> Result<ParsedExport, model::Error> {
+    let mut value: serde_json::Value = serde_json::from_str(json)?;
+    if let Some(data) = value.get_mut("data") {
+        value = data.take();
+    }
+    let export: AniListExport = serde_json::from_value(value)?;
+
+    let collection = export.collection;
+    let format = collection.user.map(|user| user.media_list_options.score_format).unwrap_or_default();
+
+    let mut records: Vec<(usize, ListElementPatch)> = Vec::new();
+    let mut issues = Vec::new();
+    // media id -> position in records
+    let mut seen: Vec<(i32, usize)> = Vec::new();
+    let entries = collection.lists.iter().flat_map(|list| list.entries.iter().map(move |entry| (list, entry)));
+
+    for (index, (list, entry)) in entries.enumerate() {
+        let media_id = entry.media.id.or(entry.media_id);
+
+        // already read from another list, only the custom list name is new
+        if let Some((_, at)) = seen.iter().find(|(id, _)| Some(*id) == media_id) {
+            if list.is_custom_list {
+                add_custom_list(&mut records[*at].1, &list.name);
+            }
+            continue;
+        }
+
+        match map_entry(entry, list, format, &mut issues) {
+            Some(record) => {
+                if let Some(media_id) = media_id {
+                    seen.push((media_id, records.len()));
+                }
+                records.push((index, record));
+            }
+            None => issues.push(ImportIssue::Skipped { index, reason: "no media title".to_string() }),
+        }
+    }
+
+    Ok((records, issues))
+}
+
+fn map_entry(entry: &AniListEntry, list: &AniListList, format: AniListScoreFormat, issues: &mut Vec<ImportIssue>) -> Option<ListElementPatch> {
+    let names = &entry.media.title;
+    let title = [&names.user_preferred, &names.romaji, &names.english, &names.native]
+        .into_iter()
+        .flatten()
+        .map(|name| name.trim())
+        .find(|name| !name.is_empty())?
+        .to_string();
+
+    let mut unmapped = |field: &'static str, value: String| {
+        issues.push(ImportIssue::Unmapped { title: title.clone(), field, value });
+    };
+
+    // nothing on our side for these
+    if entry.repeat > 0 {
+        unmapped("repeat", entry.repeat.to_string());
+    }
+    if entry.private {
+        unmapped("private", "true".to_string());
+    }
+    if entry.advanced_scores.values().any(|score| *score > 0.0) {
+        let scores = entry.advanced_scores.iter().map(|(name, score)| format!("{}: {}", name, score)).collect::<Vec<_>>();
+        unmapped("advancedScores", scores.join(", "));
+    }
+
+    let status = match entry.status.or(list.status) {
+        Some(AniListStatus::Current) => ListElementStatus::Watching,
+        Some(AniListStatus::Completed) => ListElementStatus::Completed,
+        Some(AniListStatus::Paused) => ListElementStatus::OnHold,
+        Some(AniListStatus::Dropped) => ListElementStatus::Dropped,
+        Some(AniListStatus::Planning) | None => ListElementStatus::PlanToWatch,
+        Some(AniListStatus::Repeating) => {
+            unmapped("status", "REPEATING".to_string());
+            ListElementStatus::Watching
+        }
+    };
+
+    let score = match to_score(entry.score, format) {
+        Ok(score) => score,
+        Err(_) => { unmapped("score", entry.score.to_string()); None }
+    };
+
+    let mut date = |field: &'static str, date: AniListDate| match date {
+        AniListDate { year: Some(year), month: Some(month), day: Some(day) } => {
+            match chrono::NaiveDate::from_ymd_opt(year, month, day) {
+                Some(date) => Some(date.format("%Y-%m-%d").to_string()),
+                None => { unmapped(field, format!("{}-{}-{}", year, month, day)); None }
+            }
+        }
+        AniListDate { year: None, month: None, day: None } => None,
+        partial => { unmapped(field, format!("{:?}", partial)); None }
+    };
+    let started_on = date("startedAt", entry.started_at);
+    let finished_on = date("completedAt", entry.completed_at);
+
+    // custom lists become tags
+    let tags = entry.custom_lists.iter()
+        .filter(|(_, on)| **on)
+        .map(|(name, _)| tag::normalize_tag(name))
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    let mut record = ListElementPatch {
+        title: Some(title),
+        notes: entry.notes.clone().filter(|notes| !notes.trim().is_empty()).map(Some),
+        tags: Some(tags).filter(|tags| !tags.is_empty()),
+        status: Some(status),
+        episodes_watched: Some(entry.progress.max(0)),
+        episodes_total: entry.media.episodes.filter(|total| *total > 0).map(Some),
+        score: score.map(Some),
+        started_on: started_on.map(Some),
+        finished_on: finished_on.map(Some),
+        mal_id: entry.media.id_mal.map(Some),
+    };
+
+    // the list being read counts even if the map leaves it out
+    if list.is_custom_list {
+        add_custom_list(&mut record, &list.name);
+    }
+
+    Some(record)
+}
+
+fn add_custom_list(record: &mut ListElementPatch, list_name: &str) {
+    let name = tag::normalize_tag(list_name);
+    let mut tags = tag::parse_tags(record.tags.as_deref().unwrap_or_default());
+
+    if !name.is_empty() && !tags.iter().any(|t| t.eq_ignore_ascii_case(&name)) {
+        tags.push(name);
+        record.tags = Some(tags.join(" "));
+    }
+}
+
+// AniList score in the user's format -> stored 0-100 score, 0 is unscored
+fn to_score(score: f64, format: AniListScoreFormat) -> Result<Option<i32>, model::Error> {
+    if score == 0.0 {
+        return Ok(None);
+    }
+
+    let score = match format {
+        AniListScoreFormat::Point100 => score.round() as i32,
+        AniListScoreFormat::Point10Decimal => (score * 10.0).round() as i32,
+        AniListScoreFormat::Point10 => ScoreScale::Point10.to_score(score.round() as i32)?,
+        AniListScoreFormat::Point5 => ScoreScale::Star5.to_score(score.round() as i32)?,
+        AniListScoreFormat::Point3 => ScoreScale::Smiley.to_score(score.round() as i32)?,
+    };
+
+    match (1..=SCORE_MAX).contains(&score) {
+        true => Ok(Some(score)),
+        false => Err(model::Error::InvalidValue("score", score.to_string())),
+    }
+}
+
+fn anilist_status(status: ListElementStatus) -> (AniListStatus, &'static str) {
+    match status {
+        ListElementStatus::Watching => (AniListStatus::Current, "Watching"),
+        ListElementStatus::Completed => (AniListStatus::Completed, "Completed"),
+        ListElementStatus::OnHold => (AniListStatus::Paused, "Paused"),
+        ListElementStatus::Dropped => (AniListStatus::Dropped, "Dropped"),
+        ListElementStatus::PlanToWatch => (AniListStatus::Planning, "Planning"),
+    }
+}
+
+fn to_date(date: Option<&str>) -> AniListDate {
+    match date.and_then(|date| chrono::NaiveDate::parse_from_str(date, "%Y-%m-%d").ok()) {
+        Some(date) => {
+            use chrono::Datelike;
+            AniListDate { year: Some(date.year()), month: Some(date.month()), day: Some(date.day()) }
+        }
+        None => AniListDate::default(),
+    }
+}
+
+// one list per status, tags go out as custom list flags
+fn write_anilist(elements: &[ListElement]) -> Result<String, model::Error> {
+    let mut lists: Vec<AniListList> = Vec::new();
+
+    for element in elements {
+        let (status, name) = anilist_status(element.status);
+
+        let entry = AniListEntry {
+            status: Some(status),
+            score: element.score.unwrap_or(0) as f64,
+            progress: element.episodes_watched,
+            notes: element.notes.clone(),
+            custom_lists: tag::parse_tags(element.tags.as_deref().unwrap_or_default()).into_iter().map(|name| (name, true)).collect(),
+            started_at: to_date(element.started_on.as_deref()),
+            completed_at: to_date(element.finished_on.as_deref()),
+            media: AniListMedia {
+                id_mal: element.mal_id,
+                title: AniListTitle { user_preferred: Some(element.title.clone()), ..Default::default() },
+                episodes: element.episodes_total,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        match lists.iter_mut().find(|list| list.status == Some(status)) {
+            Some(list) => list.entries.push(entry),
+            None => lists.push(AniListList { name: name.to_string(), is_custom_list: false, status: Some(status), entries: vec![entry] }),
+        }
+    }
+
+    let export = AniListData {
+        data: AniListExport {
+            collection: AniListCollection {
+                user: Some(AniListUser { media_list_options: AniListListOptions { score_format: AniListScoreFormat::Point100 } }),
+                lists,
+            },
+        },
+    };
+
+    Ok(serde_json::to_string_pretty(&export)?)
+}
+
+// region: Tests
+#[cfg(test)]
+#[path = "../_tests/model_anilist.rs"]
+mod tests;
+// endregion: Tests
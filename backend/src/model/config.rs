@@ -0,0 +1,134 @@
+#![allow(dead_code)]
+
+use serde::{Deserialize, Serialize};
+use std::{env, fs, path::{Path, PathBuf}};
+use strum_macros::Display;
+
+use crate::model;
+use crate::model::score::ScoreScale;
+
+// Environment variables, they win over the config file but lose to a --db flag
+pub const DB_ENV: &str = "THE_LIST_DB";
+pub const CONFIG_ENV: &str = "THE_LIST_CONFIG";
+
+// directory the config and the default db live in, under the platform config / data directories
+const APP_DIR: &str = "the-list";
+const CONFIG_FILE: &str = "config.toml";
+const DB_FILE: &str = "db.tldb";
+// dbs kept in `Config::recent`
+const RECENT_MAX: usize = 10;
+// days an element stays in the trash when config.toml doesn't say
+pub const TRASH_DAYS: u32 = 30;
+
+// region: Types
+// Settings shared by every frontend, read from config.toml
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Config {
+    // db to open, the data directory's db.tldb when None
+    pub db: Option<String>,
+    // dbs opened through `use_db`, most recent first
+    pub recent: Vec<String>,
+    // days trashed elements are kept before they are purged, 0 keeps them forever. TRASH_DAYS when None
+    pub trash_days: Option<u32>,
+    // scale the GUI enters and shows scores on, the last one picked there
+    pub score_scale: ScoreScale,
+}
+
+// Where the db in use was picked from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Display)]
+pub enum DbSource {
+    #[strum(to_string = "--db flag")]
+    Flag,
+    #[strum(to_string = "THE_LIST_DB")]
+    Env,
+    #[strum(to_string = "config file")]
+    Config,
+    #[strum(to_string = "default")]
+    Default,
+}
+// endregion: Types
+
+impl Config {
+    // THE_LIST_CONFIG, or config.toml in the platform config directory
+    pub fn path() -> Result<PathBuf, model::Error> {
+        if let Some(path) = env::var_os(CONFIG_ENV).filter(|path| !path.is_empty()) {
+            return Ok(PathBuf::from(path));
+        }
+
+        dirs::config_dir()
+            .map(|dir| dir.join(APP_DIR).join(CONFIG_FILE))
+            .ok_or_else(|| model::Error::ConfigError("config directory".to_string(), "the platform has none, set THE_LIST_CONFIG".to_string()))
+    }
+
+    pub fn load() -> Result<Self, model::Error> {
+        Self::load_from(&Self::path()?)
+    }
+
+    // a missing file is the default config
+    pub fn load_from(path: &Path) -> Result<Self, model::Error> {
+        match fs::read_to_string(path) {
+            Ok(contents) => toml::from_str(&contents)
+                .map_err(|ex| model::Error::ConfigError(path.display().to_string(), ex.to_string())),
+            Err(ex) if ex.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(ex) => Err(ex.into()),
+        }
+    }
+
+    pub fn save(&self) -> Result<(), model::Error> {
+        self.save_to(&Self::path()?)
+    }
+
+    pub fn save_to(&self, path: &Path) -> Result<(), model::Error> {
+        let contents = toml::to_string_pretty(self)
+            .map_err(|ex| model::Error::ConfigError(path.display().to_string(), ex.to_string()))?;
+
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(path, contents)?;
+
+        Ok(())
+    }
+
+    // the db to open: the flag, else THE_LIST_DB, else the config file, else the data directory
+    pub fn resolve_db(&self, flag: Option<&str>) -> Result<(String, DbSource), model::Error> {
+        match pick_db(flag, env::var(DB_ENV).ok().as_deref(), self.db.as_deref()) {
+            Some(picked) => Ok(picked),
+            None => Ok((default_db_path()?, DbSource::Default)),
+        }
+    }
+
+    pub fn trash_retention(&self) -> u32 {
+        self.trash_days.unwrap_or(TRASH_DAYS)
+    }
+
+    // switch to `db` for every later start, and remember it
+    pub fn use_db(&mut self, db: &str) {
+        self.recent.retain(|recent| recent != db);
+        self.recent.insert(0, db.to_string());
+        self.recent.truncate(RECENT_MAX);
+
+        self.db = Some(db.to_string());
+    }
+}
+
+// db.tldb in the platform data directory, e.g. ~/.local/share/the-list on Linux
+pub fn default_db_path() -> Result<String, model::Error> {
+    dirs::data_dir()
+        .map(|dir| dir.join(APP_DIR).join(DB_FILE).to_string_lossy().into_owned())
+        .ok_or_else(|| model::Error::ConfigError("data directory".to_string(), "the platform has none, set THE_LIST_DB".to_string()))
+}
+
+// first of flag, env and config that is set, empty values count as unset
+pub(crate) fn pick_db(flag: Option<&str>, env: Option<&str>, config: Option<&str>) -> Option<(String, DbSource)> {
+    [(flag, DbSource::Flag), (env, DbSource::Env), (config, DbSource::Config)]
+        .into_iter()
+        .find_map(|(db, source)| db.filter(|db| !db.trim().is_empty()).map(|db| (db.to_string(), source)))
+}
+
+// region: Tests
+#[cfg(test)]
+#[path = "../_tests/model_config.rs"]
+mod tests;
+// endregion: Tests
@@ -1,79 +1,190 @@
 #![allow(dead_code)]
 
 use crate::model;
+use crate::model::config::Config;
+use crate::model::migration;
 
-use std::{fs::{self, File}, path::PathBuf, io::Read, thread};
-
-use r2d2::Pool;
-use r2d2_sqlite::SqliteConnectionManager;
-use rusqlite::Result;
+use diesel::{
+    connection::SimpleConnection,
+    r2d2::{ConnectionManager, CustomizeConnection, Pool},
+    SqliteConnection,
+};
+use std::{fmt, fs, path::Path, sync::{Mutex, OnceLock}, time::Duration};
 
 // constants
+// dev and test db, init_db resolves its default through the config
 const SQL_HOST: &str = "sql/db.tldb";
 
-//sql files
-const SQL_DIR: &str = "sql/";
-const SQL_RECREATE: &str = "sql/000-recreate-db.sql";
-
-pub type Db = r2d2::Pool<r2d2_sqlite::SqliteConnectionManager>;
-
-pub fn init_db(db_path: Option<&str>) -> Result<Db, model::Error> {
-    
-    // -- Run the App sql files
-    let app_db = new_db_pool(db_path.unwrap_or(SQL_HOST),1)?;
-    let mut paths: Vec<PathBuf> = fs::read_dir(SQL_DIR)?
-        .into_iter()
-        .filter_map(|e| e.ok().map(|e| e.path()))
-        .collect();
-
-    paths.sort();
-
-    // Execute each file
-    for path in paths {
-        if let Some(path) = path.to_str() {
-            //only .sql and not the recreate
-            if path.ends_with(".sql") {
-                exec_sql(&app_db, &path)?;
+pub type DbPool = Pool<ConnectionManager<SqliteConnection>>;
+
+// region: Diesel Connection Options
+#[derive(Debug)]
+pub struct ConnectionOptions {
+    pub enable_wal: bool,
+    pub enable_foreign_keys: bool,
+    pub busy_timeout: Option<Duration>,
+}
+
+impl CustomizeConnection<SqliteConnection, diesel::r2d2::Error>
+    for ConnectionOptions
+{
+    fn on_acquire(&self, conn: &mut SqliteConnection) -> Result<(), diesel::r2d2::Error> {
+        (|| {
+            if self.enable_wal {
+                conn.batch_execute("PRAGMA journal_mode = WAL; PRAGMA synchronous = NORMAL;")?;
+            }
+            if self.enable_foreign_keys {
+                conn.batch_execute("PRAGMA foreign_keys = ON;")?;
             }
-        }
+            if let Some(d) = self.busy_timeout {
+                conn.batch_execute(&format!("PRAGMA busy_timeout = {};", d.as_millis()))?;
+            }
+            Ok(())
+        })()
+        .map_err(diesel::r2d2::Error::QueryError)
+    }
+}
+// endregion: Diesel Connection Options
+
+// changes made through a ModelManager without `with_source` are recorded as coming from here
+pub const DEFAULT_SOURCE: &str = "backend";
+
+// pools the `*_from_path` calls share, one per canonical db path, most recently used first
+static CACHED: OnceLock<Mutex<Vec<(String, ModelManager)>>> = OnceLock::new();
+// pools kept in CACHED, the least recently used one is let go past this
+const CACHED_MAX: usize = 8;
+
+// One long-lived diesel pool over a db, handed to every MAC call.
+// Clones share the pool, so it can be kept by a frontend and passed to threads.
+#[derive(Clone)]
+pub struct ModelManager {
+    db: DbPool,
+    path: String,
+    // who is making changes, written to list_history
+    source: String,
+}
+
+impl ModelManager {
+    // pool over the db as it is, see `open` to migrate it first
+    pub fn new(db_path: &str) -> Result<Self, model::Error> {
+        Ok(Self {
+            db: establish_connection(db_path)?,
+            path: db_path.to_string(),
+            source: DEFAULT_SOURCE.to_string(),
+        })
+    }
+
+    // The pool kept for this path, built on first use. Backs the `*_from_path` MAC calls,
+    // which are kept for one-off scripts, new MAC calls only take a ModelManager.
+    // Only CACHED_MAX pools are kept, a pool let go closes once its last clone is dropped.
+    pub fn cached(db_path: &str) -> Result<Self, model::Error> {
+        let key = cache_key(db_path);
+        let mut cached = lock_cached();
+
+        let mm = match cached.iter().position(|(path, _)| *path == key) {
+            Some(at) => cached.remove(at).1,
+            None => Self::new(db_path)?,
+        };
+        cached.insert(0, (key, mm.clone()));
+        cached.truncate(CACHED_MAX);
+
+        Ok(mm)
+    }
+
+    // Let go of the pool cached for this path, e.g. before the db file is moved or removed.
+    // Returns whether there was one.
+    pub fn close_cached(db_path: &str) -> bool {
+        let key = cache_key(db_path);
+        let mut cached = lock_cached();
+        let before = cached.len();
+
+        cached.retain(|(path, _)| *path != key);
+        cached.len() != before
+    }
+
+    // create and migrate the db like `init_db`
+    pub fn open(db_path: &str) -> Result<Self, model::Error> {
+        init_db(Some(db_path))
+    }
+
+    pub fn db(&self) -> &DbPool {
+        &self.db
+    }
+
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    // tag the changes made through this manager, like "gui" or "cli"
+    pub fn with_source(mut self, source: &str) -> Self {
+        self.source = source.to_string();
+        self
     }
 
-    //returning the app db location
-    Ok(app_db)
+    pub fn source(&self) -> &str {
+        &self.source
+    }
 }
 
-fn  exec_sql(db: &Db, path: &str) -> Result<(), model::Error> {
-    
-    // read file
-    let mut file = File::open(path).unwrap();
-    let mut contents = String::new();
-    file.read_to_string(&mut contents)?;
-
-    //execute sql
-    let pool = db.clone();
-    match 
-        thread::spawn( move || {
-            let conn = pool.get().unwrap();
-            conn.execute_batch(contents.as_str())
-            .expect(format!("Error parsing SQL in exec_sql").as_str());
-        }).join()
-    {
-        Ok(_) => (),
-        Err(ex) => println!("WARNING: Error in exec_sql reading {}. Cause: {:?}", path, ex)
+impl fmt::Debug for ModelManager {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ModelManager").field("path", &self.path).field("source", &self.source).field("state", &self.db.state()).finish()
+    }
+}
+
+fn lock_cached() -> std::sync::MutexGuard<'static, Vec<(String, ModelManager)>> {
+    CACHED.get_or_init(Default::default).lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+// the same file under any spelling of its path, a db not created yet as given
+fn cache_key(db_path: &str) -> String {
+    fs::canonicalize(db_path).map_or_else(|_| db_path.to_string(), |path| path.to_string_lossy().into_owned())
+}
+
+// Open the db and bring its schema up to date. Never touches the data beyond the migrations.
+// Without a path the configured db is used, a missing db and its directory are created.
+pub fn init_db(db_path: Option<&str>) -> Result<ModelManager, model::Error> {
+    let db_path = match db_path {
+        Some(db_path) => db_path.to_string(),
+        None => Config::load()?.resolve_db(None)?.0,
     };
 
-    Ok(())
+    if let Some(dir) = Path::new(&db_path).parent().filter(|dir| !dir.as_os_str().is_empty()) {
+        std::fs::create_dir_all(dir)?;
+    }
+
+    let mm = ModelManager::new(&db_path)?;
+    migration::migrate(&mut *mm.db().get()?)?;
+
+    Ok(mm)
 }
 
-fn  new_db_pool(db: &str, max_conn: u32) -> Result<Db, model::Error> {
-    let conn_string = format!("{}", db);
+// DEV ONLY -- wipe the db, migrate it from scratch and load the dev seed
+pub fn init_dev_db(db_path: Option<&str>) -> Result<ModelManager, model::Error> {
+    let mm = ModelManager::new(db_path.unwrap_or(SQL_HOST))?;
+
+    {
+        let conn = &mut *mm.db().get()?;
+        conn.batch_execute(migration::DEV_RECREATE)?;
+        migration::migrate(conn)?;
+        conn.batch_execute(migration::DEV_SEED)?;
+    }
+
+    Ok(mm)
+}
 
-    Ok(
-        Pool::builder()
-        .max_size(max_conn)
-        .build(SqliteConnectionManager::file(conn_string))
-        .unwrap()
-    )
+// One pool for every query, each connection set up with `ConnectionOptions`
+pub(crate) fn establish_connection(db_path: &str) -> Result<DbPool, model::Error> {
+    Ok(Pool::builder()
+        .max_size(5)
+        // the rest open on demand, five connections switching a new db to WAL at once wait on each other
+        .min_idle(Some(1))
+        .connection_customizer(Box::new(ConnectionOptions {
+            enable_wal: true,
+            enable_foreign_keys: true,
+            busy_timeout: Some(Duration::from_secs(30)),
+        }))
+        .build(ConnectionManager::<SqliteConnection>::new(db_path))?)
 }
 
 // region: Test
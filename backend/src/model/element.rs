@@ -1,218 +1,669 @@
 #![allow(dead_code)]
 
-use std::time::Duration;
 use diesel::{
-    connection::SimpleConnection, prelude::*, r2d2::ConnectionManager,
-    Insertable, RunQueryDsl, SqliteConnection,
+    deserialize::{self, FromSql, FromSqlRow}, expression::{AsExpression, SqlLiteral},
+    prelude::*, serialize::{self, IsNull, Output, ToSql},
+    sql_types::{Nullable, Text}, sqlite::{Sqlite, SqliteValue}, Insertable, RunQueryDsl, SqliteConnection,
 };
-use r2d2::Pool;
-use strum_macros::Display;
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+use strum_macros::{Display, EnumIter, EnumString};
 
 use crate::model;
+use crate::model::db::ModelManager;
+use crate::model::history;
+use crate::model::query::{ListQuery, SortDirection, SortField};
+use crate::model::schema::list;
+use crate::model::score::{ScoreScale, SCORE_MAX};
+use crate::model::tag::{self, TagMatch};
 
 
 // region: Types
-#[derive(Debug, Clone, Default, Queryable, Insertable)]
+#[derive(Debug, Clone, Default, Queryable, Insertable, Serialize, Deserialize)]
 #[diesel(table_name = list)]
 pub struct ListElement {
     pub id: i32,
     pub title: String,
     pub notes: Option<String>,
     pub tags: Option<String>,
-    pub status: String,
+    pub status: ListElementStatus,
     pub ctime: String, 
     pub mtime: Option<String>,
+    pub episodes_watched: i32,
+    pub episodes_total: Option<i32>,
+    // 0-100, see ScoreScale for showing it
+    pub score: Option<i32>,
+    // YYYY-MM-DD
+    pub started_on: Option<String>,
+    pub finished_on: Option<String>,
+    pub mal_id: Option<i32>,
+    // when it was moved to the trash, None while on the list
+    pub deleted_at: Option<String>,
 }
 
-#[derive(Debug, Clone, Default, Insertable, AsChangeset)]
+#[derive(Debug, Clone, Default, PartialEq, Eq, Insertable, AsChangeset, Serialize, Deserialize)]
 #[diesel(table_name = list)]
+#[serde(default)]
+// None leaves a field as it is, on the nullable ones Some(None) clears it (null in JSON)
 pub struct ListElementPatch {
     pub title: Option<String>,
-    pub notes: Option<String>,
+    #[serde(deserialize_with = "nullable", skip_serializing_if = "Option::is_none")]
+    pub notes: Option<Option<String>>,
     pub tags: Option<String>,
-    pub status: Option<String>,
+    pub status: Option<ListElementStatus>,
+    pub episodes_watched: Option<i32>,
+    #[serde(deserialize_with = "nullable", skip_serializing_if = "Option::is_none")]
+    pub episodes_total: Option<Option<i32>>,
+    #[serde(deserialize_with = "nullable", skip_serializing_if = "Option::is_none")]
+    pub score: Option<Option<i32>>,
+    #[serde(deserialize_with = "nullable", skip_serializing_if = "Option::is_none")]
+    pub started_on: Option<Option<String>>,
+    #[serde(deserialize_with = "nullable", skip_serializing_if = "Option::is_none")]
+    pub finished_on: Option<Option<String>>,
+    #[serde(deserialize_with = "nullable", skip_serializing_if = "Option::is_none")]
+    pub mal_id: Option<Option<i32>>,
+}
+
+// a field that is present is Some, even as null, a missing one falls back to the serde default
+fn nullable<'de, T: Deserialize<'de>, D: Deserializer<'de>>(deserializer: D) -> Result<Option<Option<T>>, D::Error> {
+    Option::<T>::deserialize(deserializer).map(Some)
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Display)]
+// Stored as its display string, the list_element_status table holds the same values
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Display, EnumString, EnumIter, AsExpression, FromSqlRow)]
+#[diesel(sql_type = Text)]
+#[strum(ascii_case_insensitive)]
 pub enum ListElementStatus {
-    Open,
-    Archived,
-    Closed,
+    Watching,
+    Completed,
+    #[strum(to_string = "On Hold", serialize = "OnHold")]
+    OnHold,
+    Dropped,
+    #[default]
+    #[strum(to_string = "Plan to Watch", serialize = "PlanToWatch")]
+    PlanToWatch,
 }
 
-pub type DbPool = r2d2::Pool<ConnectionManager<SqliteConnection>>;
-pub type LETuple = (i32,String,Option<String>,Option<String>,String,Option<String>,String,);
-// endregion: Types
+// serialized as its display string too
+impl Serialize for ListElementStatus {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
 
-// region: Diesel Connection Options
-#[derive(Debug)]
-pub struct ConnectionOptions {
-    pub enable_wal: bool,
-    pub enable_foreign_keys: bool,
-    pub busy_timeout: Option<Duration>,
-}
-
-impl diesel::r2d2::CustomizeConnection<SqliteConnection, diesel::r2d2::Error>
-    for ConnectionOptions
-{
-    fn on_acquire(&self, conn: &mut SqliteConnection) -> Result<(), diesel::r2d2::Error> {
-        (|| {
-            if self.enable_wal {
-                conn.batch_execute("PRAGMA journal_mode = WAL; PRAGMA synchronous = NORMAL;")?;
-            }
-            if self.enable_foreign_keys {
-                conn.batch_execute("PRAGMA foreign_keys = ON;")?;
-            }
-            if let Some(d) = self.busy_timeout {
-                conn.batch_execute(&format!("PRAGMA busy_timeout = {};", d.as_millis()))?;
-            }
-            Ok(())
-        })()
-        .map_err(diesel::r2d2::Error::QueryError)
+impl<'de> Deserialize<'de> for ListElementStatus {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let status = String::deserialize(deserializer)?;
+
+        status.parse().map_err(|_| de::Error::custom(format!("Unrecognized list element status: {}", status)))
     }
 }
-// endregion: Diesel Connection Options
 
-pub struct ListElementMAC;
+impl ToSql<Text, Sqlite> for ListElementStatus {
+    fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, Sqlite>) -> serialize::Result {
+        out.set_value(self.to_string());
+        Ok(IsNull::No)
+    }
+}
+
+impl FromSql<Text, Sqlite> for ListElementStatus {
+    fn from_sql(value: SqliteValue<'_, '_, '_>) -> deserialize::Result<Self> {
+        let status = <String as FromSql<Text, Sqlite>>::from_sql(value)?;
 
-diesel::table! {
-    list {
-        id -> Integer,
-        title ->  Text,
-        notes -> Nullable<Text>,
-        tags -> Nullable<Text>,
-        ctime -> Text,
-        mtime -> Nullable<Text>,
-        status -> Text,
+        status.parse().map_err(|_| format!("Unrecognized list element status: {}", status).into())
     }
 }
 
+impl ListElementPatch {
+    // checked before every write, a new element also needs a title
+    pub fn validate(&self, creating: bool) -> Result<(), model::Error> {
+        match &self.title {
+            Some(title) if title.trim().is_empty() => return Err(model::Error::InvalidValue("title", "is empty".to_string())),
+            None if creating => return Err(model::Error::InvalidValue("title", "is required".to_string())),
+            _ => (),
+        }
+
+        if let Some(watched) = self.episodes_watched.filter(|watched| *watched < 0) {
+            return Err(model::Error::InvalidValue("episodes_watched", format!("{} is below 0", watched)));
+        }
+        if let Some(total) = self.episodes_total.flatten().filter(|total| *total < 0) {
+            return Err(model::Error::InvalidValue("episodes_total", format!("{} is below 0", total)));
+        }
+        if let (Some(watched), Some(total)) = (self.episodes_watched, self.episodes_total.flatten()) {
+            if watched > total {
+                return Err(model::Error::InvalidValue("episodes_watched", format!("{} is more than the {} episodes", watched, total)));
+            }
+        }
+
+        if let Some(score) = self.score.flatten().filter(|score| !(0..=SCORE_MAX).contains(score)) {
+            return Err(model::Error::InvalidValue("score", format!("{} is not between 0 and {}", score, SCORE_MAX)));
+        }
+
+        Ok(())
+    }
+
+    // the stored element with this patch laid over it, what the row holds once the patch is written
+    pub fn merge(&self, element: &ListElement) -> ListElementPatch {
+        ListElementPatch {
+            title: self.title.clone().or_else(|| Some(element.title.clone())),
+            notes: self.notes.clone().or_else(|| Some(element.notes.clone())),
+            tags: self.tags.clone().or_else(|| element.tags.clone()),
+            status: self.status.or(Some(element.status)),
+            episodes_watched: self.episodes_watched.or(Some(element.episodes_watched)),
+            episodes_total: self.episodes_total.or(Some(element.episodes_total)),
+            score: self.score.or(Some(element.score)),
+            started_on: self.started_on.clone().or_else(|| Some(element.started_on.clone())),
+            finished_on: self.finished_on.clone().or_else(|| Some(element.finished_on.clone())),
+            mal_id: self.mal_id.or(Some(element.mal_id)),
+        }
+    }
+}
+
+// ctime and mtime are stored as UTC ISO-8601, works with both SQLite strftime and chrono
+pub const TIMESTAMP_FORMAT: &str = "%Y-%m-%dT%H:%M:%SZ";
+pub type LETuple = (i32,String,Option<String>,Option<String>,String,Option<String>,ListElementStatus,i32,Option<i32>,Option<i32>,Option<String>,Option<String>,Option<i32>,Option<String>,);
+// endregion: Types
+
+
+pub struct ListElementMAC;
+
 // LE Model Access Controller
 impl ListElementMAC {
-    pub fn create(db: &DbPool, patch: ListElementPatch) -> Result<ListElement, model::Error> {
-        use crate::model::element::list::dsl::list;
+    pub fn create(mm: &ModelManager, patch: ListElementPatch) -> Result<ListElement, model::Error> {
+        mm.db().get()?.transaction(|conn| insert(conn, mm.source(), &patch))
+    }
+
+    pub fn get(mm: &ModelManager, le_id: i32) -> Result<ListElement, model::Error> {
+        get_by_id(&mut *mm.db().get()?, le_id)
+    }
+
+    // everything on the list in the query's order, trashed elements are left out
+    pub fn get_all(mm: &ModelManager, query: &ListQuery) -> Result<Vec<ListElement>, model::Error> {
+        use crate::model::schema::list::dsl::list;
+        use crate::model::schema::list::*;
 
-        let row: LETuple = diesel::insert_into(list)
-            .values(&patch)
-            .get_result(&mut db.clone().get().unwrap())?;
+        let rows: Vec<LETuple> = query.apply(list.filter(deleted_at.is_null()).into_boxed())?.load(&mut mm.db().get()?)?;
 
-        Ok(parse_get_result(row))
+        Ok(rows.into_iter().map(parse_get_result).collect())
     }
 
-    pub fn get_all(db: &DbPool, limit: Option<i64>) -> Result<Vec<ListElement>, model::Error> {
-        use crate::model::element::list::dsl::list;
-       
-        let rows: Vec<LETuple>;
-        if limit.is_none() {
-            rows = list.load(&mut db.clone().get().unwrap())?;
-        }
-        else{
-            rows = list.limit(limit.unwrap()).load(&mut db.clone().get().unwrap())?;
+    // elements whose title holds `name`, see `ListQuery::name`
+    pub fn get_from_name(mm: &ModelManager, name: String, query: &ListQuery) -> Result<Vec<ListElement>, model::Error> {
+        Self::get_all(mm, &ListQuery { name: Some(name), ..query.clone() })
+    }
+
+    // how many elements on the list the query's filters let through, its order and page aside
+    pub fn count(mm: &ModelManager, query: &ListQuery) -> Result<i64, model::Error> {
+        use crate::model::schema::list::dsl::list;
+        use crate::model::schema::list::*;
+
+        Ok(query.filter(list.filter(deleted_at.is_null()).into_boxed()).count().get_result(&mut mm.db().get()?)?)
+    }
+
+    // Titles are not unique (remakes, seasons), so a title only resolves when exactly one element has it
+    pub fn get_from_title(mm: &ModelManager, le_title: String) -> Result<ListElement, model::Error> {
+        use crate::model::schema::list::dsl::list;
+        use crate::model::schema::list::*;
+
+        let mut rows: Vec<LETuple> = list.filter(title.eq(le_title.clone())).filter(deleted_at.is_null()).limit(2).load(&mut mm.db().get()?)?;
+
+        match rows.len() {
+            0 => Err(model::Error::EntityNotFound("list", le_title)),
+            1 => Ok(parse_get_result(rows.remove(0))),
+            _ => Err(model::Error::AmbiguousTitle(le_title)),
         }
+    }
+
+    // elements carrying every tag in the space separated tag_string
+    pub fn get_from_tags(mm: &ModelManager, tag_string: String, query: &ListQuery) -> Result<Vec<ListElement>, model::Error> {
+        Self::get_from_tag_list(mm, tag::parse_tags(&tag_string), TagMatch::All, query)
+    }
+
+    // the given tags take the place of the query's own, see `ListQuery::tags`
+    pub fn get_from_tag_list(mm: &ModelManager, tag_names: Vec<String>, matching: TagMatch, query: &ListQuery) -> Result<Vec<ListElement>, model::Error> {
+        Self::get_all(mm, &ListQuery { tags: tag_names, tag_match: matching, ..query.clone() })
+    }
 
-        Ok(rows.iter().map(|row| parse_get_result(row.clone())).collect())
+    pub fn add_tag(mm: &ModelManager, le_id: i32, tag_name: String) -> Result<ListElement, model::Error> {
+        mm.db().get()?.transaction(|conn| tag_row(conn, mm.source(), le_id, &tag_name, true))
     }
 
-    pub fn get_from_name(db: &DbPool, name: String) -> Result<Vec<ListElement>, model::Error> {
-        use crate::model::element::list::dsl::list;
-        use crate::model::element::list::*;
+    pub fn remove_tag(mm: &ModelManager, le_id: i32, tag_name: String) -> Result<ListElement, model::Error> {
+        mm.db().get()?.transaction(|conn| tag_row(conn, mm.source(), le_id, &tag_name, false))
+    }
 
-        let rows: Vec<LETuple> = list.filter(title.like(format!("%{}%",name))).load(&mut db.clone().get().unwrap())?;
+    pub fn update(mm: &ModelManager, le_id: i32, patch: &ListElementPatch) -> Result<ListElement, model::Error> {
+        mm.db().get()?.transaction(|conn| update_row(conn, mm.source(), le_id, patch))
+    }
 
-        Ok(rows.iter().map(|row| parse_get_result(row.clone())).collect())       
-    } 
+    pub fn update_from_title(mm: &ModelManager, le_title: String, patch: &ListElementPatch) -> Result<ListElement, model::Error> {
+        let element = Self::get_from_title(mm, le_title)?;
 
-    pub fn get_from_tags(db: &DbPool, tag_string: String) -> Result<Vec<ListElement>, model::Error> {
-        // handlie in parseing, when you add a tag, whitspace is replaced by dashes 
-        // and when you parse a search the terms a first split by white space for words 
-        // then the white space in words replaced by dahses
-        
-        use crate::model::element::list::dsl::list;
-        use crate::model::element::list::*;
+        Self::update(mm, element.id, patch)
+    }
 
-        let rows: Vec<LETuple> = list.filter(tags.like(format!("%{}%", tag_string))).load(&mut db.clone().get().unwrap())?;
+    // Move an element to the trash, see `restore` and `purge`. Trashing it again changes nothing.
+    pub fn delete(mm: &ModelManager, le_id: i32) -> Result<(), model::Error> {
+        mm.db().get()?.transaction(|conn| set_trashed(conn, mm.source(), le_id, true).map(|_| ()))
+    }
 
-        Ok(rows.iter().map(|row| parse_get_result(row.clone())).collect())       
+    // take an element back out of the trash
+    pub fn restore(mm: &ModelManager, le_id: i32) -> Result<ListElement, model::Error> {
+        mm.db().get()?.transaction(|conn| set_trashed(conn, mm.source(), le_id, false).map(|(_, after)| after))
     }
 
-    pub fn update(db: &DbPool, le_title: String, patch: &ListElementPatch) -> Result<ListElement, model::Error> {
-        
-        use crate::model::element::list::dsl::list;
-        use crate::model::element::list::*;
+    // Delete a trashed element for good, an element still on the list has to be trashed first
+    pub fn purge(mm: &ModelManager, le_id: i32) -> Result<(), model::Error> {
+        mm.db().get()?.transaction(|conn| purge_row(conn, mm.source(), le_id).map(|_| ()))
+    }
 
-        let mut patch = patch.clone();
+    // trashed elements in the query's order, `SortField::Trashed` descending has the latest trashed first
+    pub fn get_trash(mm: &ModelManager, query: &ListQuery) -> Result<Vec<ListElement>, model::Error> {
+        use crate::model::schema::list::dsl::list;
+        use crate::model::schema::list::*;
 
-        if patch.title.is_none() {
-            patch.title = Some(le_title.clone());
+        let rows: Vec<LETuple> = query.apply(list.filter(deleted_at.is_not_null()).into_boxed())?.load(&mut mm.db().get()?)?;
+
+        Ok(rows.into_iter().map(parse_get_result).collect())
+    }
+
+    // purge everything in the trash, returns how many were purged
+    pub fn empty_trash(mm: &ModelManager) -> Result<usize, model::Error> {
+        purge_trashed_before(mm, None)
+    }
+
+    // Purge what has been in the trash for more than `days`, 0 keeps the trash forever.
+    // Frontends run it on start with `Config::trash_retention`.
+    pub fn purge_expired(mm: &ModelManager, days: u32) -> Result<usize, model::Error> {
+        match days {
+            0 => Ok(0),
+            days => purge_trashed_before(mm, Some(days)),
         }
+    }
 
-        let row : LETuple = diesel::update(list).filter(title.eq(le_title)).set(patch).get_result(&mut db.clone().get().unwrap())?;
-        
-        Ok(parse_get_result(row))   
+    // deleting a title nobody has is a no-op, an ambiguous title is still refused
+    pub fn delete_from_title(mm: &ModelManager, le_title: String) -> Result<(), model::Error> {
+        match Self::get_from_title(mm, le_title) {
+            Ok(element) => Self::delete(mm, element.id),
+            Err(model::Error::EntityNotFound(_, _)) => Ok(()),
+            Err(ex) => Err(ex),
+        }
     }
 
-    pub fn delete(db: &DbPool, le_title: String) -> Result<(), model::Error> {
-        
-        use crate::model::element::list::dsl::list;
-        use crate::model::element::list::*;
+    // Set the watched count, clamped to [0, episodes_total]. Reaching the last episode completes the entry, see `update_row`.
+    pub fn set_progress(mm: &ModelManager, le_id: i32, watched: i32) -> Result<ListElement, model::Error> {
+        mm.db().get()?.transaction(|conn| progress_row(conn, mm.source(), le_id, |_| watched))
+    }
 
-        diesel::delete(list.filter(title.eq(le_title.clone())))
-            .execute(&mut db.clone().get().unwrap())
-            .expect(format!("Error Deleting Post: {}", le_title).as_str());
-        
-        Ok(())
+    pub fn increment_episodes(mm: &ModelManager, le_id: i32) -> Result<ListElement, model::Error> {
+        mm.db().get()?.transaction(|conn| progress_row(conn, mm.source(), le_id, |watched| watched + 1))
+    }
+
+    pub fn decrement_episodes(mm: &ModelManager, le_id: i32) -> Result<ListElement, model::Error> {
+        mm.db().get()?.transaction(|conn| progress_row(conn, mm.source(), le_id, |watched| watched - 1))
+    }
+
+    // value is on the given scale, None clears the score
+    pub fn set_score(mm: &ModelManager, le_id: i32, value: Option<i32>, scale: ScoreScale) -> Result<ListElement, model::Error> {
+        let new_score = value.map(|value| scale.to_score(value)).transpose()?;
+
+        mm.db().get()?.transaction(|conn| score_row(conn, mm.source(), le_id, new_score))
+    }
+
+    // scored elements, best first
+    pub fn get_top_rated(mm: &ModelManager, limit: Option<i64>) -> Result<Vec<ListElement>, model::Error> {
+        use crate::model::schema::list::dsl::list;
+        use crate::model::schema::list::*;
+
+        let query = ListQuery { limit, ..ListQuery::new(SortField::Score, SortDirection::Descending) };
+        let rows: Vec<LETuple> = query.apply(list.filter(score.is_not_null()).filter(deleted_at.is_null()).into_boxed())?.load(&mut mm.db().get()?)?;
+
+        Ok(rows.into_iter().map(parse_get_result).collect())
     }
 
     pub fn create_from_path(db_path: &str, patch: ListElementPatch) -> Result<ListElement, model::Error> {
-        let conn = establish_connection(db_path)?;
+        let mm = ModelManager::cached(db_path)?;
+
+        Self::create(&mm, patch)
+    }
+
+    pub fn get_from_path(db_path: &str, le_id: i32) -> Result<ListElement, model::Error> {
+        let mm = ModelManager::cached(db_path)?;
+
+        Self::get(&mm, le_id)
+    }
+
+    pub fn get_all_from_path(db_path: &str, query: &ListQuery) -> Result<Vec<ListElement>, model::Error> {
+        let mm = ModelManager::cached(db_path)?;
+
+        Self::get_all(&mm, query)
+    }
+
+    pub fn get_from_name_from_path(db_path: &str, name: String, query: &ListQuery) -> Result<Vec<ListElement>, model::Error> {
+        let mm = ModelManager::cached(db_path)?;
+
+        Self::get_from_name(&mm, name, query)
+    }
+
+    pub fn get_from_title_from_path(db_path: &str, le_title: String) -> Result<ListElement, model::Error> {
+        let mm = ModelManager::cached(db_path)?;
+
+        Self::get_from_title(&mm, le_title)
+    }
+
+    pub fn get_from_tags_from_path(db_path: &str, tag_string: String, query: &ListQuery) -> Result<Vec<ListElement>, model::Error> {
+        let mm = ModelManager::cached(db_path)?;
 
-        Self::create(&conn, patch)
+        Self::get_from_tags(&mm, tag_string, query)
     }
 
-    pub fn get_all_from_path(db_path: &str, limit: Option<i64>) -> Result<Vec<ListElement>, model::Error> {
-        let conn = establish_connection(db_path)?;
+    pub fn get_from_tag_list_from_path(db_path: &str, tag_names: Vec<String>, matching: TagMatch, query: &ListQuery) -> Result<Vec<ListElement>, model::Error> {
+        let mm = ModelManager::cached(db_path)?;
 
-        Self::get_all(&conn, limit)
+        Self::get_from_tag_list(&mm, tag_names, matching, query)
     }
 
-    pub fn get_from_name_from_path(db_path: &str, name: String) -> Result<Vec<ListElement>, model::Error> {
-        let conn = establish_connection(db_path)?;
+    pub fn add_tag_from_path(db_path: &str, le_id: i32, tag_name: String) -> Result<ListElement, model::Error> {
+        let mm = ModelManager::cached(db_path)?;
 
-        Self::get_from_name(&conn, name)
+        Self::add_tag(&mm, le_id, tag_name)
     }
 
-    pub fn get_from_tags_from_path(db_path: &str, tag_string: String) -> Result<Vec<ListElement>, model::Error> {
-        let conn = establish_connection(db_path)?;
+    pub fn remove_tag_from_path(db_path: &str, le_id: i32, tag_name: String) -> Result<ListElement, model::Error> {
+        let mm = ModelManager::cached(db_path)?;
 
-        Self::get_from_tags(&conn, tag_string)
+        Self::remove_tag(&mm, le_id, tag_name)
     }
 
-    pub fn update_from_path(db_path: &str, le_title: String, patch: &ListElementPatch) -> Result<ListElement, model::Error> {
-        let conn = establish_connection(db_path)?;
+    pub fn update_from_path(db_path: &str, le_id: i32, patch: &ListElementPatch) -> Result<ListElement, model::Error> {
+        let mm = ModelManager::cached(db_path)?;
 
-        Self::update(&conn, le_title, patch)
+        Self::update(&mm, le_id, patch)
     }
 
-    pub fn delete_from_path(db_path: &str, le_title: String) -> Result<(), model::Error> {
-        let conn = establish_connection(db_path)?;
+    pub fn update_from_title_from_path(db_path: &str, le_title: String, patch: &ListElementPatch) -> Result<ListElement, model::Error> {
+        let mm = ModelManager::cached(db_path)?;
 
-        Self::delete(&conn, le_title)
+        Self::update_from_title(&mm, le_title, patch)
     }
+
+    pub fn delete_from_path(db_path: &str, le_id: i32) -> Result<(), model::Error> {
+        let mm = ModelManager::cached(db_path)?;
+
+        Self::delete(&mm, le_id)
+    }
+
+    pub fn delete_from_title_from_path(db_path: &str, le_title: String) -> Result<(), model::Error> {
+        let mm = ModelManager::cached(db_path)?;
+
+        Self::delete_from_title(&mm, le_title)
+    }
+
+    pub fn set_score_from_path(db_path: &str, le_id: i32, value: Option<i32>, scale: ScoreScale) -> Result<ListElement, model::Error> {
+        let mm = ModelManager::cached(db_path)?;
+
+        Self::set_score(&mm, le_id, value, scale)
+    }
+
+    pub fn get_top_rated_from_path(db_path: &str, limit: Option<i64>) -> Result<Vec<ListElement>, model::Error> {
+        let mm = ModelManager::cached(db_path)?;
+
+        Self::get_top_rated(&mm, limit)
+    }
+
+    pub fn set_progress_from_path(db_path: &str, le_id: i32, watched: i32) -> Result<ListElement, model::Error> {
+        let mm = ModelManager::cached(db_path)?;
+
+        Self::set_progress(&mm, le_id, watched)
+    }
+
+    pub fn increment_episodes_from_path(db_path: &str, le_id: i32) -> Result<ListElement, model::Error> {
+        let mm = ModelManager::cached(db_path)?;
+
+        Self::increment_episodes(&mm, le_id)
+    }
+
+    pub fn decrement_episodes_from_path(db_path: &str, le_id: i32) -> Result<ListElement, model::Error> {
+        let mm = ModelManager::cached(db_path)?;
+
+        Self::decrement_episodes(&mm, le_id)
+    }
+}
+
+// source is what gets written to list_history, see `ModelManager::source`
+pub(crate) fn insert(conn: &mut SqliteConnection, source: &str, patch: &ListElementPatch) -> Result<ListElement, model::Error> {
+    use crate::model::schema::list::dsl::list;
+
+    patch.validate(true)?;
+
+    let row: LETuple = diesel::insert_into(list)
+        .values(patch)
+        .get_result(conn)?;
+
+    let element = match &patch.tags {
+        Some(tag_string) => sync_tags(conn, row.0, tag_string)?,
+        // ctime comes back from its column default, already formatted
+        None => parse_get_result(row),
+    };
+    history::record(conn, source, None, Some(&element))?;
+
+    Ok(element)
+}
+
+pub(crate) fn update_row(conn: &mut SqliteConnection, source: &str, le_id: i32, patch: &ListElementPatch) -> Result<ListElement, model::Error> {
+    use crate::model::schema::list::dsl::list;
+    use crate::model::schema::list::*;
+
+    let before = get_by_id(conn, le_id)?;
+
+    // diesel refuses an empty changeset, nothing to do anyway
+    if *patch == ListElementPatch::default() {
+        return Ok(before);
+    }
+
+    // checked as the whole row, a patch with only a total still has to fit the stored watched count
+    let merged = patch.merge(&before);
+    merged.validate(false)?;
+
+    // Reaching the last episode completes the entry, from any frontend.
+    // A status given with the patch wins, and a patch that leaves the progress where it was changes nothing.
+    let mut patch = patch.clone();
+    let progressed = (merged.episodes_watched, merged.episodes_total) != (Some(before.episodes_watched), Some(before.episodes_total));
+    if patch.status.is_none() && progressed && merged.episodes_total.flatten().is_some_and(|total| total > 0 && merged.episodes_watched == Some(total)) {
+        patch.status = Some(ListElementStatus::Completed);
+    }
+
+    let row: LETuple = diesel::update(list.find(le_id))
+        .set((&patch, mtime.eq(now_utc())))
+        .get_result(conn)?;
+
+    let element = match &patch.tags {
+        Some(tag_string) => sync_tags(conn, row.0, tag_string)?,
+        None => parse_get_result(row),
+    };
+    history::record(conn, source, Some(&before), Some(&element))?;
+
+    Ok(element)
+}
+
+// Move the watched count to `progress` of the stored one, clamped to [0, episodes_total], read and written in the caller's transaction
+pub(crate) fn progress_row(conn: &mut SqliteConnection, source: &str, le_id: i32, progress: impl FnOnce(i32) -> i32) -> Result<ListElement, model::Error> {
+    let element = get_by_id(conn, le_id)?;
+
+    let mut watched = progress(element.episodes_watched).max(0);
+    if let Some(total) = element.episodes_total {
+        watched = watched.min(total);
+    }
+
+    update_row(conn, source, le_id, &ListElementPatch { episodes_watched: Some(watched), ..Default::default() })
+}
+
+// add or remove one tag, the name is normalized first
+pub(crate) fn tag_row(conn: &mut SqliteConnection, source: &str, le_id: i32, tag_name: &str, add: bool) -> Result<ListElement, model::Error> {
+    let before = get_by_id(conn, le_id)?;
+    touch(conn, le_id)?;
+
+    let name = tag::normalize_tag(tag_name);
+    match add {
+        true => tag::add_tag(conn, le_id, &name)?,
+        false => tag::remove_tag(conn, le_id, &name)?,
+    }
+
+    let after = get_by_id(conn, le_id)?;
+    history::record(conn, source, Some(&before), Some(&after))?;
+
+    Ok(after)
+}
+
+// new_score is already on the 0-100 scale
+pub(crate) fn score_row(conn: &mut SqliteConnection, source: &str, le_id: i32, new_score: Option<i32>) -> Result<ListElement, model::Error> {
+    use crate::model::schema::list::dsl::list;
+    use crate::model::schema::list::*;
+
+    let before = get_by_id(conn, le_id)?;
+    diesel::update(list.find(le_id))
+        .set((score.eq(new_score), mtime.eq(now_utc())))
+        .execute(conn)?;
+
+    let after = get_by_id(conn, le_id)?;
+    history::record(conn, source, Some(&before), Some(&after))?;
+
+    Ok(after)
 }
 
-fn establish_connection(db_path: &str) -> Result<DbPool, model::Error> {
-    Ok(Pool::builder()
-        .max_size(5)
-        .connection_customizer(Box::new(ConnectionOptions {
-            enable_wal: true,
-            enable_foreign_keys: true,
-            busy_timeout: Some(Duration::from_secs(30)),
-        }))
-        .build(ConnectionManager::<SqliteConnection>::new(db_path))
-        .unwrap())
+// returns the element as it was, only a trashed element can be purged
+pub(crate) fn purge_row(conn: &mut SqliteConnection, source: &str, le_id: i32) -> Result<ListElement, model::Error> {
+    if get_by_id(conn, le_id)?.deleted_at.is_none() {
+        return Err(model::Error::InvalidValue("purge", format!("element {} is not in the trash", le_id)));
+    }
+
+    delete_row(conn, source, le_id)
+}
+
+// every element trashed more than `days` ago, or all of the trash without days
+fn purge_trashed_before(mm: &ModelManager, days: Option<u32>) -> Result<usize, model::Error> {
+    use crate::model::schema::list::dsl::list;
+    use crate::model::schema::list::*;
+
+    mm.db().get()?.transaction(|conn| {
+        let trashed = list.filter(deleted_at.is_not_null()).select(id);
+        let ids: Vec<i32> = match days {
+            None => trashed.load(conn)?,
+            Some(days) => trashed
+                .filter(deleted_at.le(diesel::dsl::sql::<Nullable<Text>>(&format!("strftime('{}', 'now', '-{} days')", TIMESTAMP_FORMAT, days))))
+                .load(conn)?,
+        };
+
+        for le_id in &ids {
+            delete_row(conn, mm.source(), *le_id)?;
+        }
+
+        Ok(ids.len())
+    })
+}
+
+// Set or clear deleted_at, returns the element before and after
+pub(crate) fn set_trashed(conn: &mut SqliteConnection, source: &str, le_id: i32, trashed: bool) -> Result<(ListElement, ListElement), model::Error> {
+    use crate::model::schema::list::dsl::list;
+    use crate::model::schema::list::*;
+
+    let before = get_by_id(conn, le_id)?;
+    if before.deleted_at.is_some() == trashed {
+        return Ok((before.clone(), before));
+    }
+
+    match trashed {
+        true => diesel::update(list.find(le_id)).set(deleted_at.eq(now_utc())).execute(conn)?,
+        false => diesel::update(list.find(le_id)).set(deleted_at.eq(None::<String>)).execute(conn)?,
+    };
+
+    let after = get_by_id(conn, le_id)?;
+    history::record(conn, source, Some(&before), Some(&after))?;
+
+    Ok((before, after))
+}
+
+// returns the element as it was before the delete
+pub(crate) fn delete_row(conn: &mut SqliteConnection, source: &str, le_id: i32) -> Result<ListElement, model::Error> {
+    use crate::model::schema::list::dsl::list;
+
+    let before = get_by_id(conn, le_id)?;
+    diesel::delete(list.find(le_id)).execute(conn)?;
+    history::record(conn, source, Some(&before), None)?;
+
+    Ok(before)
+}
+
+// Put an element back the way it was under its own id, recreated with its ctime if it is gone
+pub(crate) fn restore_row(conn: &mut SqliteConnection, source: &str, element: &ListElement) -> Result<ListElement, model::Error> {
+    use crate::model::schema::list::dsl::list;
+    use crate::model::schema::list::*;
+
+    let before = find_by_id(conn, element.id)?;
+
+    match before {
+        Some(_) => diesel::update(list.find(element.id))
+            .set((
+                title.eq(&element.title),
+                notes.eq(&element.notes),
+                status.eq(element.status),
+                episodes_watched.eq(element.episodes_watched),
+                episodes_total.eq(element.episodes_total),
+                score.eq(element.score),
+                started_on.eq(&element.started_on),
+                finished_on.eq(&element.finished_on),
+                mal_id.eq(element.mal_id),
+                deleted_at.eq(&element.deleted_at),
+                mtime.eq(now_utc()),
+            ))
+            .execute(conn)?,
+        None => diesel::insert_into(list).values(element).execute(conn)?,
+    };
+
+    let after = sync_tags(conn, element.id, element.tags.as_deref().unwrap_or_default())?;
+    history::record(conn, source, before.as_ref(), Some(&after))?;
+
+    Ok(after)
+}
+
+// the tag string is only a display copy, list_tag is what gets stored
+fn sync_tags(conn: &mut SqliteConnection, list_id: i32, tag_string: &str) -> Result<ListElement, model::Error> {
+    let names: Vec<String> = tag::parse_tags(tag_string).iter().map(|name| tag::normalize_tag(name)).collect();
+    tag::set_tags(conn, list_id, &names)?;
+
+    get_by_id(conn, list_id)
+}
+
+fn now_utc() -> SqlLiteral<Nullable<Text>> {
+    diesel::dsl::sql::<Nullable<Text>>(&format!("strftime('{}', 'now')", TIMESTAMP_FORMAT))
+}
+
+// bump mtime for changes that don't go through the list row itself, like tags
+fn touch(conn: &mut SqliteConnection, le_id: i32) -> Result<(), model::Error> {
+    use crate::model::schema::list::dsl::list;
+    use crate::model::schema::list::*;
+
+    let updated = diesel::update(list.find(le_id)).set(mtime.eq(now_utc())).execute(conn)?;
+
+    if updated == 0 {
+        return Err(model::Error::EntityNotFound("list", le_id.to_string()));
+    }
+
+    Ok(())
+}
+
+pub(crate) fn get_by_id(conn: &mut SqliteConnection, le_id: i32) -> Result<ListElement, model::Error> {
+    find_by_id(conn, le_id)?.ok_or_else(|| model::Error::EntityNotFound("list", le_id.to_string()))
+}
+
+pub(crate) fn find_by_id(conn: &mut SqliteConnection, le_id: i32) -> Result<Option<ListElement>, model::Error> {
+    use crate::model::schema::list::dsl::list;
+
+    let row: Option<LETuple> = list.find(le_id).first(conn).optional()?;
+
+    Ok(row.map(parse_get_result))
 }
 
-fn parse_get_result(row: LETuple) -> ListElement {
+pub(crate) fn parse_get_result(row: LETuple) -> ListElement {
     ListElement {
         id: row.0,
         title: row.1,
@@ -221,6 +672,13 @@ fn parse_get_result(row: LETuple) -> ListElement {
         ctime: row.4,
         mtime: row.5,
         status: row.6,
+        episodes_watched: row.7,
+        episodes_total: row.8,
+        score: row.9,
+        started_on: row.10,
+        finished_on: row.11,
+        mal_id: row.12,
+        deleted_at: row.13,
     }
 }
 
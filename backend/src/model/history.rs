@@ -0,0 +1,166 @@
+#![allow(dead_code)]
+
+use diesel::{prelude::*, RunQueryDsl, SqliteConnection};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use strum_macros::{Display, EnumString};
+
+use crate::model;
+use crate::model::db::ModelManager;
+use crate::model::element::ListElement;
+use crate::model::schema::list_history;
+
+// bookkeeping the db keeps itself, never recorded as a change
+const UNTRACKED: [&str; 3] = ["id", "ctime", "mtime"];
+
+// region: Types
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Display, EnumString, Serialize, Deserialize)]
+pub enum HistoryAction {
+    Created,
+    Updated,
+    // moved to the trash and back, Deleted is a purge
+    Trashed,
+    Restored,
+    Deleted,
+}
+
+// One field of an element before and after a change, null where it had no value
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FieldChange {
+    pub field: String,
+    pub before: Value,
+    pub after: Value,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HistoryEntry {
+    pub id: i32,
+    pub list_id: i32,
+    pub action: HistoryAction,
+    pub changes: Vec<FieldChange>,
+    pub changed_at: String,
+    // the ModelManager source that made the change, see `ModelManager::with_source`
+    pub source: String,
+}
+
+type HistoryTuple = (i32, i32, String, String, String, String);
+// endregion: Types
+
+impl FieldChange {
+    // the values as a person would type them, None where there was none
+    pub fn before_text(&self) -> Option<String> {
+        value_text(&self.before)
+    }
+
+    pub fn after_text(&self) -> Option<String> {
+        value_text(&self.after)
+    }
+}
+
+// History MAC, read only, rows are written by ListElementMAC as it changes elements
+pub struct HistoryMAC;
+
+impl HistoryMAC {
+    // changes to one element, newest first. Still there once the element is deleted.
+    pub fn get_for_element(mm: &ModelManager, le_id: i32) -> Result<Vec<HistoryEntry>, model::Error> {
+        let rows: Vec<HistoryTuple> = list_history::table
+            .filter(list_history::list_id.eq(le_id))
+            .order_by(list_history::id.desc())
+            .load(&mut mm.db().get()?)?;
+
+        rows.into_iter().map(parse_history).collect()
+    }
+
+    // the audit log across every element, newest first
+    pub fn get_all(mm: &ModelManager, limit: Option<i64>) -> Result<Vec<HistoryEntry>, model::Error> {
+        let query = list_history::table.order_by(list_history::id.desc());
+        let rows: Vec<HistoryTuple> = match limit {
+            None => query.load(&mut mm.db().get()?)?,
+            Some(limit) => query.limit(limit).load(&mut mm.db().get()?)?,
+        };
+
+        rows.into_iter().map(parse_history).collect()
+    }
+}
+
+// Write the fields that differ between the two states of an element, in the caller's transaction.
+// None before is a create and None after a delete, an update that changed nothing is not recorded.
+pub(crate) fn record(conn: &mut SqliteConnection, source: &str, before: Option<&ListElement>, after: Option<&ListElement>) -> Result<(), model::Error> {
+    let (action, list_id) = match (before, after) {
+        (None, Some(after)) => (HistoryAction::Created, after.id),
+        (Some(before), Some(after)) => match (&before.deleted_at, &after.deleted_at) {
+            (None, Some(_)) => (HistoryAction::Trashed, before.id),
+            (Some(_), None) => (HistoryAction::Restored, before.id),
+            _ => (HistoryAction::Updated, before.id),
+        },
+        (Some(before), None) => (HistoryAction::Deleted, before.id),
+        (None, None) => return Ok(()),
+    };
+
+    let changes = diff(before, after)?;
+    if changes.is_empty() && action == HistoryAction::Updated {
+        return Ok(());
+    }
+
+    diesel::insert_into(list_history::table)
+        .values((
+            list_history::list_id.eq(list_id),
+            list_history::action.eq(action.to_string()),
+            list_history::changes.eq(serde_json::to_string(&changes)?),
+            list_history::source.eq(source),
+        ))
+        .execute(conn)?;
+
+    Ok(())
+}
+
+pub(crate) fn diff(before: Option<&ListElement>, after: Option<&ListElement>) -> Result<Vec<FieldChange>, model::Error> {
+    let mut before = fields(before)?;
+    let mut after = fields(after)?;
+
+    let mut names: Vec<String> = before.keys().chain(after.keys()).cloned().collect();
+    names.sort();
+    names.dedup();
+
+    Ok(names.into_iter()
+        .filter(|name| !UNTRACKED.contains(&name.as_str()))
+        .map(|name| FieldChange {
+            before: before.remove(&name).unwrap_or(Value::Null),
+            after: after.remove(&name).unwrap_or(Value::Null),
+            field: name,
+        })
+        .filter(|change| change.before != change.after)
+        .collect())
+}
+
+fn fields(element: Option<&ListElement>) -> Result<serde_json::Map<String, Value>, model::Error> {
+    match element.map(serde_json::to_value).transpose()? {
+        Some(Value::Object(fields)) => Ok(fields),
+        _ => Ok(serde_json::Map::new()),
+    }
+}
+
+fn value_text(value: &Value) -> Option<String> {
+    match value {
+        Value::Null => None,
+        Value::String(text) => Some(text.clone()),
+        other => Some(other.to_string()),
+    }
+}
+
+fn parse_history(row: HistoryTuple) -> Result<HistoryEntry, model::Error> {
+    Ok(HistoryEntry {
+        id: row.0,
+        list_id: row.1,
+        action: row.2.parse().map_err(|_| model::Error::InvalidValue("history action", row.2.clone()))?,
+        changes: serde_json::from_str(&row.3)?,
+        changed_at: row.4,
+        source: row.5,
+    })
+}
+
+// region: Tests
+#[cfg(test)]
+#[path = "../_tests/model_history.rs"]
+mod tests;
+// endregion: Tests
@@ -0,0 +1,96 @@
+#![allow(dead_code)]
+
+use std::fmt;
+
+use diesel::Connection;
+
+use crate::model;
+use crate::model::db::ModelManager;
+use crate::model::element::{self, ListElement, ListElementMAC, ListElementPatch};
+use crate::model::query::ListQuery;
+
+// region: Types
+// Something in an export file that could not be carried over as is
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ImportIssue {
+    // the title (or MAL id) is already on the list, the record is not imported
+    Duplicate { title: String, existing_id: i32 },
+    // the same title came earlier in the file, only the first one is imported
+    DuplicateInFile { title: String },
+    // imported, but this field was dropped or replaced by its default
+    Unmapped { title: String, field: &'static str, value: String },
+    // nothing usable in the record, `index` is its position in the file from 0
+    Skipped { index: usize, reason: String },
+}
+
+// What an import did, or on a dry run what it would do
+#[derive(Debug, Clone, Default)]
+pub struct ImportReport {
+    pub dry_run: bool,
+    // the records that are (or would be) added
+    pub entries: Vec<ListElementPatch>,
+    // the elements created, empty on a dry run
+    pub created: Vec<ListElement>,
+    pub issues: Vec<ImportIssue>,
+}
+
+// The records read from an export, each after its position in the file from 0, and what could not be mapped
+pub type ParsedExport = (Vec<(usize, ListElementPatch)>, Vec<ImportIssue>);
+// endregion: Types
+
+impl ImportReport {
+    pub fn duplicates(&self) -> impl Iterator<Item = &ImportIssue> {
+        self.issues.iter().filter(|issue| matches!(issue, ImportIssue::Duplicate { .. } | ImportIssue::DuplicateInFile { .. }))
+    }
+}
+
+impl fmt::Display for ImportIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ImportIssue::Duplicate { title, existing_id } => write!(f, "{}: already on the list as [{}]", title, existing_id),
+            ImportIssue::DuplicateInFile { title } => write!(f, "{}: repeated in the file", title),
+            ImportIssue::Unmapped { title, field, value } => write!(f, "{}: could not keep {} ({})", title, field, value),
+            ImportIssue::Skipped { index, reason } => write!(f, "record {}: skipped, {}", index + 1, reason),
+        }
+    }
+}
+
+// Drop records already on the list or repeated in the file, then add the rest in one transaction.
+// Each record comes with its position in the file, records the parser dropped leave gaps.
+pub(crate) fn apply(mm: &ModelManager, records: Vec<(usize, ListElementPatch)>, mut issues: Vec<ImportIssue>, dry_run: bool) -> Result<ImportReport, model::Error> {
+    let existing = ListElementMAC::get_all(mm, &ListQuery::default())?;
+    let mut entries: Vec<ListElementPatch> = Vec::new();
+
+    for (index, record) in records {
+        let title = record.title.clone().unwrap_or_default();
+
+        if let Err(ex) = record.validate(true) {
+            issues.push(ImportIssue::Skipped { index, reason: ex.to_string() });
+            continue;
+        }
+
+        let on_list = existing.iter().find(|element| {
+            element.title.eq_ignore_ascii_case(&title) || (record.mal_id.flatten().is_some() && element.mal_id == record.mal_id.flatten())
+        });
+        if let Some(element) = on_list {
+            issues.push(ImportIssue::Duplicate { title, existing_id: element.id });
+            continue;
+        }
+
+        if entries.iter().any(|entry| entry.title.as_deref().is_some_and(|t| t.eq_ignore_ascii_case(&title))) {
+            issues.push(ImportIssue::DuplicateInFile { title });
+            continue;
+        }
+
+        entries.push(record);
+    }
+
+    let created = match dry_run {
+        true => Vec::new(),
+        false => mm.db().get()?.transaction(|conn| {
+            entries.iter().map(|entry| element::insert(conn, mm.source(), entry)).collect::<Result<Vec<_>, model::Error>>()
+        })?,
+    };
+
+    Ok(ImportReport { dry_run, entries, created, issues })
+}
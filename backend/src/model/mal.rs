@@ -0,0 +1,270 @@
+#![allow(dead_code)]
+
+use std::collections::HashMap;
+
+use chrono::NaiveDate;
+use quick_xml::{
+    events::{BytesCData, BytesDecl, BytesText, Event},
+    Reader, Writer,
+};
+
+use crate::model;
+use crate::model::db::ModelManager;
+use crate::model::element::{ListElement, ListElementMAC, ListElementPatch, ListElementStatus};
+use crate::model::import::{self, ImportIssue, ImportReport, ParsedExport};
+use crate::model::query::ListQuery;
+use crate::model::score::ScoreScale;
+use crate::model::tag;
+
+// MAL scores are 1-10, 0 is unscored
+const MAL_SCALE: ScoreScale = ScoreScale::Point10;
+// MAL writes unknown dates as zeros
+const MAL_NO_DATE: &str = "0000-00-00";
+const MAL_DATE_FORMAT: &str = "%Y-%m-%d";
+
+impl ListElementMAC {
+    // Read a MyAnimeList XML export. A dry run only reports, nothing is written.
+    pub fn import_mal(mm: &ModelManager, xml: &str, dry_run: bool) -> Result<ImportReport, model::Error> {
+        let (records, issues) = parse_mal(xml)?;
+
+        import::apply(mm, records, issues, dry_run)
+    }
+
+    // The whole list as a MyAnimeList XML export
+    pub fn export_mal(mm: &ModelManager) -> Result<String, model::Error> {
+        write_mal(&Self::get_all(mm, &ListQuery::default())?)
+    }
+
+    pub fn import_mal_from_path(db_path: &str, xml: &str, dry_run: bool) -> Result<ImportReport, model::Error> {
+        let mm = ModelManager::cached(db_path)?;
+
+        Self::import_mal(&mm, xml, dry_run)
+    }
+
+    pub fn export_mal_from_path(db_path: &str) -> Result<String, model::Error> {
+        let mm = ModelManager::cached(db_path)?;
+
+        Self::export_mal(&mm)
+    }
+}
+
+// Every <anime> in the export as a patch, with whatever could not be mapped
+pub fn parse_mal(xml: &str) -> Result<ParsedExport, model::Error> {
+    let mut reader = Reader::from_str(xml);
+    reader.trim_text(true);
+
+    let mut records = Vec::new();
+    let mut issues = Vec::new();
+    let mut found_root = false;
+
+    // child element of the current <anime> and the text read so far
+    let mut anime: Option<HashMap<String, String>> = None;
+    let mut field: Option<String> = None;
+    let mut index = 0;
+
+    loop {
+        match reader.read_event()? {
+            Event::Start(e) => {
+                let name = String::from_utf8_lossy(e.name().as_ref()).into_owned();
+                match name.as_str() {
+                    "myanimelist" => found_root = true,
+                    "anime" => anime = Some(HashMap::new()),
+                    _ if anime.is_some() => field = Some(name),
+                    _ => (),
+                }
+            }
+            Event::Text(e) => {
+                if let (Some(fields), Some(name)) = (&mut anime, &field) {
+                    fields.entry(name.clone()).or_default().push_str(&e.unescape()?);
+                }
+            }
+            Event::CData(e) => {
+                if let (Some(fields), Some(name)) = (&mut anime, &field) {
+                    fields.entry(name.clone()).or_default().push_str(&String::from_utf8_lossy(&e.into_inner()));
+                }
+            }
+            Event::End(e) => match e.name().as_ref() {
+                b"anime" => {
+                    if let Some(fields) = anime.take() {
+                        match map_anime(&fields, &mut issues) {
+                            Some(record) => records.push((index, record)),
+                            None => issues.push(ImportIssue::Skipped { index, reason: "no series_title".to_string() }),
+                        }
+                    }
+                    index += 1;
+                }
+                _ => field = None,
+            },
+            Event::Eof => break,
+            _ => (),
+        }
+    }
+
+    if !found_root {
+        return Err(model::Error::InvalidValue("mal", "no <myanimelist> element, is this a MAL export?".to_string()));
+    }
+
+    Ok((records, issues))
+}
+
+fn map_anime(fields: &HashMap<String, String>, issues: &mut Vec<ImportIssue>) -> Option<ListElementPatch> {
+    let get = |name: &str| fields.get(name).map(|value| value.trim()).filter(|value| !value.is_empty());
+
+    let title = get("series_title")?.to_string();
+    let mut unmapped = |field: &'static str, value: &str| {
+        issues.push(ImportIssue::Unmapped { title: title.clone(), field, value: value.to_string() });
+    };
+
+    let mal_id = get("series_animedb_id").and_then(|value| match value.parse::<i32>() {
+        Ok(0) => None,
+        Ok(id) => Some(id),
+        Err(_) => { unmapped("series_animedb_id", value); None }
+    });
+
+    // 0 episodes is MAL for unknown, e.g. still airing
+    let episodes_total = get("series_episodes").and_then(|value| match value.parse::<i32>() {
+        Ok(total) if total > 0 => Some(total),
+        Ok(_) => None,
+        Err(_) => { unmapped("series_episodes", value); None }
+    });
+
+    let mut episodes_watched = get("my_watched_episodes").map_or(0, |value| match value.parse::<i32>() {
+        Ok(watched) if watched >= 0 => watched,
+        _ => { unmapped("my_watched_episodes", value); 0 }
+    });
+    if let Some(total) = episodes_total.filter(|total| episodes_watched > *total) {
+        unmapped("my_watched_episodes", &episodes_watched.to_string());
+        episodes_watched = total;
+    }
+
+    let score = get("my_score").and_then(|value| match value.parse::<i32>() {
+        Ok(0) => None,
+        Ok(value) => MAL_SCALE.to_score(value).map_err(|_| unmapped("my_score", &value.to_string())).ok(),
+        Err(_) => { unmapped("my_score", value); None }
+    });
+
+    let status = match get("my_status") {
+        Some(value) => parse_status(value).unwrap_or_else(|| { unmapped("my_status", value); ListElementStatus::default() }),
+        None => ListElementStatus::default(),
+    };
+
+    let mut date = |field: &'static str| get(field).filter(|value| *value != MAL_NO_DATE).and_then(|value| {
+        match NaiveDate::parse_from_str(value, MAL_DATE_FORMAT) {
+            Ok(date) => Some(date.format(MAL_DATE_FORMAT).to_string()),
+            // partial dates like 2015-04-00 too
+            Err(_) => { unmapped(field, value); None }
+        }
+    });
+    let started_on = date("my_start_date");
+    let finished_on = date("my_finish_date");
+
+    // MAL tags are comma separated and may hold spaces
+    let tags = get("my_tags").map(|value| {
+        value.split(',').map(tag::normalize_tag).filter(|name| !name.is_empty()).collect::<Vec<_>>().join(" ")
+    });
+
+    Some(ListElementPatch {
+        title: Some(title),
+        notes: get("my_comments").map(|notes| Some(notes.to_string())),
+        tags: tags.filter(|tags| !tags.is_empty()),
+        status: Some(status),
+        episodes_watched: Some(episodes_watched),
+        episodes_total: episodes_total.map(Some),
+        score: score.map(Some),
+        started_on: started_on.map(Some),
+        finished_on: finished_on.map(Some),
+        mal_id: mal_id.map(Some),
+    })
+}
+
+// MAL writes status names, older exports the numeric codes
+fn parse_status(value: &str) -> Option<ListElementStatus> {
+    match value.to_ascii_lowercase().as_str() {
+        "watching" | "1" => Some(ListElementStatus::Watching),
+        "completed" | "2" => Some(ListElementStatus::Completed),
+        "on-hold" | "on hold" | "3" => Some(ListElementStatus::OnHold),
+        "dropped" | "4" => Some(ListElementStatus::Dropped),
+        "plan to watch" | "6" => Some(ListElementStatus::PlanToWatch),
+        _ => None,
+    }
+}
+
+fn mal_status(status: ListElementStatus) -> &'static str {
+    match status {
+        ListElementStatus::Watching => "Watching",
+        ListElementStatus::Completed => "Completed",
+        ListElementStatus::OnHold => "On-Hold",
+        ListElementStatus::Dropped => "Dropped",
+        ListElementStatus::PlanToWatch => "Plan to Watch",
+    }
+}
+
+fn write_mal(elements: &[ListElement]) -> Result<String, model::Error> {
+    let mut writer = Writer::new_with_indent(Vec::new(), b' ', 4);
+    writer.write_event(Event::Decl(BytesDecl::new("1.0", Some("UTF-8"), None)))?;
+
+    let count = |status: ListElementStatus| elements.iter().filter(|e| e.status == status).count().to_string();
+
+    writer.create_element("myanimelist").write_inner_content::<_, quick_xml::Error>(|writer| {
+        writer.create_element("myinfo").write_inner_content::<_, quick_xml::Error>(|writer| {
+            let totals = [
+                ("user_export_type", "1".to_string()),
+                ("user_total_anime", elements.len().to_string()),
+                ("user_total_watching", count(ListElementStatus::Watching)),
+                ("user_total_completed", count(ListElementStatus::Completed)),
+                ("user_total_onhold", count(ListElementStatus::OnHold)),
+                ("user_total_dropped", count(ListElementStatus::Dropped)),
+                ("user_total_plantowatch", count(ListElementStatus::PlanToWatch)),
+            ];
+            for (name, value) in totals {
+                writer.create_element(name).write_text_content(BytesText::new(&value))?;
+            }
+            Ok(())
+        })?;
+
+        for element in elements {
+            writer.create_element("anime").write_inner_content::<_, quick_xml::Error>(|writer| {
+                write_cdata(writer, "series_title", &element.title)?;
+
+                let text = [
+                    ("series_animedb_id", element.mal_id.unwrap_or(0).to_string()),
+                    ("series_episodes", element.episodes_total.unwrap_or(0).to_string()),
+                    ("my_watched_episodes", element.episodes_watched.to_string()),
+                    ("my_start_date", element.started_on.clone().unwrap_or_else(|| MAL_NO_DATE.to_string())),
+                    ("my_finish_date", element.finished_on.clone().unwrap_or_else(|| MAL_NO_DATE.to_string())),
+                    // a stored 0 stays 0, no score to MAL, the scale would round it up to 1
+                    ("my_score", element.score.filter(|score| *score > 0).map_or(0, |score| MAL_SCALE.from_score(score)).to_string()),
+                    ("my_status", mal_status(element.status).to_string()),
+                    ("update_on_import", "1".to_string()),
+                ];
+                for (name, value) in text {
+                    writer.create_element(name).write_text_content(BytesText::new(&value))?;
+                }
+
+                let tags = tag::parse_tags(element.tags.as_deref().unwrap_or_default()).join(", ");
+                write_cdata(writer, "my_comments", element.notes.as_deref().unwrap_or_default())?;
+                write_cdata(writer, "my_tags", &tags)?;
+                Ok(())
+            })?;
+        }
+        Ok(())
+    })?;
+
+    String::from_utf8(writer.into_inner()).map_err(|ex| model::Error::InvalidValue("mal", ex.to_string()))
+}
+
+// CDATA like MAL writes it, unless the text would end the section early, then as escaped text
+fn write_cdata(writer: &mut Writer<Vec<u8>>, name: &str, text: &str) -> Result<(), quick_xml::Error> {
+    match text.contains("]]>") {
+        true => writer.create_element(name).write_text_content(BytesText::new(text))?,
+        false => writer.create_element(name).write_cdata_content(BytesCData::new(text))?,
+    };
+
+    Ok(())
+}
+
+// region: Tests
+#[cfg(test)]
+#[path = "../_tests/model_mal.rs"]
+mod tests;
+// endregion: Tests
@@ -0,0 +1,161 @@
+#![allow(dead_code)]
+
+use crate::model;
+
+use diesel::{
+    connection::SimpleConnection,
+    migration::{self as diesel_migration, Migration, MigrationConnection, MigrationSource},
+    prelude::*,
+    sql_query,
+    sql_types::{Bool, Text},
+    sqlite::Sqlite,
+    SqliteConnection,
+};
+use diesel_migrations::{embed_migrations, EmbeddedMigrations, MigrationHarness};
+
+// Production migrations, one directory per version under sql/migrations.
+// Add new ones with the next number and never edit an applied one.
+pub const MIGRATIONS: EmbeddedMigrations = embed_migrations!("sql/migrations");
+
+// Dev only scripts, never run by `init_db`
+pub const DEV_RECREATE: &str = include_str!("../../sql/dev/000-recreate-db.sql");
+pub const DEV_SEED: &str = include_str!("../../sql/dev/001-dev-seed.sql");
+
+// region: Types
+// The migrations of a source up to and including a version, to bring a db to an older schema
+#[derive(Debug, Clone, Copy)]
+pub struct UpTo<S>(pub S, pub i64);
+
+impl<S: MigrationSource<Sqlite>> MigrationSource<Sqlite> for UpTo<S> {
+    fn migrations(&self) -> diesel_migration::Result<Vec<Box<dyn Migration<Sqlite>>>> {
+        Ok(self.0.migrations()?
+            .into_iter()
+            .filter(|migration| version(&**migration).is_ok_and(|version| version <= self.1))
+            .collect())
+    }
+}
+
+#[derive(QueryableByName)]
+struct ForeignKeys {
+    #[diesel(sql_type = Bool)]
+    foreign_keys: bool,
+}
+
+#[derive(QueryableByName)]
+struct ForeignKeyViolation {
+    #[diesel(sql_type = Text)]
+    table: String,
+}
+// endregion: Types
+
+// highest version this binary knows how to apply
+pub fn latest_version() -> Result<i64, model::Error> {
+    latest_in(&MIGRATIONS)
+}
+
+// highest version recorded in the db, 0 for a fresh db
+pub fn current_version(conn: &mut SqliteConnection) -> Result<i64, model::Error> {
+    adopt_schema_version(conn)?;
+
+    conn.applied_migrations()?
+        .iter()
+        .map(|applied| parse_version(&applied.to_string()))
+        .try_fold(0, |highest, version| version.map(|version| highest.max(version)))
+}
+
+// Apply every pending migration, each in its own transaction. Returns the applied versions.
+pub fn migrate(conn: &mut SqliteConnection) -> Result<Vec<i64>, model::Error> {
+    migrate_with(conn, MIGRATIONS)
+}
+
+pub(crate) fn migrate_with<S: MigrationSource<Sqlite>>(conn: &mut SqliteConnection, source: S) -> Result<Vec<i64>, model::Error> {
+    let current = current_version(conn)?;
+    let latest = latest_in(&source)?;
+
+    if current > latest {
+        return Err(model::Error::DbVersionTooNew(current, latest));
+    }
+
+    // migrations may rebuild tables, which needs foreign keys off (it can't be changed inside a transaction)
+    let foreign_keys = sql_query("PRAGMA foreign_keys").get_result::<ForeignKeys>(conn)?.foreign_keys;
+    conn.batch_execute("PRAGMA foreign_keys = OFF;")?;
+
+    let applied = apply_pending(conn, &source, current);
+
+    conn.batch_execute(&format!("PRAGMA foreign_keys = {};", if foreign_keys { "ON" } else { "OFF" }))?;
+
+    applied
+}
+
+fn apply_pending<S: MigrationSource<Sqlite>>(conn: &mut SqliteConnection, source: &S, current: i64) -> Result<Vec<i64>, model::Error> {
+    let mut pending = source.migrations()?
+        .into_iter()
+        .map(|migration| version(&*migration).map(|version| (version, migration)))
+        .collect::<Result<Vec<_>, _>>()?;
+    pending.retain(|(version, _)| *version > current);
+    pending.sort_by_key(|(version, _)| *version);
+
+    let mut applied = Vec::new();
+
+    for (version, migration) in pending {
+        conn.transaction(|conn| {
+            conn.run_migration(&*migration)
+                .map_err(|ex| model::Error::MigrationFailed(version, ex.to_string()))?;
+
+            // with foreign keys off nothing else catches a migration leaving dangling references
+            let violation = sql_query("PRAGMA foreign_key_check").load::<ForeignKeyViolation>(conn)?.into_iter().next();
+
+            match violation {
+                Some(violation) => Err(model::Error::MigrationFailed(version, format!("foreign key check failed on {}", violation.table))),
+                None => Ok(()),
+            }
+        })?;
+
+        applied.push(version);
+    }
+
+    Ok(applied)
+}
+
+// Dbs from before diesel migrations kept their versions in schema_version, carry them over once
+fn adopt_schema_version(conn: &mut SqliteConnection) -> Result<(), model::Error> {
+    let legacy = diesel::select(diesel::dsl::sql::<Bool>(
+        "EXISTS (SELECT 1 FROM sqlite_master WHERE type = 'table' AND name = 'schema_version')",
+    )).get_result::<bool>(conn)?;
+
+    if !legacy {
+        return Ok(());
+    }
+
+    conn.setup()?;
+    conn.transaction(|conn| {
+        conn.batch_execute("
+            INSERT OR IGNORE INTO __diesel_schema_migrations (version, run_on)
+                SELECT printf('%03d', version), applied_at FROM schema_version;
+            DROP TABLE schema_version;")
+    })?;
+
+    Ok(())
+}
+
+fn latest_in<S: MigrationSource<Sqlite>>(source: &S) -> Result<i64, model::Error> {
+    source.migrations()?
+        .iter()
+        .map(|migration| version(&**migration))
+        .try_fold(0, |highest, version| version.map(|version| highest.max(version)))
+}
+
+// the number a migration directory starts with, 003 for 003_normalize_tags
+fn version(migration: &dyn Migration<Sqlite>) -> Result<i64, model::Error> {
+    parse_version(&migration.name().version().to_string())
+}
+
+fn parse_version(version: &str) -> Result<i64, model::Error> {
+    version.parse().map_err(|_| model::Error::InvalidValue("migration version", version.to_string()))
+}
+
+// region: Test
+#[cfg(test)]
+#[path = "../_tests/model_migration.rs"]
+mod tests;
+// endregion: Test
@@ -1,9 +1,42 @@
+mod anilist;
+mod config;
 mod db;
-mod element; 
+mod element;
+mod history;
+mod import;
+mod mal;
+mod migration;
+mod query;
+mod schema;
+mod score;
+mod search;
+mod stats;
+mod tag;
+mod transfer;
+mod undo;
+
+// region: Tests
+#[cfg(test)]
+#[path = "../_tests/support.rs"]
+mod test_support;
+// endregion: Tests
 
 //re-export
-pub use element::{ ListElementMAC, ListElementStatus, ListElement};
-pub use db::init_db;
+pub use element::{ ListElementMAC, ListElementStatus, ListElement, ListElementPatch, TIMESTAMP_FORMAT};
+pub use config::{default_db_path, Config, DbSource, CONFIG_ENV, DB_ENV, TRASH_DAYS};
+pub use db::{init_db, init_dev_db, ModelManager, DEFAULT_SOURCE};
+pub use history::{FieldChange, HistoryAction, HistoryEntry, HistoryMAC};
+pub use migration::MIGRATIONS;
+pub use score::{ScoreScale, SCORE_MAX};
+pub use tag::{TagCount, TagMAC, TagMatch, TagScore};
+pub use anilist::{parse_anilist, AniListExport, AniListScoreFormat};
+pub use import::{ImportIssue, ImportReport, ParsedExport};
+pub use mal::parse_mal;
+pub use transfer::{Column, TransferFormat, TransferOptions, TransferReport};
+pub use stats::Stats;
+pub use query::{Cursor, ListQuery, SortDirection, SortField, SortKey};
+pub use search::{split_highlights, SearchHit, HIGHLIGHT_CLOSE, HIGHLIGHT_OPEN};
+pub use undo::{Operation, UndoMAC, UNDO_DEPTH};
 
 #[derive(thiserror::Error, Debug)]
 #[allow(dead_code)]
@@ -12,8 +45,38 @@ pub enum Error {
     #[error("Entity Not Found - {0}[{1}] ")]
 	EntityNotFound(&'static str, String),
 
+    #[error("Ambiguous Title - more than one element is titled {0}, use its id")]
+    AmbiguousTitle(String),
+
+    #[error("Invalid Value - {0}[{1}] ")]
+    InvalidValue(&'static str, String),
+
+    #[error("Database Version Too New - db is at version {0}, this build only knows up to {1}")]
+    DbVersionTooNew(i64, i64),
+
+    #[error("Migration Failed - {0}[{1}] ")]
+    MigrationFailed(i64, String),
+
+    #[error("Migration Error - {0}")]
+    MigrationError(#[from] Box<dyn std::error::Error + Send + Sync>),
+
+    #[error("Undo Conflict - element {0} was changed since, the step was dropped")]
+    UndoConflict(i32),
+
+    #[error("Config Error - {0}[{1}] ")]
+    ConfigError(String, String),
+
+    #[error(transparent)]
+    CsvError(#[from] csv::Error),
+
     #[error(transparent)]
-    RusqliteError(#[from] rusqlite::Error),
+    JsonError(#[from] serde_json::Error),
+
+    #[error(transparent)]
+    XmlError(#[from] quick_xml::Error),
+
+    #[error("Database Unavailable - {0}")]
+    PoolError(#[from] diesel::r2d2::PoolError),
 
     #[error(transparent)]
     IOError(#[from] std::io::Error),
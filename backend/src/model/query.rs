@@ -0,0 +1,234 @@
+#![allow(dead_code)]
+
+use diesel::{
+    dsl::{count, sql},
+    prelude::*,
+    sql_types::{BigInt, Bool, Integer, Text},
+    sqlite::Sqlite,
+};
+use serde::{Deserialize, Serialize};
+use strum::IntoEnumIterator;
+use strum_macros::{Display, EnumIter, EnumString};
+
+use crate::model;
+use crate::model::element::{ListElement, ListElementStatus};
+use crate::model::schema::{list, list_tag, tag};
+use crate::model::tag::{unique_tags, TagMatch};
+
+// region: Types
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Display, EnumString, EnumIter, Serialize, Deserialize)]
+#[strum(ascii_case_insensitive)]
+pub enum SortField {
+    Title,
+    // when it was added, the default keeps the order elements went in
+    #[default]
+    #[strum(to_string = "Added", serialize = "Ctime")]
+    Ctime,
+    // an element never edited counts as modified when it was added
+    #[strum(to_string = "Modified", serialize = "Mtime")]
+    Mtime,
+    // in ListElementStatus order, Watching first
+    Status,
+    // unscored elements sort below every score
+    Score,
+    // when it went to the trash, only for trashed elements
+    Trashed,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Display, EnumString, EnumIter, Serialize, Deserialize)]
+#[strum(ascii_case_insensitive)]
+pub enum SortDirection {
+    #[default]
+    #[strum(serialize = "asc")]
+    Ascending,
+    #[strum(serialize = "desc")]
+    Descending,
+}
+
+// the value an element sorts by, as the db compares it
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SortKey {
+    Text(String),
+    Number(i64),
+}
+
+// Where the last page ended, the next one starts right after this element.
+// Unlike an offset it stays put when elements are added or removed before it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Cursor {
+    order_by: SortField,
+    key: SortKey,
+    id: i32,
+}
+
+// Which elements a list query returns, in what order and which part of them.
+// Ties are broken by id, so every element has one place in the order.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ListQuery {
+    // part of the title, any case
+    pub name: Option<String>,
+    pub status: Option<ListElementStatus>,
+    // carrying these tags, every one of them or any per `tag_match`, no tags is no filter
+    pub tags: Vec<String>,
+    pub tag_match: TagMatch,
+    pub order_by: SortField,
+    pub direction: SortDirection,
+    // elements skipped before the first one returned, counted after the cursor
+    pub offset: Option<i64>,
+    pub limit: Option<i64>,
+    // only elements after this one, see `ListQuery::after`
+    pub cursor: Option<Cursor>,
+}
+// endregion: Types
+
+impl SortField {
+    // the column as compared in SQL, see `SortField::key` for the same value from an element
+    fn sql(&self) -> String {
+        match self {
+            SortField::Title => "title COLLATE NOCASE".to_string(),
+            SortField::Ctime => "ctime".to_string(),
+            SortField::Mtime => "COALESCE(mtime, ctime)".to_string(),
+            SortField::Status => {
+                let ranks: String = ListElementStatus::iter().enumerate()
+                    .map(|(rank, status)| format!("WHEN '{}' THEN {} ", status, rank))
+                    .collect();
+
+                format!("(CASE status {}END)", ranks)
+            }
+            SortField::Score => "COALESCE(score, -1)".to_string(),
+            SortField::Trashed => "COALESCE(deleted_at, '')".to_string(),
+        }
+    }
+
+    pub fn key(&self, element: &ListElement) -> SortKey {
+        match self {
+            SortField::Title => SortKey::Text(element.title.clone()),
+            SortField::Ctime => SortKey::Text(element.ctime.clone()),
+            SortField::Mtime => SortKey::Text(element.mtime.clone().unwrap_or_else(|| element.ctime.clone())),
+            SortField::Status => SortKey::Number(ListElementStatus::iter().position(|status| status == element.status).unwrap_or_default() as i64),
+            SortField::Score => SortKey::Number(element.score.unwrap_or(-1).into()),
+            SortField::Trashed => SortKey::Text(element.deleted_at.clone().unwrap_or_default()),
+        }
+    }
+}
+
+impl SortDirection {
+    fn sql(&self) -> &'static str {
+        match self {
+            SortDirection::Ascending => "ASC",
+            SortDirection::Descending => "DESC",
+        }
+    }
+}
+
+impl Cursor {
+    pub fn new(order_by: SortField, element: &ListElement) -> Self {
+        Self { order_by, key: order_by.key(element), id: element.id }
+    }
+
+    pub fn id(&self) -> i32 {
+        self.id
+    }
+}
+
+impl ListQuery {
+    pub fn new(order_by: SortField, direction: SortDirection) -> Self {
+        Self { order_by, direction, ..Default::default() }
+    }
+
+    // the first `limit` elements
+    pub fn limited(limit: Option<i64>) -> Self {
+        Self { limit, ..Default::default() }
+    }
+
+    // the same query continued after `last`, the element a previous page ended on
+    pub fn after(&self, last: &ListElement) -> Self {
+        Self { offset: None, cursor: Some(Cursor::new(self.order_by, last)), ..self.clone() }
+    }
+
+    // the tags to match, deduped, and how many of them an element needs
+    pub(crate) fn tag_filter(&self) -> Option<(Vec<String>, i64)> {
+        let names = unique_tags(&self.tags);
+        let needed = match self.tag_match {
+            TagMatch::All => names.len() as i64,
+            TagMatch::Any => 1,
+        };
+
+        (!names.is_empty()).then_some((names, needed))
+    }
+
+    // Narrow a query on list to the elements the filters let through
+    pub(crate) fn filter<'a>(&self, mut query: list::BoxedQuery<'a, Sqlite>) -> list::BoxedQuery<'a, Sqlite> {
+        if let Some(name) = &self.name {
+            query = query.filter(list::title.like(like_pattern(name)).escape(LIKE_ESCAPE));
+        }
+        if let Some(status) = self.status {
+            query = query.filter(list::status.eq(status));
+        }
+        if let Some((names, needed)) = self.tag_filter() {
+            let tagged = list_tag::table
+                .inner_join(tag::table)
+                .filter(tag::name.eq_any(names))
+                .group_by(list_tag::list_id)
+                .having(count(list_tag::tag_id).ge(needed))
+                .select(list_tag::list_id);
+            query = query.filter(list::id.eq_any(tagged));
+        }
+
+        query
+    }
+
+    // Filter, order and cut a query on list, a cursor taken for another order is refused
+    pub(crate) fn apply<'a>(&self, query: list::BoxedQuery<'a, Sqlite>) -> Result<list::BoxedQuery<'a, Sqlite>, model::Error> {
+        let key = self.order_by.sql();
+        let direction = self.direction.sql();
+
+        let mut query = self.filter(query).order_by(sql::<Text>(&format!("{} {}, id {}", key, direction, direction)));
+
+        if let Some(cursor) = &self.cursor {
+            if cursor.order_by != self.order_by {
+                return Err(model::Error::InvalidValue("cursor", format!("taken for {} order, the query is ordered by {}", cursor.order_by, self.order_by)));
+            }
+
+            let comparison = match self.direction {
+                SortDirection::Ascending => ">",
+                SortDirection::Descending => "<",
+            };
+            let after = sql::<Bool>(&format!("({}, id) {} (", key, comparison));
+            query = match &cursor.key {
+                SortKey::Text(value) => query.filter(after.bind::<Text, _>(value.clone()).sql(", ").bind::<Integer, _>(cursor.id).sql(")")),
+                SortKey::Number(value) => query.filter(after.bind::<BigInt, _>(*value).sql(", ").bind::<Integer, _>(cursor.id).sql(")")),
+            };
+        }
+
+        // sqlite only takes an offset after a limit, -1 is no limit
+        if self.limit.is_some() || self.offset.is_some() {
+            query = query.limit(self.limit.unwrap_or(-1));
+        }
+        if let Some(offset) = self.offset {
+            query = query.offset(offset);
+        }
+
+        Ok(query)
+    }
+}
+
+// escapes the LIKE wildcards in `like_pattern`, SQL written by hand says ESCAPE '\'
+pub(crate) const LIKE_ESCAPE: char = '\\';
+
+// LIKE pattern for titles holding `text`, its own % and _ match only themselves
+pub(crate) fn like_pattern(text: &str) -> String {
+    let escaped: String = text.chars().flat_map(|c| match c {
+        '%' | '_' | LIKE_ESCAPE => vec![LIKE_ESCAPE, c],
+        c => vec![c],
+    }).collect();
+
+    format!("%{}%", escaped)
+}
+
+// region: Tests
+#[cfg(test)]
+#[path = "../_tests/model_query.rs"]
+mod tests;
+// endregion: Tests
@@ -0,0 +1,99 @@
+#![allow(dead_code)]
+
+use serde::{Deserialize, Serialize};
+use strum_macros::{Display, EnumIter, EnumString};
+
+use crate::model;
+
+// Scores are stored 0-100, a scale only changes how they are entered and shown
+pub const SCORE_MAX: i32 = 100;
+
+// smiley faces, from the saddest, and the stored score each one maps to
+const SMILEYS: [&str; 3] = [":(", ":|", ":)"];
+const SMILEY_SCORES: [i32; 3] = [35, 60, 85];
+
+// region: Types
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Display, EnumString, EnumIter, Serialize, Deserialize)]
+#[strum(ascii_case_insensitive)]
+pub enum ScoreScale {
+    #[default]
+    #[strum(to_string = "10 Point", serialize = "Point10")]
+    Point10,
+    #[strum(to_string = "100 Point", serialize = "Point100")]
+    Point100,
+    #[strum(to_string = "5 Star", serialize = "Star5")]
+    Star5,
+    Smiley,
+}
+// endregion: Types
+
+impl ScoreScale {
+    // highest value on this scale, the lowest is always 1
+    pub fn max(&self) -> i32 {
+        match self {
+            ScoreScale::Point10 => 10,
+            ScoreScale::Point100 => 100,
+            ScoreScale::Star5 => 5,
+            ScoreScale::Smiley => SMILEYS.len() as i32,
+        }
+    }
+
+    // value on this scale -> stored 0-100 score
+    pub fn to_score(&self, value: i32) -> Result<i32, model::Error> {
+        if !(1..=self.max()).contains(&value) {
+            return Err(model::Error::InvalidValue("score", format!("{} is not on the {} scale", value, self)));
+        }
+
+        Ok(match self {
+            ScoreScale::Smiley => SMILEY_SCORES[value as usize - 1],
+            _ => value * SCORE_MAX / self.max(),
+        })
+    }
+
+    // stored 0-100 score -> nearest value on this scale, never below 1
+    pub fn from_score(&self, score: i32) -> i32 {
+        let score = score.clamp(0, SCORE_MAX);
+
+        let value = match self {
+            ScoreScale::Smiley => match score {
+                s if s <= 35 => 1,
+                s if s <= 60 => 2,
+                _ => 3,
+            },
+            // round half up
+            _ => (score * self.max() + SCORE_MAX / 2) / SCORE_MAX,
+        };
+
+        value.max(1)
+    }
+
+    // move a value from one scale to another
+    pub fn convert(value: i32, from: ScoreScale, to: ScoreScale) -> Result<i32, model::Error> {
+        Ok(to.from_score(from.to_score(value)?))
+    }
+
+    // one symbol per value, for widgets that show every value on the scale
+    pub fn symbol(&self, value: i32) -> String {
+        match self {
+            ScoreScale::Star5 => "\u{2605}".to_string(),
+            ScoreScale::Smiley => SMILEYS[(value.clamp(1, 3) - 1) as usize].to_string(),
+            _ => value.to_string(),
+        }
+    }
+
+    pub fn format(&self, score: i32) -> String {
+        let value = self.from_score(score);
+
+        match self {
+            ScoreScale::Star5 => format!("{}{}", "\u{2605}".repeat(value as usize), "\u{2606}".repeat((self.max() - value) as usize)),
+            ScoreScale::Smiley => self.symbol(value),
+            _ => format!("{}/{}", value, self.max()),
+        }
+    }
+}
+
+// region: Tests
+#[cfg(test)]
+#[path = "../_tests/model_score.rs"]
+mod tests;
+// endregion: Tests
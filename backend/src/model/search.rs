@@ -0,0 +1,170 @@
+#![allow(dead_code)]
+
+use diesel::{
+    prelude::*,
+    sql_types::{BigInt, Double, Integer, Nullable, Text},
+    RunQueryDsl,
+};
+
+use serde::Serialize;
+
+use crate::model;
+use crate::model::db::ModelManager;
+use crate::model::element::{parse_get_result, ListElement, ListElementMAC, LETuple};
+use crate::model::query::{like_pattern, ListQuery};
+
+// Markers wrapped around matched terms in `SearchHit::title` and `SearchHit::snippet`
+pub const HIGHLIGHT_OPEN: &str = "\u{2}";
+pub const HIGHLIGHT_CLOSE: &str = "\u{3}";
+const SNIPPET_ELLIPSIS: &str = "\u{2026}";
+const SNIPPET_TOKENS: i32 = 16;
+
+// a title match counts for more than a notes match
+const TITLE_WEIGHT: f64 = 10.0;
+const NOTES_WEIGHT: f64 = 1.0;
+
+// region: Types
+#[derive(Debug, Clone, Serialize)]
+pub struct SearchHit {
+    pub element: ListElement,
+    // bm25 score, lower is a better match
+    pub rank: f64,
+    // the full title with matches highlighted
+    pub title: String,
+    // a short window of the notes around the best match, highlighted
+    pub snippet: String,
+}
+
+#[derive(Debug, QueryableByName)]
+struct SearchRow {
+    #[diesel(sql_type = Integer)]
+    id: i32,
+    #[diesel(sql_type = Double)]
+    rank: f64,
+    #[diesel(sql_type = Text)]
+    title: String,
+    #[diesel(sql_type = Text)]
+    snippet: String,
+}
+// endregion: Types
+
+impl ListElementMAC {
+    // Search titles and notes, best match first.
+    // Bare words match as prefixes, "quoted text" matches the exact phrase.
+    // The query's filters, offset and limit apply, its order and cursor don't, hits are always in rank order,
+    // equal ranks by id so pages neither repeat nor skip a hit.
+    pub fn search(mm: &ModelManager, text: String, query: &ListQuery) -> Result<Vec<SearchHit>, model::Error> {
+        use crate::model::schema::list::dsl::list;
+        use crate::model::schema::list::*;
+
+        let fts_query = build_fts_query(&text);
+        if fts_query.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let (tag_names, tags_needed) = query.tag_filter().unwrap_or_default();
+        let conn = &mut mm.db().get()?;
+
+        let hits: Vec<SearchRow> = diesel::sql_query(format!(
+            "SELECT list_fts.rowid AS id,
+                bm25(list_fts, {TITLE_WEIGHT:.1}, {NOTES_WEIGHT:.1}) AS rank,
+                highlight(list_fts, 0, ?1, ?2) AS title,
+                coalesce(snippet(list_fts, 1, ?1, ?2, ?3, {SNIPPET_TOKENS}), '') AS snippet
+            FROM list_fts JOIN list ON list.id = list_fts.rowid
+            WHERE list_fts MATCH ?4 AND list.deleted_at IS NULL
+                AND (?6 IS NULL OR list.title LIKE ?6 ESCAPE '\\')
+                AND (?7 IS NULL OR list.status = ?7)
+                AND (?9 = 0 OR list.id IN (
+                    SELECT list_tag.list_id FROM list_tag JOIN tag ON tag.id = list_tag.tag_id
+                    WHERE tag.name IN (SELECT value FROM json_each(?8))
+                    GROUP BY list_tag.list_id HAVING count(list_tag.tag_id) >= ?9))
+            ORDER BY rank, list.id LIMIT ?5 OFFSET ?10"))
+            .bind::<Text, _>(HIGHLIGHT_OPEN)
+            .bind::<Text, _>(HIGHLIGHT_CLOSE)
+            .bind::<Text, _>(SNIPPET_ELLIPSIS)
+            .bind::<Text, _>(&fts_query)
+            .bind::<BigInt, _>(query.limit.unwrap_or(-1))
+            .bind::<Nullable<Text>, _>(query.name.as_deref().map(like_pattern))
+            .bind::<Nullable<Text>, _>(query.status.map(|wanted| wanted.to_string()))
+            .bind::<Text, _>(serde_json::to_string(&tag_names)?)
+            .bind::<BigInt, _>(tags_needed)
+            .bind::<BigInt, _>(query.offset.unwrap_or(0))
+            .load(conn)?;
+
+        let rows: Vec<LETuple> = list.filter(id.eq_any(hits.iter().map(|hit| hit.id))).load(conn)?;
+        let elements: Vec<ListElement> = rows.into_iter().map(parse_get_result).collect();
+
+        Ok(hits.into_iter()
+            .filter_map(|hit| {
+                let element = elements.iter().find(|e| e.id == hit.id)?.clone();
+                Some(SearchHit { element, rank: hit.rank, title: hit.title, snippet: hit.snippet })
+            })
+            .collect())
+    }
+
+    pub fn search_from_path(db_path: &str, text: String, query: &ListQuery) -> Result<Vec<SearchHit>, model::Error> {
+        let mm = ModelManager::cached(db_path)?;
+
+        Self::search(&mm, text, query)
+    }
+}
+
+// Turn user input into an FTS5 query. Every term is quoted so punctuation in titles
+// can't be read as FTS5 syntax, bare words get a prefix `*`, terms are ANDed.
+pub fn build_fts_query(input: &str) -> String {
+    let mut terms: Vec<String> = Vec::new();
+    let mut rest = input;
+
+    while let Some(start) = rest.find('"') {
+        terms.extend(prefix_terms(&rest[..start]));
+
+        let after = &rest[start + 1..];
+        let end = after.find('"').unwrap_or(after.len());
+        let phrase = after[..end].split_whitespace().collect::<Vec<_>>().join(" ");
+        if !phrase.is_empty() {
+            terms.push(format!("\"{}\"", phrase));
+        }
+
+        rest = after.get(end + 1..).unwrap_or("");
+    }
+    terms.extend(prefix_terms(rest));
+
+    terms.join(" ")
+}
+
+fn prefix_terms(words: &str) -> Vec<String> {
+    words.split_whitespace()
+        .map(|word| word.trim_end_matches('*').replace('"', ""))
+        .filter(|word| !word.is_empty())
+        .map(|word| format!("\"{}\"*", word))
+        .collect()
+}
+
+// Split highlighted text into (text, is_match) segments for display
+pub fn split_highlights(text: &str) -> Vec<(&str, bool)> {
+    let mut segments = Vec::new();
+    let mut rest = text;
+
+    while let Some(start) = rest.find(HIGHLIGHT_OPEN) {
+        if start > 0 {
+            segments.push((&rest[..start], false));
+        }
+
+        let after = &rest[start + HIGHLIGHT_OPEN.len()..];
+        let end = after.find(HIGHLIGHT_CLOSE).unwrap_or(after.len());
+        segments.push((&after[..end], true));
+
+        rest = after.get(end + HIGHLIGHT_CLOSE.len()..).unwrap_or("");
+    }
+    if !rest.is_empty() {
+        segments.push((rest, false));
+    }
+
+    segments
+}
+
+// region: Tests
+#[cfg(test)]
+#[path = "../_tests/model_search.rs"]
+mod tests;
+// endregion: Tests
@@ -0,0 +1,71 @@
+#![allow(dead_code)]
+
+use serde::{Serialize, Serializer};
+use strum::IntoEnumIterator;
+
+use crate::model;
+use crate::model::db::ModelManager;
+use crate::model::element::{ListElementMAC, ListElementStatus};
+use crate::model::query::ListQuery;
+use crate::model::tag::{TagCount, TagMAC};
+
+// tags listed in `Stats::top_tags`
+const TOP_TAGS: usize = 5;
+
+// region: Types
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Stats {
+    pub total: usize,
+    // every status, in display order, zero counts included
+    #[serde(serialize_with = "serialize_by_status")]
+    pub by_status: Vec<(ListElementStatus, usize)>,
+    pub episodes_watched: i64,
+    pub scored: usize,
+    // 0-100
+    pub mean_score: Option<f64>,
+    // most used first
+    pub top_tags: Vec<TagCount>,
+}
+// endregion: Types
+
+impl ListElementMAC {
+    // counts, progress and scores over the whole list
+    pub fn stats(mm: &ModelManager) -> Result<Stats, model::Error> {
+        let elements = Self::get_all(mm, &ListQuery::default())?;
+        let scores: Vec<i32> = elements.iter().filter_map(|element| element.score).collect();
+
+        let mut top_tags = TagMAC::get_all(mm)?;
+        top_tags.truncate(TOP_TAGS);
+
+        Ok(Stats {
+            total: elements.len(),
+            by_status: ListElementStatus::iter()
+                .map(|status| (status, elements.iter().filter(|element| element.status == status).count()))
+                .collect(),
+            episodes_watched: elements.iter().map(|element| element.episodes_watched as i64).sum(),
+            scored: scores.len(),
+            mean_score: match scores.is_empty() {
+                true => None,
+                false => Some(scores.iter().sum::<i32>() as f64 / scores.len() as f64),
+            },
+            top_tags,
+        })
+    }
+
+    pub fn stats_from_path(db_path: &str) -> Result<Stats, model::Error> {
+        let mm = ModelManager::cached(db_path)?;
+
+        Self::stats(&mm)
+    }
+}
+
+// a status -> count object rather than a list of pairs
+fn serialize_by_status<S: Serializer>(by_status: &[(ListElementStatus, usize)], serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.collect_map(by_status.iter().map(|(status, count)| (status.to_string(), count)))
+}
+
+// region: Tests
+#[cfg(test)]
+#[path = "../_tests/model_stats.rs"]
+mod tests;
+// endregion: Tests
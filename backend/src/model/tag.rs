@@ -0,0 +1,157 @@
+#![allow(dead_code)]
+
+use diesel::{
+    dsl::count, prelude::*,
+    sql_types::{BigInt, Double, Text},
+    RunQueryDsl, SqliteConnection,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::model;
+use crate::model::db::ModelManager;
+use crate::model::schema::{list, list_tag, tag};
+
+// region: Types
+#[derive(Debug, Clone, PartialEq, Eq, Queryable, Serialize)]
+pub struct TagCount {
+    pub name: String,
+    pub count: i64,
+}
+
+// average 0-100 score of the scored elements carrying a tag
+#[derive(Debug, Clone, PartialEq, QueryableByName, Serialize)]
+pub struct TagScore {
+    #[diesel(sql_type = Text)]
+    pub name: String,
+    #[diesel(sql_type = Double)]
+    pub average: f64,
+    #[diesel(sql_type = BigInt)]
+    pub count: i64,
+}
+
+// How a multi-tag query combines its tags
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum TagMatch {
+    #[default]
+    All,
+    Any,
+}
+// endregion: Types
+
+// Tag MAC
+pub struct TagMAC;
+
+impl TagMAC {
+    // every tag in use on the list with the number of elements carrying it, most used first
+    pub fn get_all(mm: &ModelManager) -> Result<Vec<TagCount>, model::Error> {
+        let rows: Vec<(String, i64)> = tag::table
+            .inner_join(list_tag::table.inner_join(list::table))
+            .filter(list::deleted_at.is_null())
+            .group_by((tag::id, tag::name))
+            .select((tag::name, count(list_tag::list_id)))
+            .order_by((count(list_tag::list_id).desc(), tag::name))
+            .load(&mut mm.db().get()?)?;
+
+        Ok(rows.into_iter().map(|(name, count)| TagCount { name, count }).collect())
+    }
+
+    // highest average first, unscored elements don't count
+    pub fn get_average_scores(mm: &ModelManager) -> Result<Vec<TagScore>, model::Error> {
+        let rows: Vec<TagScore> = diesel::sql_query(
+            "SELECT t.name AS name, avg(l.score) AS average, count(l.score) AS count
+            FROM tag t
+                JOIN list_tag lt ON lt.tag_id = t.id
+                JOIN list l ON l.id = lt.list_id
+            WHERE l.score IS NOT NULL AND l.deleted_at IS NULL
+            GROUP BY t.id, t.name
+            ORDER BY average DESC, t.name")
+            .load(&mut mm.db().get()?)?;
+
+        Ok(rows)
+    }
+
+    pub fn get_average_scores_from_path(db_path: &str) -> Result<Vec<TagScore>, model::Error> {
+        let mm = ModelManager::cached(db_path)?;
+
+        Self::get_average_scores(&mm)
+    }
+
+    pub fn get_all_from_path(db_path: &str) -> Result<Vec<TagCount>, model::Error> {
+        let mm = ModelManager::cached(db_path)?;
+
+        Self::get_all(&mm)
+    }
+}
+
+// whitespace inside a single tag becomes dashes so it survives the space separated display string
+pub fn normalize_tag(tag_name: &str) -> String {
+    tag_name.split_whitespace().collect::<Vec<_>>().join("-")
+}
+
+// split a space separated tag string, dropping case-insensitive duplicates but keeping order
+pub fn parse_tags(tag_string: &str) -> Vec<String> {
+    let mut tags: Vec<String> = Vec::new();
+
+    for name in tag_string.split_whitespace() {
+        if !tags.iter().any(|t| t.eq_ignore_ascii_case(name)) {
+            tags.push(name.to_string());
+        }
+    }
+
+    tags
+}
+
+// normalized, without empty names or case-insensitive duplicates, in the order given
+pub(crate) fn unique_tags(names: &[String]) -> Vec<String> {
+    let mut tags: Vec<String> = Vec::new();
+
+    for name in names.iter().map(|name| normalize_tag(name)).filter(|name| !name.is_empty()) {
+        if !tags.iter().any(|t| t.eq_ignore_ascii_case(&name)) {
+            tags.push(name);
+        }
+    }
+
+    tags
+}
+
+pub(crate) fn add_tag(conn: &mut SqliteConnection, list_id: i32, name: &str) -> Result<(), model::Error> {
+    diesel::insert_or_ignore_into(tag::table)
+        .values(tag::name.eq(name))
+        .execute(conn)?;
+
+    let tag_id: i32 = tag::table.filter(tag::name.eq(name)).select(tag::id).first(conn)?;
+
+    diesel::insert_or_ignore_into(list_tag::table)
+        .values((list_tag::list_id.eq(list_id), list_tag::tag_id.eq(tag_id)))
+        .execute(conn)?;
+
+    Ok(())
+}
+
+pub(crate) fn remove_tag(conn: &mut SqliteConnection, list_id: i32, name: &str) -> Result<(), model::Error> {
+    let tag_ids = tag::table.filter(tag::name.eq(name)).select(tag::id);
+
+    diesel::delete(list_tag::table
+        .filter(list_tag::list_id.eq(list_id))
+        .filter(list_tag::tag_id.eq_any(tag_ids)))
+        .execute(conn)?;
+
+    Ok(())
+}
+
+// replace every tag on an element, the triggers rebuild list.tags
+pub(crate) fn set_tags(conn: &mut SqliteConnection, list_id: i32, names: &[String]) -> Result<(), model::Error> {
+    diesel::delete(list_tag::table.filter(list_tag::list_id.eq(list_id))).execute(conn)?;
+
+    for name in names {
+        add_tag(conn, list_id, name)?;
+    }
+
+    Ok(())
+}
+
+// region: Tests
+#[cfg(test)]
+#[path = "../_tests/model_tag.rs"]
+mod tests;
+// endregion: Tests
@@ -0,0 +1,496 @@
+#![allow(dead_code)]
+
+use std::{
+    cell::RefCell,
+    fmt::{self, Write as _},
+    io::{Read, Write},
+};
+
+use chrono::{NaiveDate, NaiveDateTime};
+use diesel::{prelude::*, Connection, RunQueryDsl, SqliteConnection};
+use serde::{
+    de::{self, DeserializeSeed, MapAccess, SeqAccess, Visitor},
+    ser::SerializeMap,
+    Deserializer, Serialize, Serializer,
+};
+use strum::IntoEnumIterator;
+use strum_macros::{Display, EnumIter, EnumString};
+
+use crate::model;
+use crate::model::db::ModelManager;
+use crate::model::element::{
+    self, parse_get_result, LETuple, ListElement, ListElementMAC, ListElementPatch, TIMESTAMP_FORMAT,
+};
+use crate::model::import::ImportIssue;
+use crate::model::score::SCORE_MAX;
+use crate::model::tag;
+
+// started_on and finished_on as stored
+const DATE_FORMAT: &str = "%Y-%m-%d";
+
+// region: Types
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Display, EnumString, EnumIter)]
+#[strum(serialize_all = "lowercase", ascii_case_insensitive)]
+pub enum TransferFormat {
+    #[default]
+    Csv,
+    Json,
+}
+
+// One field of a list element, named like the struct field
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Display, EnumString, EnumIter)]
+#[strum(serialize_all = "snake_case", ascii_case_insensitive)]
+pub enum Column {
+    Id,
+    Title,
+    Notes,
+    Tags,
+    Status,
+    EpisodesWatched,
+    EpisodesTotal,
+    Score,
+    StartedOn,
+    FinishedOn,
+    MalId,
+    Ctime,
+    Mtime,
+}
+
+#[derive(Debug, Clone)]
+pub struct TransferOptions {
+    // written in this order, on import any other column is ignored
+    pub columns: Vec<Column>,
+    // between tags in a CSV cell, JSON writes tags as an array
+    pub tag_delimiter: String,
+    // chrono format for the date and time columns, None keeps the stored ISO-8601
+    pub date_format: Option<String>,
+}
+
+impl Default for TransferOptions {
+    fn default() -> Self {
+        Self {
+            columns: Column::iter().collect(),
+            tag_delimiter: " ".to_string(),
+            date_format: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TransferReport {
+    pub created: usize,
+    pub updated: usize,
+    // rows that were skipped and why
+    pub issues: Vec<ImportIssue>,
+}
+
+// a cell as read, before it is parsed for its column
+#[derive(Debug, Clone, PartialEq)]
+enum Field {
+    Null,
+    Text(String),
+    List(Vec<String>),
+}
+
+// a cell to write
+enum Cell {
+    Null,
+    Int(i32),
+    Text(String),
+    List(Vec<String>),
+}
+// endregion: Types
+
+impl ListElementMAC {
+    // Write every element, one row at a time, returns the number of rows written
+    pub fn export_to<W: Write>(mm: &ModelManager, writer: W, format: TransferFormat, options: &TransferOptions) -> Result<usize, model::Error> {
+        use crate::model::schema::list::dsl::list;
+        use crate::model::schema::list::*;
+
+        let conn = &mut mm.db().get()?;
+        let rows = list.filter(deleted_at.is_null()).order_by(id).load_iter::<LETuple, diesel::connection::DefaultLoadingMode>(conn)?;
+        let elements = rows.map(|row| Ok::<_, model::Error>(parse_get_result(row?)));
+
+        match format {
+            TransferFormat::Csv => write_csv(elements, writer, options),
+            TransferFormat::Json => write_json(elements, writer, options),
+        }
+    }
+
+    // Read rows in one transaction. A row with the id of an element updates it, any other row is created.
+    // ctime and mtime are kept by the db and ignored on import.
+    pub fn import_from<R: Read>(mm: &ModelManager, reader: R, format: TransferFormat, options: &TransferOptions) -> Result<TransferReport, model::Error> {
+        mm.db().get()?.transaction(|conn| {
+            let mut report = TransferReport::default();
+            let mut index = 0;
+
+            // a row that does not make a valid element is skipped, a failing db ends the import and nothing is kept
+            let mut handle = |fields: Vec<(Column, Field)>| -> Result<(), model::Error> {
+                let row = row_to_patch(fields, options);
+                let checked = match row {
+                    Ok((le_id, patch)) => {
+                        let existing = match le_id {
+                            Some(le_id) => element::find_by_id(conn, le_id)?,
+                            None => None,
+                        };
+                        check_row(existing.as_ref(), &patch).map(|_| (existing, patch))
+                    }
+                    Err(reason) => Err(reason),
+                };
+
+                match checked {
+                    Ok((existing, patch)) => import_row(conn, mm.source(), existing, &patch, &mut report)?,
+                    Err(reason) => report.issues.push(ImportIssue::Skipped { index, reason }),
+                }
+                index += 1;
+                Ok(())
+            };
+
+            match format {
+                TransferFormat::Csv => read_csv(reader, options, &mut handle)?,
+                TransferFormat::Json => read_json(reader, options, &mut handle)?,
+            }
+
+            Ok(report)
+        })
+    }
+
+    pub fn export_to_from_path<W: Write>(db_path: &str, writer: W, format: TransferFormat, options: &TransferOptions) -> Result<usize, model::Error> {
+        let mm = ModelManager::cached(db_path)?;
+
+        Self::export_to(&mm, writer, format, options)
+    }
+
+    pub fn import_from_from_path<R: Read>(db_path: &str, reader: R, format: TransferFormat, options: &TransferOptions) -> Result<TransferReport, model::Error> {
+        let mm = ModelManager::cached(db_path)?;
+
+        Self::import_from(&mm, reader, format, options)
+    }
+}
+
+// region: Export
+fn write_csv<W: Write>(elements: impl Iterator<Item = Result<ListElement, model::Error>>, writer: W, options: &TransferOptions) -> Result<usize, model::Error> {
+    let mut csv = csv::Writer::from_writer(writer);
+    csv.write_record(options.columns.iter().map(|column| column.to_string()))?;
+
+    let mut count = 0;
+    for element in elements {
+        let element = element?;
+        let mut record = Vec::with_capacity(options.columns.len());
+
+        for column in &options.columns {
+            record.push(match cell(&element, *column, options)? {
+                Cell::Null => String::new(),
+                Cell::Int(value) => value.to_string(),
+                Cell::Text(text) => text,
+                Cell::List(items) => items.join(&options.tag_delimiter),
+            });
+        }
+
+        csv.write_record(&record)?;
+        count += 1;
+    }
+
+    csv.flush()?;
+    Ok(count)
+}
+
+// a JSON array of objects, written element by element
+fn write_json<W: Write>(elements: impl Iterator<Item = Result<ListElement, model::Error>>, mut writer: W, options: &TransferOptions) -> Result<usize, model::Error> {
+    writer.write_all(b"[")?;
+
+    let mut count = 0;
+    for element in elements {
+        let element = element?;
+        let cells = options.columns.iter()
+            .map(|column| Ok((*column, cell(&element, *column, options)?)))
+            .collect::<Result<Vec<_>, model::Error>>()?;
+
+        writer.write_all(if count == 0 { b"\n  " } else { b",\n  " })?;
+        serde_json::to_writer(&mut writer, &JsonRow(&cells))?;
+        count += 1;
+    }
+
+    writer.write_all(b"\n]\n")?;
+    writer.flush()?;
+    Ok(count)
+}
+
+struct JsonRow<'a>(&'a [(Column, Cell)]);
+
+impl Serialize for JsonRow<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(self.0.len()))?;
+
+        for (column, cell) in self.0 {
+            let key = column.to_string();
+            match cell {
+                Cell::Null => map.serialize_entry(&key, &None::<()>)?,
+                Cell::Int(value) => map.serialize_entry(&key, value)?,
+                Cell::Text(text) => map.serialize_entry(&key, text)?,
+                Cell::List(items) => map.serialize_entry(&key, items)?,
+            }
+        }
+
+        map.end()
+    }
+}
+
+fn cell(element: &ListElement, column: Column, options: &TransferOptions) -> Result<Cell, model::Error> {
+    let text = |value: &Option<String>| value.clone().map_or(Cell::Null, Cell::Text);
+    let int = |value: Option<i32>| value.map_or(Cell::Null, Cell::Int);
+
+    Ok(match column {
+        Column::Id => Cell::Int(element.id),
+        Column::Title => Cell::Text(element.title.clone()),
+        Column::Notes => text(&element.notes),
+        Column::Tags => Cell::List(tag::parse_tags(element.tags.as_deref().unwrap_or_default())),
+        Column::Status => Cell::Text(element.status.to_string()),
+        Column::EpisodesWatched => Cell::Int(element.episodes_watched),
+        Column::EpisodesTotal => int(element.episodes_total),
+        Column::Score => int(element.score),
+        Column::StartedOn => text(&format_date(element.started_on.as_deref(), options)?),
+        Column::FinishedOn => text(&format_date(element.finished_on.as_deref(), options)?),
+        Column::MalId => int(element.mal_id),
+        Column::Ctime => text(&format_timestamp(Some(&element.ctime), options)?),
+        Column::Mtime => text(&format_timestamp(element.mtime.as_deref(), options)?),
+    })
+}
+
+fn format_date(date: Option<&str>, options: &TransferOptions) -> Result<Option<String>, model::Error> {
+    match (date, &options.date_format) {
+        (Some(date), Some(format)) => match NaiveDate::parse_from_str(date, DATE_FORMAT) {
+            Ok(parsed) => write_formatted(parsed.format(format)).map(Some),
+            Err(_) => Ok(Some(date.to_string())),
+        },
+        (date, _) => Ok(date.map(str::to_string)),
+    }
+}
+
+fn format_timestamp(timestamp: Option<&str>, options: &TransferOptions) -> Result<Option<String>, model::Error> {
+    match (timestamp, &options.date_format) {
+        (Some(timestamp), Some(format)) => match NaiveDateTime::parse_from_str(timestamp, TIMESTAMP_FORMAT) {
+            Ok(parsed) => write_formatted(parsed.format(format)).map(Some),
+            Err(_) => Ok(Some(timestamp.to_string())),
+        },
+        (timestamp, _) => Ok(timestamp.map(str::to_string)),
+    }
+}
+
+// chrono reports a bad format string, or time fields on a date, as a fmt error
+fn write_formatted(formatted: impl fmt::Display) -> Result<String, model::Error> {
+    let mut out = String::new();
+
+    write!(out, "{}", formatted).map_err(|_| model::Error::InvalidValue("date_format", "not a valid format for this column".to_string()))?;
+    Ok(out)
+}
+// endregion: Export
+
+// region: Import
+fn read_csv<R: Read>(reader: R, options: &TransferOptions, handle: &mut dyn FnMut(Vec<(Column, Field)>) -> Result<(), model::Error>) -> Result<(), model::Error> {
+    let mut csv = csv::Reader::from_reader(reader);
+
+    // position of each wanted column in the file
+    let columns: Vec<(usize, Column)> = csv.headers()?.iter()
+        .enumerate()
+        .filter_map(|(at, name)| Some((at, name.trim().parse::<Column>().ok()?)))
+        .filter(|(_, column)| options.columns.contains(column))
+        .collect();
+
+    for record in csv.records() {
+        let record = record?;
+        let fields = columns.iter()
+            .map(|(at, column)| (*column, record.get(*at).map_or(Field::Null, |value| Field::Text(value.to_string()))))
+            .collect();
+
+        handle(fields)?;
+    }
+
+    Ok(())
+}
+
+fn read_json<R: Read>(reader: R, options: &TransferOptions, handle: &mut dyn FnMut(Vec<(Column, Field)>) -> Result<(), model::Error>) -> Result<(), model::Error> {
+    // errors from the handler can't travel through serde as they are, they wait here
+    let failed: RefCell<Option<model::Error>> = RefCell::new(None);
+
+    let result = serde_json::Deserializer::from_reader(reader)
+        .deserialize_seq(RowsVisitor { options, handle, failed: &failed });
+
+    match (failed.into_inner(), result) {
+        (Some(ex), _) => Err(ex),
+        (None, result) => Ok(result?),
+    }
+}
+
+// calls the handler for each object in the top level array, without collecting them
+struct RowsVisitor<'a, 'h> {
+    options: &'a TransferOptions,
+    handle: &'a mut (dyn FnMut(Vec<(Column, Field)>) -> Result<(), model::Error> + 'h),
+    failed: &'a RefCell<Option<model::Error>>,
+}
+
+impl<'de> Visitor<'de> for RowsVisitor<'_, '_> {
+    type Value = ();
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("an array of list elements")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<(), A::Error> {
+        while let Some(fields) = seq.next_element_seed(RowSeed(self.options))? {
+            if let Err(ex) = (self.handle)(fields) {
+                *self.failed.borrow_mut() = Some(ex);
+                return Err(de::Error::custom("import stopped"));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+struct RowSeed<'a>(&'a TransferOptions);
+
+impl<'de> DeserializeSeed<'de> for RowSeed<'_> {
+    type Value = Vec<(Column, Field)>;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        deserializer.deserialize_map(self)
+    }
+}
+
+impl<'de> Visitor<'de> for RowSeed<'_> {
+    type Value = Vec<(Column, Field)>;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a list element object")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+        let mut fields = Vec::new();
+
+        while let Some(key) = map.next_key::<String>()? {
+            let value: serde_json::Value = map.next_value()?;
+
+            if let Some(column) = key.parse::<Column>().ok().filter(|column| self.0.columns.contains(column)) {
+                fields.push((column, json_field(value)));
+            }
+        }
+
+        Ok(fields)
+    }
+}
+
+fn json_field(value: serde_json::Value) -> Field {
+    use serde_json::Value;
+
+    match value {
+        Value::Null => Field::Null,
+        Value::String(text) => Field::Text(text),
+        Value::Array(items) => Field::List(items.into_iter().map(|item| match item {
+            Value::String(text) => text,
+            other => other.to_string(),
+        }).collect()),
+        other => Field::Text(other.to_string()),
+    }
+}
+
+// Whether the row makes a valid element, as an update of `existing` or as a new one
+fn check_row(existing: Option<&ListElement>, patch: &ListElementPatch) -> Result<(), String> {
+    match existing {
+        Some(existing) => patch.merge(existing).validate(false).map_err(|ex| ex.to_string()),
+        None if patch.title.is_none() => Err("no title".to_string()),
+        None => patch.validate(true).map_err(|ex| ex.to_string()),
+    }
+}
+
+// Write a checked row, whatever fails now is the db's
+fn import_row(conn: &mut SqliteConnection, source: &str, existing: Option<ListElement>, patch: &ListElementPatch, report: &mut TransferReport) -> Result<(), model::Error> {
+    match existing {
+        Some(existing) => {
+            element::update_row(conn, source, existing.id, patch)?;
+            report.updated += 1;
+        }
+        None => {
+            element::insert(conn, source, patch)?;
+            report.created += 1;
+        }
+    }
+
+    Ok(())
+}
+
+// An empty cell, or null in JSON, clears a nullable column, the way an export writes NULL.
+// Title, status and episodes watched can't be NULL, for them an empty cell keeps what is stored.
+fn row_to_patch(fields: Vec<(Column, Field)>, options: &TransferOptions) -> Result<(Option<i32>, ListElementPatch), String> {
+    let mut le_id = None;
+    let mut patch = ListElementPatch::default();
+
+    for (column, field) in fields {
+        let text = match &field {
+            Field::Null => String::new(),
+            Field::Text(text) => text.trim().to_string(),
+            Field::List(items) => items.join(" "),
+        };
+        let int = || match text.is_empty() {
+            true => Ok(None),
+            false => text.parse::<i32>().map(Some).map_err(|_| format!("{} is not a number: {}", column, text)),
+        };
+
+        match column {
+            Column::Id => le_id = int()?,
+            Column::Title => patch.title = Some(text.clone()).filter(|title| !title.is_empty()),
+            Column::Notes => patch.notes = Some(Some(text.clone()).filter(|notes| !notes.is_empty())),
+            Column::Tags => {
+                let names: Vec<String> = match field {
+                    Field::List(items) => items,
+                    _ => split_tags(&text, &options.tag_delimiter),
+                };
+                patch.tags = Some(names.iter().map(|name| tag::normalize_tag(name)).collect::<Vec<_>>().join(" "));
+            }
+            Column::Status if !text.is_empty() => {
+                patch.status = Some(text.parse().map_err(|_| format!("unknown status: {}", text))?);
+            }
+            Column::EpisodesWatched => patch.episodes_watched = int()?,
+            Column::EpisodesTotal => patch.episodes_total = Some(int()?),
+            Column::Score => {
+                patch.score = Some(int()?);
+                if patch.score.flatten().is_some_and(|score| !(0..=SCORE_MAX).contains(&score)) {
+                    return Err(format!("score is not between 0 and {}: {}", SCORE_MAX, text));
+                }
+            }
+            Column::StartedOn => patch.started_on = Some(parse_date(&text, options)?),
+            Column::FinishedOn => patch.finished_on = Some(parse_date(&text, options)?),
+            Column::MalId => patch.mal_id = Some(int()?),
+            Column::Status | Column::Ctime | Column::Mtime => (),
+        }
+    }
+
+    Ok((le_id, patch))
+}
+
+fn split_tags(text: &str, delimiter: &str) -> Vec<String> {
+    let names = match delimiter.trim().is_empty() {
+        true => text.split_whitespace().map(str::to_string).collect::<Vec<_>>(),
+        false => text.split(delimiter).map(|name| name.trim().to_string()).collect(),
+    };
+
+    names.into_iter().filter(|name| !name.is_empty()).collect()
+}
+
+fn parse_date(text: &str, options: &TransferOptions) -> Result<Option<String>, String> {
+    if text.is_empty() {
+        return Ok(None);
+    }
+
+    let format = options.date_format.as_deref().unwrap_or(DATE_FORMAT);
+    NaiveDate::parse_from_str(text, format)
+        .map(|date| Some(date.format(DATE_FORMAT).to_string()))
+        .map_err(|_| format!("not a date in {}: {}", format, text))
+}
+// endregion: Import
+
+// region: Tests
+#[cfg(test)]
+#[path = "../_tests/model_transfer.rs"]
+mod tests;
+// endregion: Tests
@@ -0,0 +1,248 @@
+#![allow(dead_code)]
+
+use diesel::{prelude::*, RunQueryDsl, SqliteConnection};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+use crate::model;
+use crate::model::db::ModelManager;
+use crate::model::element::{self, ListElement, ListElementPatch};
+use crate::model::history;
+use crate::model::score::ScoreScale;
+use crate::model::schema::undo_stack;
+
+// operations kept for undo, older ones are dropped
+pub const UNDO_DEPTH: i32 = 100;
+
+// region: Types
+// A reversible edit, holding the element as it was before and after it.
+// Moving to the trash and back are updates of deleted_at, Delete is the element going away for good.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum Operation {
+    Create { element: ListElement },
+    // boxed, an update holds two elements
+    Update { before: Box<ListElement>, after: Box<ListElement> },
+    Delete { element: ListElement },
+}
+
+impl Operation {
+    pub fn element_id(&self) -> i32 {
+        match self {
+            Operation::Create { element } | Operation::Delete { element } => element.id,
+            Operation::Update { after, .. } => after.id,
+        }
+    }
+
+    fn states(&self) -> (Option<&ListElement>, Option<&ListElement>) {
+        match self {
+            Operation::Create { element } => (None, Some(element)),
+            Operation::Update { before, after } => (Some(before.as_ref()), Some(after.as_ref())),
+            Operation::Delete { element } => (Some(element), None),
+        }
+    }
+
+    pub(crate) fn apply(&self, conn: &mut SqliteConnection, source: &str) -> Result<(), model::Error> {
+        let (before, after) = self.states();
+
+        transition(conn, source, self.element_id(), before, after)
+    }
+
+    pub(crate) fn revert(&self, conn: &mut SqliteConnection, source: &str) -> Result<(), model::Error> {
+        let (before, after) = self.states();
+
+        transition(conn, source, self.element_id(), after, before)
+    }
+}
+
+// "delete Cowboy Bebop", for telling the user what was undone
+impl fmt::Display for Operation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Operation::Create { element } => write!(f, "add {}", element.title),
+            Operation::Update { before, after } => match (&before.deleted_at, &after.deleted_at) {
+                (None, Some(_)) => write!(f, "delete {}", before.title),
+                (Some(_), None) => write!(f, "restore {}", before.title),
+                _ => write!(f, "edit {}", before.title),
+            },
+            Operation::Delete { element } => write!(f, "delete {}", element.title),
+        }
+    }
+}
+// endregion: Types
+
+// Undo MAC, every edit a user makes by hand, done so it can be undone and redone.
+// The stack lives in the db, so it survives restarts and is shared by every frontend on it.
+// Writes through ListElementMAC are not recorded, the frontends leave only bulk changes to it:
+// imports, emptying the trash and purging expired elements. An undo step over an element changed
+// that way since is refused, see `transition`.
+pub struct UndoMAC;
+
+impl UndoMAC {
+    pub fn create(mm: &ModelManager, patch: ListElementPatch) -> Result<ListElement, model::Error> {
+        mm.db().get()?.transaction(|conn| {
+            let element = element::insert(conn, mm.source(), &patch)?;
+            push(conn, &Operation::Create { element: element.clone() })?;
+
+            Ok(element)
+        })
+    }
+
+    pub fn update(mm: &ModelManager, le_id: i32, patch: &ListElementPatch) -> Result<ListElement, model::Error> {
+        tracked(mm, le_id, |conn| element::update_row(conn, mm.source(), le_id, patch))
+    }
+
+    // see `ListElementMAC::set_progress`
+    pub fn set_progress(mm: &ModelManager, le_id: i32, watched: i32) -> Result<ListElement, model::Error> {
+        tracked(mm, le_id, |conn| element::progress_row(conn, mm.source(), le_id, |_| watched))
+    }
+
+    pub fn increment_episodes(mm: &ModelManager, le_id: i32) -> Result<ListElement, model::Error> {
+        tracked(mm, le_id, |conn| element::progress_row(conn, mm.source(), le_id, |watched| watched + 1))
+    }
+
+    pub fn decrement_episodes(mm: &ModelManager, le_id: i32) -> Result<ListElement, model::Error> {
+        tracked(mm, le_id, |conn| element::progress_row(conn, mm.source(), le_id, |watched| watched - 1))
+    }
+
+    // value is on the given scale, None clears the score
+    pub fn set_score(mm: &ModelManager, le_id: i32, value: Option<i32>, scale: ScoreScale) -> Result<ListElement, model::Error> {
+        let new_score = value.map(|value| scale.to_score(value)).transpose()?;
+
+        tracked(mm, le_id, |conn| element::score_row(conn, mm.source(), le_id, new_score))
+    }
+
+    pub fn add_tag(mm: &ModelManager, le_id: i32, tag_name: String) -> Result<ListElement, model::Error> {
+        tracked(mm, le_id, |conn| element::tag_row(conn, mm.source(), le_id, &tag_name, true))
+    }
+
+    pub fn remove_tag(mm: &ModelManager, le_id: i32, tag_name: String) -> Result<ListElement, model::Error> {
+        tracked(mm, le_id, |conn| element::tag_row(conn, mm.source(), le_id, &tag_name, false))
+    }
+
+    // moves the element to the trash like `ListElementMAC::delete`
+    pub fn delete(mm: &ModelManager, le_id: i32) -> Result<(), model::Error> {
+        mm.db().get()?.transaction(|conn| {
+            let (before, after) = element::set_trashed(conn, mm.source(), le_id, true)?;
+
+            push_update(conn, before, &after)
+        })
+    }
+
+    pub fn restore(mm: &ModelManager, le_id: i32) -> Result<ListElement, model::Error> {
+        mm.db().get()?.transaction(|conn| {
+            let (before, after) = element::set_trashed(conn, mm.source(), le_id, false)?;
+            push_update(conn, before, &after)?;
+
+            Ok(after)
+        })
+    }
+
+    // deletes a trashed element for good like `ListElementMAC::purge`, undoing it puts it back in the trash
+    pub fn purge(mm: &ModelManager, le_id: i32) -> Result<(), model::Error> {
+        mm.db().get()?.transaction(|conn| {
+            let element = element::purge_row(conn, mm.source(), le_id)?;
+
+            push(conn, &Operation::Delete { element })
+        })
+    }
+
+    // Revert the latest operation, None when there is nothing left to undo
+    pub fn undo(mm: &ModelManager) -> Result<Option<Operation>, model::Error> {
+        step(mm, false)
+    }
+
+    // Apply the last undone operation again, None when nothing was undone since the last edit
+    pub fn redo(mm: &ModelManager) -> Result<Option<Operation>, model::Error> {
+        step(mm, true)
+    }
+}
+
+// a new operation drops the redo side and the oldest ones past UNDO_DEPTH
+fn push(conn: &mut SqliteConnection, operation: &Operation) -> Result<(), model::Error> {
+    diesel::delete(undo_stack::table.filter(undo_stack::undone.eq(true))).execute(conn)?;
+
+    let id: i32 = diesel::insert_into(undo_stack::table)
+        .values(undo_stack::operation.eq(serde_json::to_string(operation)?))
+        .returning(undo_stack::id)
+        .get_result(conn)?;
+
+    diesel::delete(undo_stack::table.filter(undo_stack::id.le(id - UNDO_DEPTH))).execute(conn)?;
+
+    Ok(())
+}
+
+// nothing to undo when nothing changed
+fn push_update(conn: &mut SqliteConnection, before: ListElement, after: &ListElement) -> Result<(), model::Error> {
+    match history::diff(Some(&before), Some(after))?.is_empty() {
+        true => Ok(()),
+        false => push(conn, &Operation::Update { before: Box::new(before), after: Box::new(after.clone()) }),
+    }
+}
+
+// an edit of one element in a transaction, pushed as an update of it
+fn tracked(mm: &ModelManager, le_id: i32, edit: impl FnOnce(&mut SqliteConnection) -> Result<ListElement, model::Error>) -> Result<ListElement, model::Error> {
+    mm.db().get()?.transaction(|conn| {
+        let before = element::get_by_id(conn, le_id)?;
+        let after = edit(conn)?;
+
+        push_update(conn, before, &after)?;
+
+        Ok(after)
+    })
+}
+
+// undo takes the latest operation still done, redo the one undone last
+fn step(mm: &ModelManager, redo: bool) -> Result<Option<Operation>, model::Error> {
+    let conn = &mut *mm.db().get()?;
+
+    let query = undo_stack::table.filter(undo_stack::undone.eq(redo)).select((undo_stack::id, undo_stack::operation));
+    let next: Option<(i32, String)> = match redo {
+        true => query.order_by(undo_stack::id.asc()).first(conn).optional()?,
+        false => query.order_by(undo_stack::id.desc()).first(conn).optional()?,
+    };
+    let Some((id, operation)) = next else { return Ok(None) };
+    let operation: Operation = serde_json::from_str(&operation)?;
+
+    let result = conn.transaction(|conn| {
+        match redo {
+            true => operation.apply(conn, mm.source())?,
+            false => operation.revert(conn, mm.source())?,
+        }
+
+        diesel::update(undo_stack::table.find(id)).set(undo_stack::undone.eq(!redo)).execute(conn)?;
+        Ok(())
+    });
+
+    // the element was changed elsewhere since, this step can't be taken anymore so it is dropped
+    if let Err(model::Error::UndoConflict(_)) = result {
+        diesel::delete(undo_stack::table.find(id)).execute(conn)?;
+    }
+
+    result.map(|_| Some(operation))
+}
+
+// Move an element from one state to another, None being not there.
+// Refused when it is no longer in the `from` state.
+fn transition(conn: &mut SqliteConnection, source: &str, le_id: i32, from: Option<&ListElement>, to: Option<&ListElement>) -> Result<(), model::Error> {
+    let current = element::find_by_id(conn, le_id)?;
+
+    let unchanged = match (&current, from) {
+        (Some(current), Some(from)) => history::diff(Some(current), Some(from))?.is_empty(),
+        (None, None) => true,
+        _ => false,
+    };
+    if !unchanged {
+        return Err(model::Error::UndoConflict(le_id));
+    }
+
+    match to {
+        Some(to) => element::restore_row(conn, source, to).map(|_| ()),
+        None => element::delete_row(conn, source, le_id).map(|_| ()),
+    }
+}
+
+// region: Tests
+#[cfg(test)]
+#[path = "../_tests/model_undo.rs"]
+mod tests;
+// endregion: Tests
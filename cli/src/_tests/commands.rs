@@ -0,0 +1,101 @@
+use backend::model::{Error, ListElementPatch, ModelManager, ScoreScale, UndoMAC};
+use clap::Parser;
+use std::fs;
+
+use super::{guess_format, list_query, to_patch};
+use crate::{Cli, Command, FileFormat};
+
+// the command line as the shell hands it over, scale set the way `run` sets it
+fn parse(args: &[&str]) -> Result<Cli, clap::Error> {
+    let mut cli = Cli::try_parse_from(["the-list"].iter().chain(args))?;
+    cli.scale = cli.scale_flag.unwrap_or_default();
+    Ok(cli)
+}
+
+#[test]
+fn cli_to_patch() -> Result<(), Box<dyn std::error::Error>> {
+
+    let cli = parse(&["--scale", "star5", "edit", "1", "--score", "4", "--episodes", "3", "--clear", "notes,total"])?;
+    let Command::Edit { fields, .. } = &cli.command else { return Err("not an edit".into()) };
+    let patch = to_patch(&cli, fields)?;
+
+    assert_eq!(Some(Some(80)), patch.score, "To Patch Test Score On Scale");
+    assert_eq!((Some(None), Some(None)), (patch.notes, patch.episodes_total), "To Patch Test Cleared");
+    assert_eq!((Some(3), None), (patch.episodes_watched, patch.tags), "To Patch Test Given Only");
+
+    Ok(())
+}
+
+#[test]
+fn cli_cleared() -> Result<(), Box<dyn std::error::Error>> {
+
+    let cli = parse(&["edit", "1", "--notes", "again", "--clear", "notes"])?;
+    let Command::Edit { fields, .. } = &cli.command else { return Err("not an edit".into()) };
+    assert!(matches!(to_patch(&cli, fields), Err(Error::InvalidValue("clear", _))), "Cleared Test Given And Cleared");
+
+    let cli = parse(&["edit", "1", "--score", "11"])?;
+    let Command::Edit { fields, .. } = &cli.command else { return Err("not an edit".into()) };
+    assert!(matches!(to_patch(&cli, fields), Err(Error::InvalidValue("score", _))), "Cleared Test Off The Scale");
+
+    assert!(parse(&["edit", "1", "--clear", "title"]).is_err(), "Cleared Test Not Nullable");
+
+    Ok(())
+}
+
+#[test]
+fn cli_scale_flag() -> Result<(), Box<dyn std::error::Error>> {
+
+    assert_eq!(None, parse(&["stats"])?.scale_flag, "Scale Flag Test Left Out");
+    assert_eq!(Some(ScoreScale::Smiley), parse(&["stats", "--scale", "smiley"])?.scale_flag, "Scale Flag Test Global");
+
+    Ok(())
+}
+
+#[test]
+fn cli_list_query() -> Result<(), Box<dyn std::error::Error>> {
+
+    let dir = tempfile::tempdir()?;
+    let mm = ModelManager::open(&dir.path().join("test.tldb").to_string_lossy())?;
+    let element = UndoMAC::create(&mm, ListElementPatch { title: Some("Mushishi".to_string()), ..Default::default() })?;
+
+    // --after and --offset together, the offset counts from the cursor
+    let after = element.id.to_string();
+    let cli = parse(&["ls", "--sort", "title", "--desc", "--after", &after, "--offset", "2", "--limit", "5"])?;
+    let Command::Ls { page, .. } = &cli.command else { return Err("not ls".into()) };
+    let query = list_query(&mm, page)?;
+
+    assert_eq!(Some(element.id), query.cursor.as_ref().map(|cursor| cursor.id()), "List Query Test Cursor");
+    assert_eq!((Some(2), Some(5)), (query.offset, query.limit), "List Query Test Offset Limit");
+
+    let cli = parse(&["ls", "--after", "999"])?;
+    let Command::Ls { page, .. } = &cli.command else { return Err("not ls".into()) };
+    assert!(matches!(list_query(&mm, page), Err(Error::EntityNotFound(..))), "List Query Test Unknown After");
+
+    Ok(())
+}
+
+#[test]
+fn cli_guess_format() -> Result<(), Box<dyn std::error::Error>> {
+
+    let dir = tempfile::tempdir()?;
+    let file = |name: &str, contents: &str| -> Result<String, std::io::Error> {
+        let path = dir.path().join(name);
+        fs::write(&path, contents)?;
+        Ok(path.to_string_lossy().into_owned())
+    };
+
+    let anilist = file("anilist.json", r#"{ "data": { "MediaListCollection": { "lists": [] } } }"#)?;
+    let ours = file("ours.JSON", r#"[{ "title": "Cowboy Bebop" }]"#)?;
+    // past the peeked prefix the key is not looked for
+    let late = file("late.json", &format!(r#"[{{ "notes": "{}" }}, {{ "MediaListCollection": 1 }}]"#, " ".repeat(5000)))?;
+
+    assert_eq!(FileFormat::Mal, guess_format("list.xml")?, "Guess Format Test MAL");
+    assert_eq!(FileFormat::Csv, guess_format("list.CSV")?, "Guess Format Test CSV");
+    assert_eq!(FileFormat::Anilist, guess_format(&anilist)?, "Guess Format Test AniList");
+    assert_eq!(FileFormat::Json, guess_format(&ours)?, "Guess Format Test JSON");
+    assert_eq!(FileFormat::Json, guess_format(&late)?, "Guess Format Test Prefix Only");
+    assert!(matches!(guess_format("list.txt"), Err(Error::InvalidValue("format", _))), "Guess Format Test Unknown");
+    assert!(matches!(guess_format("missing.json"), Err(Error::IOError(_))), "Guess Format Test Missing");
+
+    Ok(())
+}
@@ -0,0 +1,54 @@
+use backend::model::{ListElement, ListElementStatus};
+use clap::Parser;
+
+use super::{print_table, write_elements};
+use crate::Cli;
+
+fn element() -> ListElement {
+    ListElement {
+        id: 7,
+        title: "Mushishi".to_string(),
+        status: ListElementStatus::Watching,
+        episodes_watched: 3,
+        score: Some(80),
+        tags: Some("calm".to_string()),
+        ..Default::default()
+    }
+}
+
+fn written(args: &[&str]) -> Result<String, Box<dyn std::error::Error>> {
+    let cli = Cli::try_parse_from(["the-list"].iter().chain(args))?;
+    let mut out = Vec::new();
+    write_elements(&mut out, &cli, &[element()])?;
+
+    Ok(String::from_utf8(out)?)
+}
+
+#[test]
+fn cli_output_modes() -> Result<(), Box<dyn std::error::Error>> {
+
+    let table = written(&["stats"])?;
+    let plain = written(&["-o", "plain", "stats"])?;
+    let json: serde_json::Value = serde_json::from_str(&written(&["-o", "json", "stats"])?)?;
+
+    assert!(table.starts_with("ID  TITLE"), "Output Modes Test Table Header");
+    assert!(table.contains("3/?"), "Output Modes Test Table Unknown Total");
+    assert_eq!("7\tMushishi\tWatching\t3\t\t8\tcalm\n", plain, "Output Modes Test Plain");
+    assert_eq!(Some("Mushishi"), json[0]["title"].as_str(), "Output Modes Test JSON");
+
+    Ok(())
+}
+
+#[test]
+fn cli_output_table() -> Result<(), Box<dyn std::error::Error>> {
+
+    let mut out = Vec::new();
+    print_table(&mut out, &["A", "LONGER"], &[vec!["wide cell".to_string(), "x".to_string()]])?;
+    assert_eq!("A          LONGER\nwide cell  x\n", String::from_utf8(out)?, "Output Table Test Padding");
+
+    let mut out = Vec::new();
+    print_table(&mut out, &["", ""], &[vec!["a".to_string(), "b".to_string()]])?;
+    assert_eq!("a  b\n", String::from_utf8(out)?, "Output Table Test No Header");
+
+    Ok(())
+}
@@ -0,0 +1,252 @@
+use std::{fs, io::{self, Read, Write}, path::Path};
+
+use backend::model::{
+    init_db, Config, DbSource, Error, HistoryMAC, ListElementMAC, ListElementPatch, ListQuery, ModelManager, SortDirection, SortField, TagMAC,
+    TransferOptions, UndoMAC,
+};
+
+use crate::{ClearField, Cli, Command, DbCommand, ElementFields, FileFormat, PageArgs, TagCommand, TransferArgs, TrashCommand};
+use crate::output;
+
+// bytes of a .json read to tell AniList from our own export, AniList names its collection in the first keys
+const PEEK_BYTES: u64 = 4096;
+
+pub fn run(cli: &Cli, mm: &ModelManager) -> Result<(), Error> {
+    match &cli.command {
+        Command::Add { title, fields } => {
+            let patch = ListElementPatch {
+                title: Some(title.clone()),
+                ..to_patch(cli, fields)?
+            };
+
+            output::elements(cli, &[UndoMAC::create(mm, patch)?])
+        }
+        Command::Edit { id, title, fields } => {
+            let patch = ListElementPatch {
+                title: title.clone(),
+                ..to_patch(cli, fields)?
+            };
+
+            output::elements(cli, &[UndoMAC::update(mm, *id, &patch)?])
+        }
+        Command::Rm { ids } => {
+            for id in ids {
+                UndoMAC::delete(mm, *id)?;
+            }
+            Ok(())
+        }
+        Command::Ls { status, tags, page } => {
+            let query = ListQuery {
+                status: *status,
+                tags: tags.clone(),
+                ..list_query(mm, page)?
+            };
+
+            output::elements(cli, &ListElementMAC::get_all(mm, &query)?)
+        }
+        Command::Search { query, limit } => {
+            let hits = ListElementMAC::search(mm, query.clone(), &ListQuery::limited(*limit))?;
+
+            output::elements(cli, &hits.into_iter().map(|hit| hit.element).collect::<Vec<_>>())
+        }
+        Command::Tag(TagCommand::Add { id, tags }) => {
+            let mut element = None;
+            for name in tags {
+                element = Some(UndoMAC::add_tag(mm, *id, name.clone())?);
+            }
+
+            output::elements(cli, &element.into_iter().collect::<Vec<_>>())
+        }
+        Command::Tag(TagCommand::Rm { id, tags }) => {
+            let mut element = None;
+            for name in tags {
+                element = Some(UndoMAC::remove_tag(mm, *id, name.clone())?);
+            }
+
+            output::elements(cli, &element.into_iter().collect::<Vec<_>>())
+        }
+        Command::Tag(TagCommand::Ls) => output::tags(cli, &TagMAC::get_all(mm)?),
+        Command::Status { id, status } => {
+            let patch = ListElementPatch {
+                status: Some(*status),
+                ..Default::default()
+            };
+
+            output::elements(cli, &[UndoMAC::update(mm, *id, &patch)?])
+        }
+        Command::Import { file, format, dry_run, transfer } => {
+            let format = match format {
+                Some(format) => *format,
+                None => guess_format(file)?,
+            };
+
+            match format.transfer() {
+                Some(transfer_format) => {
+                    if *dry_run {
+                        return Err(Error::InvalidValue("dry-run", "only MAL and AniList imports can be dry run".to_string()));
+                    }
+
+                    let reader = io::BufReader::new(fs::File::open(file)?);
+                    let report = ListElementMAC::import_from(mm, reader, transfer_format, &transfer_options(transfer))?;
+
+                    output::transfer_report(cli, &report)
+                }
+                None => {
+                    let contents = fs::read_to_string(file)?;
+                    let report = match format {
+                        FileFormat::Mal => ListElementMAC::import_mal(mm, &contents, *dry_run)?,
+                        _ => ListElementMAC::import_anilist(mm, &contents, *dry_run)?,
+                    };
+
+                    output::import_report(cli, &report)
+                }
+            }
+        }
+        Command::Export { format, out, transfer } => {
+            let mut writer: Box<dyn Write> = match out {
+                Some(path) => Box::new(io::BufWriter::new(fs::File::create(path)?)),
+                None => Box::new(io::stdout().lock()),
+            };
+
+            match (format, format.transfer()) {
+                (_, Some(transfer_format)) => {
+                    ListElementMAC::export_to(mm, &mut writer, transfer_format, &transfer_options(transfer))?;
+                }
+                (FileFormat::Mal, None) => writer.write_all(ListElementMAC::export_mal(mm)?.as_bytes())?,
+                (_, None) => writer.write_all(ListElementMAC::export_anilist(mm)?.as_bytes())?,
+            }
+
+            writer.flush()?;
+            Ok(())
+        }
+        Command::Stats => output::stats(cli, &ListElementMAC::stats(mm)?),
+        Command::Trash(TrashCommand::Ls) => output::elements(cli, &ListElementMAC::get_trash(mm, &ListQuery::new(SortField::Trashed, SortDirection::Descending))?),
+        Command::Trash(TrashCommand::Restore { ids }) => {
+            let restored = ids.iter().map(|id| UndoMAC::restore(mm, *id)).collect::<Result<Vec<_>, _>>()?;
+
+            output::elements(cli, &restored)
+        }
+        Command::Trash(TrashCommand::Purge { ids }) => {
+            for id in ids {
+                UndoMAC::purge(mm, *id)?;
+            }
+            Ok(())
+        }
+        Command::Trash(TrashCommand::Empty) => ListElementMAC::empty_trash(mm).map(|_| ()),
+        Command::Undo => output::undo(cli, false, UndoMAC::undo(mm)?.as_ref()),
+        Command::Redo => output::undo(cli, true, UndoMAC::redo(mm)?.as_ref()),
+        Command::History { id, limit } => {
+            let mut history = match id {
+                Some(id) => HistoryMAC::get_for_element(mm, *id)?,
+                None => HistoryMAC::get_all(mm, *limit)?,
+            };
+            if let Some(limit) = limit {
+                history.truncate((*limit).max(0) as usize);
+            }
+
+            output::history(cli, &history)
+        }
+        Command::Db(command) => Err(Error::InvalidValue("command", format!("{:?} runs before a db is opened", command))),
+    }
+}
+
+pub fn db(cli: &Cli, config: &mut Config, command: &DbCommand) -> Result<(), Error> {
+    match command {
+        DbCommand::Path => {
+            let (db, source) = config.resolve_db(cli.db.as_deref())?;
+            output::db_path(cli, &db, source)
+        }
+        DbCommand::Use { path } => {
+            init_db(Some(path))?;
+
+            // absolute, so it is found whatever directory the next run starts in
+            let path = fs::canonicalize(path)?.to_string_lossy().into_owned();
+            config.use_db(&path);
+            config.save()?;
+
+            output::db_path(cli, &path, DbSource::Config)
+        }
+        DbCommand::Ls => {
+            let (current, _) = config.resolve_db(cli.db.as_deref())?;
+            output::dbs(cli, &config.recent, &current)
+        }
+    }
+}
+
+fn to_patch(cli: &Cli, fields: &ElementFields) -> Result<ListElementPatch, Error> {
+    let score = fields.score.map(|value| cli.scale.to_score(value)).transpose()?;
+
+    Ok(ListElementPatch {
+        notes: cleared(fields, ClearField::Notes, fields.notes.clone())?,
+        tags: fields.tags.clone(),
+        status: fields.status,
+        episodes_watched: fields.episodes,
+        episodes_total: cleared(fields, ClearField::Total, fields.total)?,
+        score: cleared(fields, ClearField::Score, score)?,
+        ..Default::default()
+    })
+}
+
+// a nullable field for the patch, Some(None) empties it, a field both given and cleared is refused
+fn cleared<T>(fields: &ElementFields, field: ClearField, value: Option<T>) -> Result<Option<Option<T>>, Error> {
+    match (fields.clear.contains(&field), value) {
+        (true, Some(_)) => Err(Error::InvalidValue("clear", format!("{:?} is both given and cleared", field).to_lowercase())),
+        (true, None) => Ok(Some(None)),
+        (false, value) => Ok(value.map(Some)),
+    }
+}
+
+fn list_query(mm: &ModelManager, args: &PageArgs) -> Result<ListQuery, Error> {
+    let query = ListQuery {
+        order_by: args.sort,
+        direction: if args.desc { SortDirection::Descending } else { SortDirection::Ascending },
+        offset: args.offset,
+        limit: args.limit,
+        ..Default::default()
+    };
+
+    match args.after {
+        Some(id) => Ok(ListQuery { offset: args.offset, ..query.after(&ListElementMAC::get(mm, id)?) }),
+        None => Ok(query),
+    }
+}
+
+fn transfer_options(args: &TransferArgs) -> TransferOptions {
+    let mut options = TransferOptions {
+        tag_delimiter: args.tag_delimiter.clone(),
+        date_format: args.date_format.clone(),
+        ..Default::default()
+    };
+
+    if !args.columns.is_empty() {
+        options.columns = args.columns.clone();
+    }
+
+    options
+}
+
+// .xml is MAL, a .json with AniList's MediaListCollection is AniList
+fn guess_format(file: &str) -> Result<FileFormat, Error> {
+    let extension = Path::new(file).extension().and_then(|ext| ext.to_str()).map(str::to_ascii_lowercase);
+
+    match extension.as_deref() {
+        Some("xml") => Ok(FileFormat::Mal),
+        Some("csv") => Ok(FileFormat::Csv),
+        Some("json") => {
+            let mut head = Vec::new();
+            fs::File::open(file)?.take(PEEK_BYTES).read_to_end(&mut head)?;
+
+            match String::from_utf8_lossy(&head).contains("\"MediaListCollection\"") {
+                true => Ok(FileFormat::Anilist),
+                false => Ok(FileFormat::Json),
+            }
+        }
+        _ => Err(Error::InvalidValue("format", format!("can't tell the format of {}, use --format", file))),
+    }
+}
+
+// region: Tests
+#[cfg(test)]
+#[path = "_tests/commands.rs"]
+mod tests;
+// endregion: Tests
@@ -0,0 +1,282 @@
+mod commands;
+mod output;
+
+use backend::model::{Config, Error, ListElementMAC, ListElementStatus, ModelManager, ScoreScale, SortField, TransferFormat};
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use std::{io, process::ExitCode};
+
+#[derive(Debug, Parser)]
+#[command(name = "the-list", about = "Keep track of the anime you watch", version)]
+pub struct Cli {
+    /// SQLite file holding the list, created and migrated on first use.
+    /// THE_LIST_DB, then the config file, then the data directory when left out
+    #[arg(long, global = true)]
+    pub db: Option<String>,
+
+    /// How results are printed
+    #[arg(long, short, global = true, value_enum, default_value_t = OutputMode::Table)]
+    pub output: OutputMode,
+
+    /// Scale scores are entered and shown in, the one picked in the GUI when left out
+    #[arg(long = "scale", value_name = "SCALE", global = true)]
+    pub scale_flag: Option<ScoreScale>,
+
+    // --scale, else the config's, set once the config is read
+    #[arg(skip)]
+    pub scale: ScoreScale,
+
+    #[command(subcommand)]
+    pub command: Command,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum OutputMode {
+    /// Aligned columns for reading
+    Table,
+    Json,
+    /// Tab separated, no header, for scripts
+    Plain,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum Command {
+    /// Add an element to the list
+    Add {
+        title: String,
+        #[command(flatten)]
+        fields: ElementFields,
+    },
+    /// Change the fields given on an element
+    Edit {
+        id: i32,
+        #[arg(long)]
+        title: Option<String>,
+        #[command(flatten)]
+        fields: ElementFields,
+    },
+    /// Move elements to the trash
+    Rm {
+        #[arg(required = true)]
+        ids: Vec<i32>,
+    },
+    /// List elements, all of them without filters
+    Ls {
+        #[arg(long)]
+        status: Option<ListElementStatus>,
+        /// Only elements carrying every one of these tags
+        #[arg(long = "tag")]
+        tags: Vec<String>,
+        #[command(flatten)]
+        page: PageArgs,
+    },
+    /// Full text search over titles and notes
+    Search {
+        query: String,
+        #[arg(long)]
+        limit: Option<i64>,
+    },
+    /// Add, remove or list tags
+    #[command(subcommand)]
+    Tag(TagCommand),
+    /// List, restore or purge trashed elements
+    #[command(subcommand)]
+    Trash(TrashCommand),
+    /// Set the status of an element
+    Status {
+        id: i32,
+        status: ListElementStatus,
+    },
+    /// Read an export file into the list
+    Import {
+        file: String,
+        /// Guessed from the file when left out
+        #[arg(long, value_enum)]
+        format: Option<FileFormat>,
+        /// Only report what would be imported, MAL and AniList only
+        #[arg(long)]
+        dry_run: bool,
+        #[command(flatten)]
+        transfer: TransferArgs,
+    },
+    /// Write the list out, to stdout without --out
+    Export {
+        #[arg(long, value_enum, default_value_t = FileFormat::Json)]
+        format: FileFormat,
+        #[arg(long)]
+        out: Option<String>,
+        #[command(flatten)]
+        transfer: TransferArgs,
+    },
+    /// Counts, progress and scores over the whole list
+    Stats,
+    /// Revert the last add, edit or rm, from here or the GUI
+    Undo,
+    /// Apply the last undone change again
+    Redo,
+    /// Changes made to an element, or to the whole list without an id, newest first
+    History {
+        id: Option<i32>,
+        #[arg(long)]
+        limit: Option<i64>,
+    },
+    /// Show or switch the database file
+    #[command(subcommand)]
+    Db(DbCommand),
+}
+
+#[derive(Debug, Subcommand)]
+pub enum TagCommand {
+    /// Tag an element
+    Add {
+        id: i32,
+        #[arg(required = true)]
+        tags: Vec<String>,
+    },
+    /// Untag an element
+    Rm {
+        id: i32,
+        #[arg(required = true)]
+        tags: Vec<String>,
+    },
+    /// Every tag in use and how many elements carry it
+    Ls,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum TrashCommand {
+    /// Trashed elements, the latest trashed first
+    Ls,
+    /// Put elements back on the list
+    Restore {
+        #[arg(required = true)]
+        ids: Vec<i32>,
+    },
+    /// Delete trashed elements for good
+    Purge {
+        #[arg(required = true)]
+        ids: Vec<i32>,
+    },
+    /// Purge everything in the trash
+    Empty,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum DbCommand {
+    /// The database in use and where it was picked from
+    Path,
+    /// Open this database from now on, created and migrated if new
+    Use {
+        path: String,
+    },
+    /// Databases used before, most recent first
+    Ls,
+}
+
+#[derive(Debug, Args)]
+pub struct ElementFields {
+    #[arg(long)]
+    pub notes: Option<String>,
+    /// Space separated, replaces the element's tags
+    #[arg(long)]
+    pub tags: Option<String>,
+    #[arg(long)]
+    pub status: Option<ListElementStatus>,
+    /// Episodes watched
+    #[arg(long)]
+    pub episodes: Option<i32>,
+    /// Episodes in the series
+    #[arg(long)]
+    pub total: Option<i32>,
+    /// Score on --scale
+    #[arg(long)]
+    pub score: Option<i32>,
+    /// Comma separated fields to empty
+    #[arg(long, value_enum, value_delimiter = ',')]
+    pub clear: Vec<ClearField>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ClearField {
+    Notes,
+    Total,
+    Score,
+}
+
+// columns and formatting for CSV and JSON files
+#[derive(Debug, Args)]
+pub struct TransferArgs {
+    /// Comma separated columns, all of them by default
+    #[arg(long, value_delimiter = ',')]
+    pub columns: Vec<backend::model::Column>,
+    #[arg(long, default_value = " ")]
+    pub tag_delimiter: String,
+    /// chrono format for dates, ISO-8601 by default
+    #[arg(long)]
+    pub date_format: Option<String>,
+}
+
+// order and the part of the list shown
+#[derive(Debug, Args)]
+pub struct PageArgs {
+    /// title, added, modified, status or score
+    #[arg(long, default_value_t = SortField::Ctime)]
+    pub sort: SortField,
+    /// Largest first
+    #[arg(long)]
+    pub desc: bool,
+    /// Only elements after this one in the order, for the next page
+    #[arg(long)]
+    pub after: Option<i32>,
+    #[arg(long)]
+    pub offset: Option<i64>,
+    #[arg(long)]
+    pub limit: Option<i64>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum FileFormat {
+    Mal,
+    Anilist,
+    Csv,
+    Json,
+}
+
+impl FileFormat {
+    pub fn transfer(&self) -> Option<TransferFormat> {
+        match self {
+            FileFormat::Csv => Some(TransferFormat::Csv),
+            FileFormat::Json => Some(TransferFormat::Json),
+            FileFormat::Mal | FileFormat::Anilist => None,
+        }
+    }
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+
+    match run(cli) {
+        Ok(()) => ExitCode::SUCCESS,
+        // the reader went away, as with `the-list ls | head`, nothing is left to tell it
+        Err(Error::IOError(ex)) if ex.kind() == io::ErrorKind::BrokenPipe => ExitCode::SUCCESS,
+        Err(ex) => {
+            eprintln!("the-list: {}", ex);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+// db commands work on the config, everything else on the resolved db
+fn run(mut cli: Cli) -> Result<(), Error> {
+    let mut config = Config::load()?;
+    cli.scale = cli.scale_flag.unwrap_or(config.score_scale);
+
+    if let Command::Db(command) = &cli.command {
+        return commands::db(&cli, &mut config, command);
+    }
+
+    let (db, _) = config.resolve_db(cli.db.as_deref())?;
+    let mm = ModelManager::open(&db)?.with_source("cli");
+    ListElementMAC::purge_expired(&mm, config.trash_retention())?;
+
+    commands::run(&cli, &mm)
+}
@@ -0,0 +1,255 @@
+use backend::model::{DbSource, Error, HistoryEntry, ImportReport, ListElement, Operation, Stats, TagCount, TransferReport};
+use serde_json::json;
+use std::io::{self, Write};
+
+use crate::{Cli, OutputMode};
+
+pub fn elements(cli: &Cli, elements: &[ListElement]) -> Result<(), Error> {
+    write_elements(&mut io::stdout().lock(), cli, elements)
+}
+
+fn write_elements(out: &mut impl Write, cli: &Cli, elements: &[ListElement]) -> Result<(), Error> {
+    match cli.output {
+        OutputMode::Json => print_json(out, &serde_json::to_value(elements)?),
+        OutputMode::Plain => {
+            for element in elements {
+                writeln!(out, "{}\t{}\t{}\t{}\t{}\t{}\t{}",
+                    element.id,
+                    element.title,
+                    element.status,
+                    element.episodes_watched,
+                    element.episodes_total.map(|total| total.to_string()).unwrap_or_default(),
+                    element.score.map(|score| cli.scale.from_score(score).to_string()).unwrap_or_default(),
+                    element.tags.as_deref().unwrap_or_default(),
+                )?;
+            }
+            Ok(())
+        }
+        OutputMode::Table => {
+            let rows = elements.iter().map(|element| vec![
+                element.id.to_string(),
+                element.title.clone(),
+                element.status.to_string(),
+                match element.episodes_total {
+                    Some(total) => format!("{}/{}", element.episodes_watched, total),
+                    None => format!("{}/?", element.episodes_watched),
+                },
+                element.score.map(|score| cli.scale.format(score)).unwrap_or_default(),
+                element.tags.clone().unwrap_or_default(),
+            ]).collect::<Vec<_>>();
+
+            print_table(out, &["ID", "TITLE", "STATUS", "EPISODES", "SCORE", "TAGS"], &rows)
+        }
+    }
+}
+
+pub fn tags(cli: &Cli, tags: &[TagCount]) -> Result<(), Error> {
+    let out = &mut io::stdout().lock();
+
+    match cli.output {
+        OutputMode::Json => print_json(out, &json!(tags.iter().map(|tag| json!({ "name": tag.name, "count": tag.count })).collect::<Vec<_>>())),
+        OutputMode::Plain => {
+            for tag in tags {
+                writeln!(out, "{}\t{}", tag.name, tag.count)?;
+            }
+            Ok(())
+        }
+        OutputMode::Table => {
+            let rows = tags.iter().map(|tag| vec![tag.name.clone(), tag.count.to_string()]).collect::<Vec<_>>();
+
+            print_table(out, &["TAG", "COUNT"], &rows)
+        }
+    }
+}
+
+// one row per changed field, the entry repeated on each
+pub fn history(cli: &Cli, history: &[HistoryEntry]) -> Result<(), Error> {
+    let out = &mut io::stdout().lock();
+
+    let rows = history.iter().flat_map(|entry| entry.changes.iter().map(move |change| vec![
+        entry.changed_at.clone(),
+        entry.list_id.to_string(),
+        entry.action.to_string(),
+        entry.source.clone(),
+        change.field.clone(),
+        change.before_text().unwrap_or_default(),
+        change.after_text().unwrap_or_default(),
+    ])).collect::<Vec<_>>();
+
+    match cli.output {
+        OutputMode::Json => print_json(out, &serde_json::to_value(history)?),
+        OutputMode::Plain => {
+            for row in rows {
+                writeln!(out, "{}", row.join("\t"))?;
+            }
+            Ok(())
+        }
+        OutputMode::Table => {
+            print_table(out, &["WHEN", "ID", "ACTION", "SOURCE", "FIELD", "BEFORE", "AFTER"], &rows)
+        }
+    }
+}
+
+// what an undo or redo did, None when there was nothing to do
+pub fn undo(cli: &Cli, redo: bool, operation: Option<&Operation>) -> Result<(), Error> {
+    let out = &mut io::stdout().lock();
+
+    let (verb, noun) = if redo { ("Redid", "redo") } else { ("Undid", "undo") };
+
+    match (&cli.output, operation) {
+        (OutputMode::Json, _) => print_json(out, &json!({ "redo": redo, "operation": operation })),
+        (_, Some(operation)) => {
+            writeln!(out, "{} {}", verb, operation)?;
+            Ok(())
+        }
+        (_, None) => {
+            writeln!(out, "Nothing to {}", noun)?;
+            Ok(())
+        }
+    }
+}
+
+pub fn import_report(cli: &Cli, report: &ImportReport) -> Result<(), Error> {
+    let out = &mut io::stdout().lock();
+
+    let issues: Vec<String> = report.issues.iter().map(|issue| issue.to_string()).collect();
+    let added = match report.dry_run {
+        true => report.entries.len(),
+        false => report.created.len(),
+    };
+
+    match cli.output {
+        OutputMode::Json => print_json(out, &json!({
+            "dry_run": report.dry_run,
+            "added": added,
+            "duplicates": report.duplicates().count(),
+            "issues": issues,
+        })),
+        _ => {
+            let verb = if report.dry_run { "Would add" } else { "Added" };
+            writeln!(out, "{} {}, {} duplicates, {} issues", verb, added, report.duplicates().count(), issues.len())?;
+            for issue in issues {
+                writeln!(out, "  {}", issue)?;
+            }
+            Ok(())
+        }
+    }
+}
+
+pub fn transfer_report(cli: &Cli, report: &TransferReport) -> Result<(), Error> {
+    let out = &mut io::stdout().lock();
+
+    let issues: Vec<String> = report.issues.iter().map(|issue| issue.to_string()).collect();
+
+    match cli.output {
+        OutputMode::Json => print_json(out, &json!({ "created": report.created, "updated": report.updated, "issues": issues })),
+        _ => {
+            writeln!(out, "Created {}, updated {}, {} skipped", report.created, report.updated, issues.len())?;
+            for issue in issues {
+                writeln!(out, "  {}", issue)?;
+            }
+            Ok(())
+        }
+    }
+}
+
+pub fn stats(cli: &Cli, stats: &Stats) -> Result<(), Error> {
+    let out = &mut io::stdout().lock();
+
+    let mean = stats.mean_score.map(|mean| cli.scale.format(mean.round() as i32));
+
+    match cli.output {
+        OutputMode::Json => print_json(out, &serde_json::to_value(stats)?),
+        OutputMode::Plain => {
+            writeln!(out, "total\t{}", stats.total)?;
+            for (status, count) in &stats.by_status {
+                writeln!(out, "{}\t{}", status, count)?;
+            }
+            writeln!(out, "episodes_watched\t{}", stats.episodes_watched)?;
+            writeln!(out, "scored\t{}", stats.scored)?;
+            writeln!(out, "mean_score\t{}", stats.mean_score.map(|mean| format!("{:.1}", mean)).unwrap_or_default())?;
+            Ok(())
+        }
+        OutputMode::Table => {
+            let mut rows = vec![vec!["Total".to_string(), stats.total.to_string()]];
+            rows.extend(stats.by_status.iter().map(|(status, count)| vec![format!("  {}", status), count.to_string()]));
+            rows.push(vec!["Episodes watched".to_string(), stats.episodes_watched.to_string()]);
+            rows.push(vec!["Scored".to_string(), stats.scored.to_string()]);
+            rows.push(vec!["Mean score".to_string(), mean.unwrap_or_else(|| "-".to_string())]);
+            rows.push(vec!["Top tags".to_string(), stats.top_tags.iter().map(|tag| format!("{} ({})", tag.name, tag.count)).collect::<Vec<_>>().join(", ")]);
+
+            print_table(out, &["", ""], &rows)
+        }
+    }
+}
+
+pub fn db_path(cli: &Cli, db: &str, source: DbSource) -> Result<(), Error> {
+    let out = &mut io::stdout().lock();
+
+    match cli.output {
+        OutputMode::Json => print_json(out, &json!({ "db": db, "source": source.to_string() })),
+        OutputMode::Plain => {
+            writeln!(out, "{}", db)?;
+            Ok(())
+        }
+        OutputMode::Table => {
+            writeln!(out, "{} ({})", db, source)?;
+            Ok(())
+        }
+    }
+}
+
+// the one in use is marked with *
+pub fn dbs(cli: &Cli, recent: &[String], current: &str) -> Result<(), Error> {
+    let out = &mut io::stdout().lock();
+
+    match cli.output {
+        OutputMode::Json => print_json(out, &json!(recent.iter().map(|db| json!({ "db": db, "current": db == current })).collect::<Vec<_>>())),
+        OutputMode::Plain => {
+            for db in recent {
+                writeln!(out, "{}", db)?;
+            }
+            Ok(())
+        }
+        OutputMode::Table => {
+            for db in recent {
+                writeln!(out, "{} {}", if db == current { "*" } else { " " }, db)?;
+            }
+            Ok(())
+        }
+    }
+}
+
+fn print_json(out: &mut impl Write, value: &serde_json::Value) -> Result<(), Error> {
+    writeln!(out, "{}", serde_json::to_string_pretty(value)?)?;
+    Ok(())
+}
+
+// columns padded to their widest cell, an all empty header is left out
+fn print_table(out: &mut impl Write, header: &[&str], rows: &[Vec<String>]) -> Result<(), Error> {
+    let mut widths: Vec<usize> = header.iter().map(|name| name.chars().count()).collect();
+    for row in rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+
+    let line = |cells: Vec<&str>| {
+        let padded: Vec<String> = cells.iter().zip(&widths).map(|(cell, width)| format!("{:<width$}", cell, width = width)).collect();
+        padded.join("  ").trim_end().to_string()
+    };
+
+    if header.iter().any(|name| !name.is_empty()) {
+        writeln!(out, "{}", line(header.to_vec()))?;
+    }
+    for row in rows {
+        writeln!(out, "{}", line(row.iter().map(String::as_str).collect()))?;
+    }
+    Ok(())
+}
+
+// region: Tests
+#[cfg(test)]
+#[path = "_tests/output.rs"]
+mod tests;
+// endregion: Tests
@@ -0,0 +1,54 @@
+use backend::model::Config;
+use eframe::egui::{TextEdit, Ui};
+use crate::app::ERROR_RED;
+
+// Shows the db in use and picks another one, typed in or from the ones used before
+#[derive(Debug, Default)]
+pub struct DatabasePicker {
+    path: String,
+    recent: Vec<String>,
+    pub error: Option<String>,
+}
+
+impl DatabasePicker {
+
+    pub fn new() -> Self {
+        match Config::load() {
+            Ok(config) => Self { recent: config.recent, ..Default::default() },
+            Err(ex) => Self { error: Some(ex.to_string()), ..Default::default() },
+        }
+    }
+
+    // returns the db to switch to once one is picked
+    pub fn render(&mut self, ui: &mut Ui, current: &str) -> Option<String> {
+        let mut picked = None;
+
+        ui.label(format!("In use: {}", current));
+
+        ui.horizontal(|ui| {
+            ui.label("File");
+            ui.add(TextEdit::singleline(&mut self.path).hint_text("path to a .tldb file, created if new"));
+
+            if ui.button("Open").clicked() && !self.path.trim().is_empty() {
+                picked = Some(self.path.trim().to_string());
+            }
+        });
+
+        if !self.recent.is_empty() {
+            ui.separator();
+            ui.label("Recent");
+
+            for db in &self.recent {
+                if ui.selectable_label(db == current, db).clicked() && db != current {
+                    picked = Some(db.clone());
+                }
+            }
+        }
+
+        if let Some(error) = &self.error {
+            ui.colored_label(ERROR_RED, error);
+        }
+
+        picked
+    }
+}
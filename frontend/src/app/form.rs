@@ -0,0 +1,156 @@
+use backend::model::{ListElement, ListElementPatch, ListElementStatus, SCORE_MAX};
+use eframe::egui::{ComboBox, Grid, TextEdit, Ui};
+use std::hash::Hash;
+use strum::IntoEnumIterator;
+use crate::app::ERROR_RED;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FormAction {
+    None,
+    Save,
+    Cancel,
+}
+
+// Text backed form for creating or editing one list element
+#[derive(Debug, Clone, Default)]
+pub struct ListElementForm {
+    pub title: String,
+    pub notes: String,
+    pub tags: String,
+    pub status: ListElementStatus,
+    pub episodes_watched: String,
+    pub episodes_total: String,
+    pub score: String,
+    pub error: Option<String>,
+}
+
+impl ListElementForm {
+
+    pub fn new() -> Self {
+        Self {
+            episodes_watched: "0".to_string(),
+            ..Default::default()
+        }
+    }
+
+    pub fn from_element(le: &ListElement) -> Self {
+        Self {
+            title: le.title.clone(),
+            notes: le.notes.clone().unwrap_or_default(),
+            tags: le.tags.clone().unwrap_or_default(),
+            status: le.status,
+            episodes_watched: le.episodes_watched.to_string(),
+            episodes_total: le.episodes_total.map(|total| total.to_string()).unwrap_or_default(),
+            score: le.score.map(|score| score.to_string()).unwrap_or_default(),
+            error: None,
+        }
+    }
+
+    // validate the fields, the form holds the whole element so an emptied notes, total or score clears it
+    pub fn to_patch(&self) -> Result<ListElementPatch, String> {
+        let title = self.title.trim();
+        if title.is_empty() {
+            return Err("Title is required".to_string());
+        }
+
+        let episodes_watched = parse_episodes("Episodes watched", &self.episodes_watched)?;
+        let episodes_total = parse_episodes("Episodes total", &self.episodes_total)?;
+        let score = parse_score(&self.score)?;
+
+        if let (Some(watched), Some(total)) = (episodes_watched, episodes_total) {
+            if watched > total {
+                return Err(format!("Episodes watched ({}) is more than the total ({})", watched, total));
+            }
+        }
+
+        Ok(ListElementPatch {
+            title: Some(title.to_string()),
+            notes: Some(Some(self.notes.trim().to_string()).filter(|notes| !notes.is_empty())),
+            tags: Some(self.tags.trim().to_string()),
+            status: Some(self.status),
+            episodes_watched,
+            episodes_total: Some(episodes_total),
+            score: Some(score),
+            ..Default::default()
+        })
+    }
+
+    pub fn render(&mut self, ui: &mut Ui, id_source: impl Hash) -> FormAction {
+        let mut action = FormAction::None;
+
+        Grid::new(("element_form", &id_source)).num_columns(2).show(ui, |ui| {
+            ui.label("Title");
+            ui.text_edit_singleline(&mut self.title);
+            ui.end_row();
+
+            ui.label("Notes");
+            ui.add(TextEdit::multiline(&mut self.notes).desired_rows(3));
+            ui.end_row();
+
+            ui.label("Tags");
+            ui.add(TextEdit::singleline(&mut self.tags).hint_text("space separated"));
+            ui.end_row();
+
+            ui.label("Status");
+            ComboBox::from_id_source(("element_form_status", &id_source))
+                .selected_text(self.status.to_string())
+                .show_ui(ui, |ui| {
+                    for status in ListElementStatus::iter() {
+                        ui.selectable_value(&mut self.status, status, status.to_string());
+                    }
+                });
+            ui.end_row();
+
+            ui.label("Episodes");
+            ui.horizontal(|ui| {
+                ui.add(TextEdit::singleline(&mut self.episodes_watched).desired_width(40.0));
+                ui.label("/");
+                ui.add(TextEdit::singleline(&mut self.episodes_total).desired_width(40.0).hint_text("?"));
+            });
+            ui.end_row();
+
+            ui.label("Score");
+            ui.add(TextEdit::singleline(&mut self.score).desired_width(40.0).hint_text(format!("0-{}", SCORE_MAX)));
+            ui.end_row();
+        });
+
+        if let Some(error) = &self.error {
+            ui.colored_label(ERROR_RED, error);
+        }
+
+        ui.horizontal(|ui| {
+            if ui.button("Save").clicked() {
+                action = FormAction::Save;
+            }
+            if ui.button("Cancel").clicked() {
+                action = FormAction::Cancel;
+            }
+        });
+
+        action
+    }
+}
+
+fn parse_episodes(field: &str, value: &str) -> Result<Option<i32>, String> {
+    let value = value.trim();
+    if value.is_empty() {
+        return Ok(None);
+    }
+
+    match value.parse::<i32>() {
+        Ok(episodes) if episodes >= 0 => Ok(Some(episodes)),
+        _ => Err(format!("{} must be a whole number, got {}", field, value)),
+    }
+}
+
+fn parse_score(value: &str) -> Result<Option<i32>, String> {
+    let value = value.trim();
+    if value.is_empty() {
+        return Ok(None);
+    }
+
+    match value.parse::<i32>() {
+        Ok(score) if (0..=SCORE_MAX).contains(&score) => Ok(Some(score)),
+        _ => Err(format!("Score must be a whole number from 0 to {}, got {}", SCORE_MAX, value)),
+    }
+}
@@ -1,51 +1,118 @@
 
-use backend::{self, model::ListElement};
-use eframe::{epaint::Color32, egui::{Label, RichText, Layout, Separator}, emath::Align};
-use std::{fmt::Display};
-use chrono::{ NaiveDateTime, format::{DelayedFormat, StrftimeItems} };
+use backend::{self, model::{
+    Config, HistoryAction, HistoryEntry, HistoryMAC, ListElement, ListElementMAC, ListElementStatus, ListQuery, ModelManager, ScoreScale,
+    SortDirection, SortField, TagMatch, UndoMAC, TIMESTAMP_FORMAT,
+}};
+use eframe::{epaint::Color32, egui::{CollapsingHeader, ComboBox, Label, RichText, Layout, Separator, Slider, TextEdit, Ui}, emath::Align};
+use std::{fmt::Display, fs};
+use chrono::NaiveDateTime;
 use itertools::{Itertools};
+use strum::IntoEnumIterator;
+
+use super::form::{FormAction, ListElementForm};
+use crate::app::ERROR_RED;
 
 pub const PADDING : f32 = 5.0;
+// elements read from the db at a time, the next page is read once the end of the list scrolls into view
+const PAGE_SIZE: usize = 50;
 const WHITE: Color32 = Color32::from_rgb(255, 255, 255);
 
+// what a row asked for, applied once the list is no longer borrowed
+enum RowAction {
+    Edit(i32),
+    SaveEdit,
+    CancelEdit,
+    Delete(i32),
+    ConfirmDelete(i32),
+    CancelDelete,
+    ToggleTag(String),
+}
+
+// What narrows the displayed list, every part has to match
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ListFilter {
+    // substring of the title
+    pub query: String,
+    pub status: Option<ListElementStatus>,
+    // elements must carry all of these
+    pub tags: Vec<String>,
+    pub order_by: SortField,
+    pub direction: SortDirection,
+}
+
+impl ListFilter {
+    // the filter as a db query, the name filter is left out while the search box is blank
+    fn to_query(&self) -> ListQuery {
+        let text = self.query.trim();
+
+        ListQuery {
+            name: Some(text.to_string()).filter(|_| !text.is_empty()),
+            status: self.status,
+            tags: self.tags.clone(),
+            tag_match: TagMatch::All,
+            ..ListQuery::new(self.order_by, self.direction)
+        }
+    }
+
+    fn toggle_tag(&mut self, tag: String) {
+        match self.tags.iter().position(|t| t.eq_ignore_ascii_case(&tag)) {
+            Some(index) => { self.tags.remove(index); }
+            None => self.tags.push(tag),
+        }
+    }
+}
+
 #[derive(Debug)]
-struct ListElementData<'a> { 
+struct ListElementData {
+    id: i32,
     title: String,
     notes: String,
     tags: Vec<String>,
-    status: String,
-    ctime: DelayedFormat<StrftimeItems<'a>>,
-    mtime: DelayedFormat<StrftimeItems<'a>>,
+    status: ListElementStatus,
+    episodes_watched: i32,
+    episodes_total: Option<i32>,
+    score: Option<i32>,
+    ctime: String,
+    mtime: String,
+    // newest first, loaded the first time the history is opened
+    history: Option<Vec<HistoryEntry>>,
 }
 
-impl ListElementData <'_> {
+impl ListElementData {
 
     fn new(le: ListElement) -> Self {
         Self {
+            id: le.id,
             title: le.title,
             notes: le.notes.unwrap_or_default(),
             tags: le.tags.unwrap_or_default().split_whitespace().map(|tag| tag.to_string()).collect(),
             status: le.status,
-            ctime: NaiveDateTime::parse_from_str(
-                le.ctime.as_str(), "%Y-%m-%d %H:%M:%S")
-                .unwrap()
-                .format("%H:%M %d-%m-%Y"),
-            mtime: NaiveDateTime::parse_from_str(
-                (le.mtime.unwrap_or(le.ctime)).as_str(), "%Y-%m-%d %H:%M:%S")
-                .unwrap()
-                .format("%H:%M %d-%m-%Y"),
+            episodes_watched: le.episodes_watched,
+            episodes_total: le.episodes_total,
+            score: le.score,
+            ctime: format_time(&le.ctime),
+            mtime: format_time(le.mtime.as_deref().unwrap_or(&le.ctime)),
+            history: None,
+        }
+    }
+
+    fn progress(&self) -> String {
+        match self.episodes_total {
+            Some(total) => format!("{} / {}", self.episodes_watched, total),
+            None => format!("{} / ?", self.episodes_watched),
         }
     }
 }
 
-impl Display for ListElementData <'_>{
+impl Display for ListElementData {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         
-        write!(f, "Title: {}, Notes: {}, Tags: {}, Status: {}, Created At: {}, Last Modified At: {}",
+        write!(f, "Title: {}, Notes: {}, Tags: {}, Status: {}, Episodes: {}, Created At: {}, Last Modified At: {}",
             self.title,
             self.notes,
             self.tags.iter().format(", "),
-            self.status.to_string(),
+            self.status,
+            self.progress(),
             self.ctime,
             self.mtime,
         )
@@ -54,40 +121,253 @@ impl Display for ListElementData <'_>{
 
 
 #[derive(Debug, Default)]
-pub struct List <'a>{
-    list: Vec<ListElementData <'a>>
+pub struct List {
+    // db the list is read from and written to, and the pool over it
+    pub db: String,
+    mm: Option<ModelManager>,
+    list: Vec<ListElementData>,
+    // the query for the next page, None once the last one is read
+    next: Option<ListQuery>,
+    pub score_scale: ScoreScale,
+    // element being edited inline and its form
+    editing: Option<(i32, ListElementForm)>,
+    // element waiting on delete confirmation
+    confirm_delete: Option<i32>,
+    filter: ListFilter,
+    // what the last undo / redo did
+    notice: Option<String>,
+    // bumped on every reload, for views that show more of the db
+    revision: u64,
+    pub error: Option<String>,
 }
 
-impl List <'_>{
+impl List {
 
-    pub fn new() -> Result<Self, backend::model::Error> {
+    pub fn new(db: &str) -> Result<Self, backend::model::Error> {
         
-        Ok(Self { 
-            list:
-                backend::model::ListElementMAC::get_all_from_path(super::DEFAULT_PATH, None)?
-                .iter().map( move |element|
-                    ListElementData::new(element.to_owned())
-                )
-                .collect()
-        })
+        let mut list = Self::default();
+        list.open(db)?;
+
+        Ok(list)
+    }
+
+    // switch to another db, created and migrated if new, and keep its absolute path
+    pub fn open(&mut self, db: &str) -> Result<(), backend::model::Error> {
+
+        let mm = ModelManager::open(db)?.with_source("gui");
+        ListElementMAC::purge_expired(&mm, Config::load()?.trash_retention())?;
+
+        self.db = fs::canonicalize(db)?.to_string_lossy().into_owned();
+        self.mm = Some(mm);
+        self.list.clear();
+        self.editing = None;
+        self.confirm_delete = None;
+        self.notice = None;
+        self.refresh()
+    }
+
+    // the open db, an error until one could be opened
+    pub fn mm(&self) -> Result<&ModelManager, backend::model::Error> {
+        opened(&self.mm)
+    }
+
+    // Reload the elements matching the filter from the db.
+    // As many are read as were before, so a reload doesn't jump back to the top.
+    pub fn refresh(&mut self) -> Result<(), backend::model::Error> {
+
+        let limit = self.list.len().max(PAGE_SIZE);
+        self.list.clear();
+        self.next = Some(self.filter.to_query());
+        self.load_page(limit)?;
+        self.revision += 1;
+
+        Ok(())
+    }
+
+    // read the next `limit` elements the filter lets through
+    fn load_page(&mut self, limit: usize) -> Result<(), backend::model::Error> {
+
+        let Some(query) = self.next.clone() else { return Ok(()) };
+        let elements = ListElementMAC::get_all(self.mm()?, &ListQuery { limit: Some(limit as i64), ..query.clone() })?;
+
+        // a short page is the last one
+        self.next = elements.last().filter(|_| elements.len() == limit).map(|last| query.after(last));
+        self.list.extend(elements.into_iter().map(ListElementData::new));
+
+        Ok(())
+    }
+
+    pub fn revision(&self) -> u64 {
+        self.revision
+    }
+
+    // step the db's undo stack back, or forward again with redo
+    pub fn undo(&mut self, redo: bool) {
+        let result = match redo {
+            true => self.mm().and_then(UndoMAC::redo),
+            false => self.mm().and_then(UndoMAC::undo),
+        };
+
+        self.notice = match &result {
+            Ok(Some(operation)) => Some(format!("{} {}", if redo { "Redid" } else { "Undid" }, operation)),
+            Ok(None) => Some(format!("Nothing to {}", if redo { "redo" } else { "undo" })),
+            Err(_) => None,
+        };
+        self.editing = None;
+        self.confirm_delete = None;
+        self.error = result.and_then(|_| self.refresh()).err().map(|ex| ex.to_string());
+    }
+
+    fn set_filter(&mut self, filter: ListFilter) {
+        if filter != self.filter {
+            self.filter = filter;
+            self.list.clear();
+            self.error = self.refresh().err().map(|ex| ex.to_string());
+        }
+    }
+
+    // search box, status tabs and the active tag chips, the list narrows as they change
+    pub fn render_filters(&mut self, ui: &mut Ui) {
+
+        let mut filter = self.filter.clone();
+
+        ui.horizontal(|ui| {
+            ui.label("Search");
+            ui.add(TextEdit::singleline(&mut filter.query).hint_text("title"));
+
+            ui.add_space(PADDING);
+            ui.label("Sort");
+            ComboBox::from_id_source("sort")
+                .selected_text(filter.order_by.to_string())
+                .show_ui(ui, |ui| {
+                    for field in SortField::iter().filter(|field| *field != SortField::Trashed) {
+                        ui.selectable_value(&mut filter.order_by, field, field.to_string());
+                    }
+                });
+
+            let direction = match filter.direction {
+                SortDirection::Ascending => "\u{2191} Ascending",
+                SortDirection::Descending => "\u{2193} Descending",
+            };
+            if ui.button(direction).clicked() {
+                filter.direction = match filter.direction {
+                    SortDirection::Ascending => SortDirection::Descending,
+                    SortDirection::Descending => SortDirection::Ascending,
+                };
+            }
+        });
+
+        ui.horizontal(|ui| {
+            ui.selectable_value(&mut filter.status, None, "All");
+            for status in ListElementStatus::iter() {
+                ui.selectable_value(&mut filter.status, Some(status), status.to_string());
+            }
+        });
+
+        if !filter.tags.is_empty() {
+            ui.horizontal(|ui| {
+                ui.label("Tags");
+
+                // clicking an active chip removes it
+                let mut removed = None;
+                for tag in &filter.tags {
+                    if ui.selectable_label(true, format!("{} \u{00d7}", tag)).clicked() {
+                        removed = Some(tag.clone());
+                    }
+                }
+                if let Some(tag) = removed {
+                    filter.toggle_tag(tag);
+                }
+
+                if ui.button("Clear").clicked() {
+                    filter.tags.clear();
+                }
+            });
+        }
+
+        self.set_filter(filter);
     }
 
-    pub fn render_list_elements(&self, ui: &mut eframe::egui::Ui) -> () {
+    pub fn render_list_elements(&mut self, ui: &mut eframe::egui::Ui) {
+
+        let score_scale = self.score_scale;
+        let mm = &self.mm;
+        let mut action = None;
+
+        if let Some(notice) = &self.notice {
+            ui.label(notice);
+        }
+        if let Some(error) = &self.error {
+            ui.colored_label(ERROR_RED, error);
+        }
 
-        for ele in &self.list {
+        for ele in &mut self.list {
             ui.add_space(PADDING);
+
+            //render inline edit panel in place of the element
+            if let Some((id, form)) = &mut self.editing {
+                if *id == ele.id {
+                    match form.render(ui, ele.id) {
+                        FormAction::Save => action = Some(RowAction::SaveEdit),
+                        FormAction::Cancel => action = Some(RowAction::CancelEdit),
+                        FormAction::None => (),
+                    }
+
+                    ui.add_space(PADDING);
+                    ui.add(Separator::default());
+                    continue;
+                }
+            }
             
-            //render LE Title
-            ui.colored_label(WHITE, &ele.title);
+            //render LE Title and actions
+            ui.horizontal(|ui| {
+                ui.colored_label(WHITE, &ele.title);
+
+                ui.with_layout(Layout::right_to_left(Align::Center), |ui| {
+                    if self.confirm_delete == Some(ele.id) {
+                        if ui.button("No").clicked() {
+                            action = Some(RowAction::CancelDelete);
+                        }
+                        if ui.button("Yes").clicked() {
+                            action = Some(RowAction::ConfirmDelete(ele.id));
+                        }
+                        ui.label("Delete?");
+                    } else {
+                        if ui.button("Delete").clicked() {
+                            action = Some(RowAction::Delete(ele.id));
+                        }
+                        if ui.button("Edit").clicked() {
+                            action = Some(RowAction::Edit(ele.id));
+                        }
+                    }
+                });
+            });
 
             //render status
             ui.add_space(PADDING);
-            let status = Label::new(RichText::new(&ele.status).text_style(eframe::egui::TextStyle::Button));
-            ui.add(status);
+            let status = Label::new(RichText::new(ele.status.to_string()).text_style(eframe::egui::TextStyle::Button));
+            let progress = Label::new(RichText::new(format!("Episodes: {}", ele.progress())).text_style(eframe::egui::TextStyle::Button));
+
+            ui.horizontal(|ui| {
+                ui.add(status);
+                ui.add_space(PADDING);
+                ui.add(progress);
+            });
+
+            //render score
+            ui.add_space(PADDING);
+            let picked = ui.horizontal(|ui| render_rating(ui, score_scale, ele.score)).inner;
+
+            if let Some(value) = picked {
+                match opened(mm).and_then(|mm| UndoMAC::set_score(mm, ele.id, value, score_scale)) {
+                    Ok(updated) => *ele = ListElementData::new(updated),
+                    Err(ex) => self.error = Some(format!("Error saving score for {}: {}", ele.title, ex)),
+                }
+            }
             
             //render dates
             let ctime = Label::new(RichText::new(format!("Created At: {}", &ele.ctime)).text_style(eframe::egui::TextStyle::Button));
-            let mtime = Label::new(RichText::new(format!("Created At: {}", &ele.mtime)).text_style(eframe::egui::TextStyle::Button));
+            let mtime = Label::new(RichText::new(format!("Last Modified At: {}", &ele.mtime)).text_style(eframe::egui::TextStyle::Button));
 
             ui.add_space(PADDING);
             ui.with_layout(Layout::right_to_left(Align::TOP), |ui| {
@@ -102,9 +382,29 @@ impl List <'_>{
             
             ui.with_layout(Layout::left_to_right(Align::LEFT), |ui|{
 
+                // tag chips toggle the tag filter
                 for tag in &ele.tags {
-                    let wtag = Label::new(RichText::new(tag).text_style(eframe::egui::TextStyle::Button));
-                    ui.add(wtag);
+                    let active = self.filter.tags.iter().any(|t| t.eq_ignore_ascii_case(tag));
+                    if ui.selectable_label(active, tag).clicked() {
+                        action = Some(RowAction::ToggleTag(tag.clone()));
+                    }
+                }
+            });
+
+            //render history
+            ui.add_space(PADDING);
+            CollapsingHeader::new("History").id_source(("history", ele.id)).show(ui, |ui| {
+                if ele.history.is_none() {
+                    match opened(mm).and_then(|mm| HistoryMAC::get_for_element(mm, ele.id)) {
+                        Ok(history) => ele.history = Some(history),
+                        Err(ex) => self.error = Some(format!("Error loading history for {}: {}", ele.title, ex)),
+                    }
+                }
+
+                match ele.history.as_deref() {
+                    Some([]) => { ui.label("No changes recorded"); }
+                    Some(history) => history.iter().for_each(|entry| render_history_entry(ui, entry)),
+                    None => (),
                 }
             });
             
@@ -112,18 +412,144 @@ impl List <'_>{
             ui.add_space(PADDING);
             ui.add(Separator::default());
         }
+
+        // the end of the list is in view, read the next page
+        let mut load_more = false;
+        if self.next.is_some() {
+            let end = ui.label("Loading\u{2026}").rect;
+            load_more = ui.is_rect_visible(end);
+        }
+
+        if let Some(action) = action {
+            self.apply(action);
+        } else if load_more {
+            match self.load_page(PAGE_SIZE) {
+                // the filter may have let none through, keep reading while the end is in view
+                Ok(()) => ui.ctx().request_repaint(),
+                Err(ex) => self.error = Some(ex.to_string()),
+            }
+        }
+    }
+
+    fn apply(&mut self, action: RowAction) {
+        self.notice = None;
+
+        let result = match action {
+            RowAction::Edit(id) => self.mm().and_then(|mm| ListElementMAC::get(mm, id))
+                .map(|element| self.editing = Some((id, ListElementForm::from_element(&element)))),
+            RowAction::SaveEdit => {
+                let Some((id, form)) = &mut self.editing else { return };
+                let id = *id;
+
+                match form.to_patch() {
+                    Ok(patch) => self.mm().and_then(|mm| UndoMAC::update(mm, id, &patch))
+                        .map(|_| self.editing = None),
+                    Err(message) => {
+                        form.error = Some(message);
+                        return;
+                    }
+                }
+            }
+            RowAction::CancelEdit => {
+                self.editing = None;
+                return;
+            }
+            RowAction::Delete(id) => {
+                self.confirm_delete = Some(id);
+                return;
+            }
+            RowAction::ConfirmDelete(id) => {
+                self.confirm_delete = None;
+                self.mm().and_then(|mm| UndoMAC::delete(mm, id))
+            }
+            RowAction::CancelDelete => {
+                self.confirm_delete = None;
+                return;
+            }
+            RowAction::ToggleTag(tag) => {
+                let mut filter = self.filter.clone();
+                filter.toggle_tag(tag);
+                self.set_filter(filter);
+                return;
+            }
+        };
+
+        self.error = result.and_then(|_| self.refresh()).err().map(|ex| ex.to_string());
     }
+}
 
+// the list's pool, or why there is none
+fn opened(mm: &Option<ModelManager>) -> Result<&ModelManager, backend::model::Error> {
+    mm.as_ref().ok_or_else(|| backend::model::Error::ConfigError("database".to_string(), "none is open, pick one under Database".to_string()))
+}
 
+// shown as hour and day, a timestamp that doesn't parse is shown as stored
+pub fn format_time(time: &str) -> String {
+    match NaiveDateTime::parse_from_str(time, TIMESTAMP_FORMAT) {
+        Ok(time) => time.format("%H:%M %d-%m-%Y").to_string(),
+        Err(_) => time.to_string(),
+    }
+}
 
+// One change in the timeline: when, what and from where, then a line per field
+fn render_history_entry(ui: &mut Ui, entry: &HistoryEntry) {
+    ui.label(RichText::new(format!("{} - {} from {}", format_time(&entry.changed_at), entry.action, entry.source)).text_style(eframe::egui::TextStyle::Button));
+
+    for change in &entry.changes {
+        let before = change.before_text().unwrap_or_else(|| "none".to_string());
+        let after = change.after_text().unwrap_or_else(|| "none".to_string());
+
+        ui.label(match entry.action {
+            HistoryAction::Created => format!("    {}: {}", change.field, after),
+            HistoryAction::Updated => format!("    {}: {} -> {}", change.field, before, after),
+            HistoryAction::Deleted => format!("    {}: was {}", change.field, before),
+            // only deleted_at changes, the line above says it all
+            HistoryAction::Trashed | HistoryAction::Restored => continue,
+        });
+    }
+}
+
+// Rating widget for one element, returns the value the user picked on `scale` (None clears the score)
+fn render_rating(ui: &mut Ui, scale: ScoreScale, score: Option<i32>) -> Option<Option<i32>> {
+    let current = score.map(|score| scale.from_score(score));
+    let mut picked = None;
+
+    ui.label(RichText::new("Score:").text_style(eframe::egui::TextStyle::Button));
+
+    match scale {
+        ScoreScale::Point100 => {
+            // 0 on the slider is unscored
+            let mut value = current.unwrap_or(0);
+            let response = ui.add(Slider::new(&mut value, 0..=scale.max()));
+
+            if response.drag_released() || (response.changed() && !response.dragged()) {
+                picked = Some(Some(value).filter(|value| *value > 0));
+            }
+        }
+        _ => {
+            for value in 1..=scale.max() {
+                // stars fill up to the score, the other scales only mark the one picked
+                let selected = match scale {
+                    ScoreScale::Star5 => current.is_some_and(|current| value <= current),
+                    _ => current == Some(value),
+                };
+
+                // clicking the current score again clears it
+                if ui.selectable_label(selected, scale.symbol(value)).clicked() {
+                    picked = Some(Some(value).filter(|value| Some(*value) != current));
+                }
+            }
+        }
+    }
 
+    picked
 }
 
-impl Display for List <'_>{
+impl Display for List {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         
         for element in self.list.iter() {
-            write!(f, "{}\n", element)?;
+            writeln!(f, "{}", element)?;
         }       
 
         Ok(())
@@ -1,15 +1,21 @@
 pub mod window;
 pub mod list;
+pub mod form;
+pub mod transfer;
+pub mod database;
+pub mod trash;
 
 // Re-export 
 pub use window::run;
+#[allow(unused_imports)]
 pub use list::List;
 
 
 
 
 // main
-use eframe::egui::Vec2;
+use eframe::egui::{Color32, Vec2};
 
-pub const DEFAULT_PATH: &str = "backend/sql/db.tldb";
-pub const WINDOW_SIZE: Vec2 = Vec2::new(540., 960.);
\ No newline at end of file
+pub const WINDOW_SIZE: Vec2 = Vec2::new(540., 960.);
+// error messages in every window
+pub const ERROR_RED: Color32 = Color32::from_rgb(255, 90, 90);
\ No newline at end of file
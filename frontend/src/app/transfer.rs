@@ -0,0 +1,147 @@
+use backend::model::{ImportReport, ListElementMAC, ModelManager};
+use eframe::egui::{Button, ComboBox, ScrollArea, TextEdit, Ui};
+use std::fs;
+use crate::app::ERROR_RED;
+
+// Export file formats the list can be read from and written to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TransferFormat {
+    #[default]
+    Mal,
+    AniList,
+}
+
+impl TransferFormat {
+    const ALL: [TransferFormat; 2] = [TransferFormat::Mal, TransferFormat::AniList];
+
+    fn label(&self) -> &'static str {
+        match self {
+            TransferFormat::Mal => "MyAnimeList XML",
+            TransferFormat::AniList => "AniList JSON",
+        }
+    }
+
+    fn import(&self, mm: &ModelManager, contents: &str, dry_run: bool) -> Result<ImportReport, backend::model::Error> {
+        match self {
+            TransferFormat::Mal => ListElementMAC::import_mal(mm, contents, dry_run),
+            TransferFormat::AniList => ListElementMAC::import_anilist(mm, contents, dry_run),
+        }
+    }
+
+    fn export(&self, mm: &ModelManager) -> Result<String, backend::model::Error> {
+        match self {
+            TransferFormat::Mal => ListElementMAC::export_mal(mm),
+            TransferFormat::AniList => ListElementMAC::export_anilist(mm),
+        }
+    }
+}
+
+// Import and export panel, imports are previewed as a dry run first
+#[derive(Debug, Default)]
+pub struct Transfer {
+    format: TransferFormat,
+    path: String,
+    report: Option<ImportReport>,
+    message: Option<String>,
+    error: Option<String>,
+}
+
+impl Transfer {
+
+    // returns true when the list changed and should be reloaded
+    pub fn render(&mut self, ui: &mut Ui, mm: &ModelManager) -> bool {
+        let mut imported = false;
+
+        ui.horizontal(|ui| {
+            ui.label("Format");
+            ComboBox::from_id_source("transfer_format")
+                .selected_text(self.format.label())
+                .show_ui(ui, |ui| {
+                    for format in TransferFormat::ALL {
+                        if ui.selectable_value(&mut self.format, format, format.label()).changed() {
+                            self.report = None;
+                        }
+                    }
+                });
+        });
+
+        ui.horizontal(|ui| {
+            ui.label("File");
+            if ui.add(TextEdit::singleline(&mut self.path).hint_text("path to the export file")).changed() {
+                self.report = None;
+            }
+        });
+
+        ui.horizontal(|ui| {
+            if ui.button("Preview").clicked() {
+                self.run_import(mm, true);
+            }
+            // only after a preview of the same file
+            if ui.add_enabled(self.report.as_ref().is_some_and(|report| report.dry_run), Button::new("Import")).clicked() {
+                imported = self.run_import(mm, false);
+            }
+            if ui.button("Export").clicked() {
+                self.run_export(mm);
+            }
+        });
+
+        if let Some(error) = &self.error {
+            ui.colored_label(ERROR_RED, error);
+        }
+        if let Some(message) = &self.message {
+            ui.label(message);
+        }
+
+        if let Some(report) = &self.report {
+            ui.separator();
+            ui.label(match report.dry_run {
+                true => format!("{} to add, {} duplicates, {} issues", report.entries.len(), report.duplicates().count(), report.issues.len()),
+                false => format!("Added {}, {} issues", report.created.len(), report.issues.len()),
+            });
+
+            ScrollArea::vertical().max_height(240.).show(ui, |ui| {
+                for issue in &report.issues {
+                    ui.label(issue.to_string());
+                }
+            });
+        }
+
+        imported
+    }
+
+    fn run_import(&mut self, mm: &ModelManager, dry_run: bool) -> bool {
+        self.message = None;
+
+        let result = fs::read_to_string(self.path.trim())
+            .map_err(|ex| ex.to_string())
+            .and_then(|contents| self.format.import(mm, &contents, dry_run).map_err(|ex| ex.to_string()));
+
+        match result {
+            Ok(report) => {
+                let created = !report.created.is_empty();
+                self.report = Some(report);
+                self.error = None;
+                created
+            }
+            Err(error) => {
+                self.report = None;
+                self.error = Some(error);
+                false
+            }
+        }
+    }
+
+    fn run_export(&mut self, mm: &ModelManager) {
+        let result = self.format.export(mm)
+            .map_err(|ex| ex.to_string())
+            .and_then(|contents| fs::write(self.path.trim(), contents).map_err(|ex| ex.to_string()));
+
+        match result {
+            Ok(()) => {
+                self.message = Some(format!("Exported to {}", self.path.trim()));
+                self.error = None;
+            }
+            Err(error) => self.error = Some(error),
+        }
+    }
+}
@@ -0,0 +1,104 @@
+use backend::model::{ListElement, ListElementMAC, ListQuery, ModelManager, SortDirection, SortField, UndoMAC};
+use eframe::{egui::{Button, Layout, ScrollArea, Ui}, emath::Align};
+
+use super::list::format_time;
+use crate::app::ERROR_RED;
+
+// what a row asked for, applied once the elements are no longer borrowed
+enum TrashAction {
+    Restore(i32),
+    Purge(i32),
+}
+
+// Trashed elements, restored or purged one at a time or all at once
+#[derive(Debug, Default)]
+pub struct Trash {
+    elements: Vec<ListElement>,
+    // list revision the elements were read at, see `List::revision`
+    loaded: Option<u64>,
+    confirm_empty: bool,
+    error: Option<String>,
+}
+
+impl Trash {
+
+    // returns true when the list changed and should be reloaded
+    pub fn render(&mut self, ui: &mut Ui, mm: &ModelManager, revision: u64) -> bool {
+        let mut changed = false;
+
+        // deleting from the list moves elements in here, read them again after every reload of it
+        if self.loaded != Some(revision) {
+            match ListElementMAC::get_trash(mm, &ListQuery::new(SortField::Trashed, SortDirection::Descending)) {
+                Ok(elements) => {
+                    self.elements = elements;
+                    self.error = None;
+                }
+                Err(ex) => self.error = Some(ex.to_string()),
+            }
+            self.loaded = Some(revision);
+        }
+
+        if self.elements.is_empty() {
+            ui.label("The trash is empty");
+        }
+
+        let mut action = None;
+
+        ScrollArea::vertical().max_height(480.).show(ui, |ui| {
+            for element in &self.elements {
+                ui.horizontal(|ui| {
+                    ui.label(&element.title);
+                    ui.label(format!("trashed {}", format_time(element.deleted_at.as_deref().unwrap_or_default())));
+
+                    ui.with_layout(Layout::right_to_left(Align::Center), |ui| {
+                        if ui.button("Purge").clicked() {
+                            action = Some(TrashAction::Purge(element.id));
+                        }
+                        if ui.button("Restore").clicked() {
+                            action = Some(TrashAction::Restore(element.id));
+                        }
+                    });
+                });
+            }
+        });
+
+        changed |= match action {
+            // through the undo stack, so Ctrl+Z puts it back in the trash
+            Some(TrashAction::Restore(id)) => self.run(UndoMAC::restore(mm, id).map(|_| ())),
+            Some(TrashAction::Purge(id)) => self.run(UndoMAC::purge(mm, id)),
+            None => false,
+        };
+
+        ui.separator();
+        ui.horizontal(|ui| {
+            if self.confirm_empty {
+                ui.label("Purge everything in the trash?");
+                if ui.button("Yes").clicked() {
+                    self.confirm_empty = false;
+                    changed |= self.run(ListElementMAC::empty_trash(mm).map(|_| ()));
+                }
+                if ui.button("No").clicked() {
+                    self.confirm_empty = false;
+                }
+            } else if ui.add_enabled(!self.elements.is_empty(), Button::new("Empty Trash")).clicked() {
+                self.confirm_empty = true;
+            }
+        });
+
+        if let Some(error) = &self.error {
+            ui.colored_label(ERROR_RED, error);
+        }
+
+        changed
+    }
+
+    fn run(&mut self, result: Result<(), backend::model::Error>) -> bool {
+        match result {
+            Ok(()) => true,
+            Err(ex) => {
+                self.error = Some(ex.to_string());
+                false
+            }
+        }
+    }
+}
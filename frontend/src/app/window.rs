@@ -1,43 +1,235 @@
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")] // hide console window on Windows in release
 
-use super::list::List;
-use eframe::{NativeOptions, Frame, run_native};
+use super::database::DatabasePicker;
+use super::form::{FormAction, ListElementForm};
+use super::list::{List, PADDING};
+use super::transfer::Transfer;
+use super::trash::Trash;
+use eframe::{NativeOptions, run_native};
 use eframe::App;
-use eframe::egui::{ CentralPanel, ScrollArea };
+use eframe::egui::{ CentralPanel, ComboBox, Key, ScrollArea, TopBottomPanel, Window };
+use backend::model::{Config, ScoreScale, UndoMAC};
+use strum::IntoEnumIterator;
 use crate::app::WINDOW_SIZE;
 
 pub fn run() {
 
-    let mut win_option = NativeOptions::default();
-    win_option.initial_window_size = Some(WINDOW_SIZE);
+    let win_option = NativeOptions {
+        initial_window_size: Some(WINDOW_SIZE),
+        ..Default::default()
+    };
     
     run_native("The List", win_option, Box::new( |cc| Box::new(ListApp::new(cc))))    
 }
 
 #[derive(Default)]
-struct ListApp <'a>
+struct ListApp
 {
- list: List <'a>,   
+ list: List,
+ // form for the element being added, open while Some
+ new_element: Option<ListElementForm>,
+ // import / export window, open while Some
+ transfer: Option<Transfer>,
+ // database window, open while Some
+ database: Option<DatabasePicker>,
+ // trash window, open while Some
+ trash: Option<Trash>,
 }
 
-impl ListApp <'_>{
+impl ListApp {
 
-    fn new (cc: &eframe::CreationContext<'_>) -> Self {
+    fn new (_cc: &eframe::CreationContext<'_>) -> Self {
         
         //configure do inital setup here like font families and stuff like that
+        let config = Config::load();
+        let score_scale = config.as_ref().map(|config| config.score_scale).unwrap_or_default();
+        let opened = config
+            .and_then(|config| config.resolve_db(None))
+            .and_then(|(db, _)| List::new(&db));
+
+        // without a db the list stays empty and says why
+        let mut list = opened.unwrap_or_else(|ex| {
+            let mut list = List::default();
+            list.error = Some(ex.to_string());
+            list
+        });
+        list.score_scale = score_scale;
+
         Self {
-            list: List::new().unwrap()
+            list,
+            new_element: None,
+            transfer: None,
+            database: None,
+            trash: None,
         }
     }
+
+    // open the picked db and make it the one every later start uses
+    fn switch_db(&mut self, db: &str) -> Result<(), backend::model::Error> {
+        self.list.open(db)?;
+
+        let mut config = Config::load()?;
+        config.use_db(&self.list.db);
+        config.save()
+    }
+
+    // keep the picked scale for every later start
+    fn save_score_scale(&self) -> Result<(), backend::model::Error> {
+        let mut config = Config::load()?;
+        config.score_scale = self.list.score_scale;
+        config.save()
+    }
 }
 
-impl App for ListApp <'_>{
+impl App for ListApp {
 
     fn update(&mut self, 
         ctx: &eframe::egui::Context, 
         _frame: &mut eframe::Frame
     )
     { 
+        // Ctrl+Z / Ctrl+Shift+Z, a text field being typed in keeps them for itself
+        if !ctx.wants_keyboard_input() {
+            let (undo, redo) = {
+                let input = ctx.input();
+                let pressed = input.modifiers.command && input.key_pressed(Key::Z);
+                (pressed && !input.modifiers.shift, pressed && input.modifiers.shift)
+            };
+
+            if undo || redo {
+                self.list.undo(redo);
+            }
+        }
+
+        TopBottomPanel::top("top_panel").show(ctx, |ui| {
+            ui.horizontal(|ui| {
+                if ui.button("Add").clicked() && self.new_element.is_none() {
+                    self.new_element = Some(ListElementForm::new());
+                }
+                if ui.button("Import / Export").clicked() && self.transfer.is_none() {
+                    self.transfer = Some(Transfer::default());
+                }
+                if ui.button("Database").clicked() && self.database.is_none() {
+                    self.database = Some(DatabasePicker::new());
+                }
+                if ui.button("Trash").clicked() && self.trash.is_none() {
+                    self.trash = Some(Trash::default());
+                }
+
+                ui.separator();
+                if ui.button("Undo").on_hover_text("Ctrl+Z").clicked() {
+                    self.list.undo(false);
+                }
+                if ui.button("Redo").on_hover_text("Ctrl+Shift+Z").clicked() {
+                    self.list.undo(true);
+                }
+
+                ui.separator();
+                ui.label("Score Scale");
+                let scale_before = self.list.score_scale;
+                ComboBox::from_id_source("score_scale")
+                    .selected_text(self.list.score_scale.to_string())
+                    .show_ui(ui, |ui| {
+                        for scale in ScoreScale::iter() {
+                            ui.selectable_value(&mut self.list.score_scale, scale, scale.to_string());
+                        }
+                    });
+                if self.list.score_scale != scale_before {
+                    if let Err(ex) = self.save_score_scale() {
+                        self.list.error = Some(format!("Error saving score scale: {}", ex));
+                    }
+                }
+            });
+
+            ui.add_space(PADDING);
+            self.list.render_filters(ui);
+            ui.add_space(PADDING);
+        });
+
+        if let Some(form) = &mut self.new_element {
+            let mut action = FormAction::None;
+
+            Window::new("Add Element").collapsible(false).show(ctx, |ui| {
+                action = form.render(ui, "new_element");
+            });
+
+            match action {
+                FormAction::Save => match form.to_patch() {
+                    Ok(patch) => match self.list.mm().and_then(|mm| UndoMAC::create(mm, patch)) {
+                        Ok(_) => {
+                            self.new_element = None;
+                            self.list.error = self.list.refresh().err().map(|ex| ex.to_string());
+                        }
+                        Err(ex) => form.error = Some(ex.to_string()),
+                    },
+                    Err(message) => form.error = Some(message),
+                },
+                FormAction::Cancel => self.new_element = None,
+                FormAction::None => (),
+            }
+        }
+
+        if let Some(transfer) = &mut self.transfer {
+            let mut open = true;
+            let mut imported = false;
+
+            Window::new("Import / Export").open(&mut open).show(ctx, |ui| {
+                match self.list.mm() {
+                    Ok(mm) => imported = transfer.render(ui, mm),
+                    Err(ex) => { ui.label(ex.to_string()); }
+                }
+            });
+
+            if imported {
+                self.list.error = self.list.refresh().err().map(|ex| ex.to_string());
+            }
+            if !open {
+                self.transfer = None;
+            }
+        }
+
+        if let Some(trash) = &mut self.trash {
+            let mut open = true;
+            let mut changed = false;
+
+            Window::new("Trash").open(&mut open).show(ctx, |ui| {
+                match self.list.mm() {
+                    Ok(mm) => changed = trash.render(ui, mm, self.list.revision()),
+                    Err(ex) => { ui.label(ex.to_string()); }
+                }
+            });
+
+            if changed {
+                self.list.error = self.list.refresh().err().map(|ex| ex.to_string());
+            }
+            if !open {
+                self.trash = None;
+            }
+        }
+
+        if let Some(database) = &mut self.database {
+            let mut open = true;
+            let mut picked = None;
+
+            Window::new("Database").open(&mut open).show(ctx, |ui| {
+                picked = database.render(ui, &self.list.db);
+            });
+
+            if let Some(db) = picked {
+                match self.switch_db(&db) {
+                    Ok(()) => open = false,
+                    Err(ex) => {
+                        if let Some(database) = &mut self.database {
+                            database.error = Some(ex.to_string());
+                        }
+                    }
+                }
+            }
+            if !open {
+                self.database = None;
+            }
+        }
+
         CentralPanel::default().show( ctx, |ui|{
             ScrollArea::vertical().auto_shrink([true;2]).show(ui, |ui|{
                 self.list.render_list_elements(ui);
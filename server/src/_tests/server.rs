@@ -0,0 +1,141 @@
+use backend::model::{Error, ListElementPatch, ListElementStatus, ModelManager, UndoMAC};
+use diesel::RunQueryDsl;
+use serde_json::Value;
+use std::io::Read;
+use tempfile::TempDir;
+use tiny_http::{Header, Method, Request, TestRequest};
+
+use super::{api, to_response};
+use crate::reply::ApiError;
+
+// a response as the client sees it
+struct Sent {
+    status: u16,
+    etag: Option<String>,
+    body: Option<Value>,
+}
+
+// A db in a temp dir with "A 1" to "A 5" watching and "B" dropped, all tagged foo.
+// The backend's TestDb is private to its own tests, this is the server's.
+fn test_db() -> Result<(ModelManager, TempDir), Box<dyn std::error::Error>> {
+    let dir = tempfile::tempdir()?;
+    let mm = ModelManager::open(&dir.path().join("test.tldb").to_string_lossy())?.with_source("api");
+
+    for title in ["A 1", "A 2", "B", "A 3", "A 4", "A 5"] {
+        let status = if title == "B" { ListElementStatus::Dropped } else { ListElementStatus::Watching };
+        UndoMAC::create(&mm, ListElementPatch { title: Some(title.to_string()), status: Some(status), tags: Some("foo".to_string()), ..Default::default() })?;
+    }
+
+    Ok((mm, dir))
+}
+
+fn send(mm: &ModelManager, method: Method, path: &str, headers: &[(&str, &str)], body: &'static str) -> Result<Sent, Box<dyn std::error::Error>> {
+    let mut request = TestRequest::new().with_method(method).with_path(path).with_body(body);
+    for (name, value) in headers {
+        request = request.with_header(Header::from_bytes(name.as_bytes(), value.as_bytes()).map_err(|_| "invalid header")?);
+    }
+    let mut request: Request = request.into();
+
+    let result = api::handle(mm, &mut request);
+    let response = to_response(&request, result);
+
+    let status = response.status_code().0;
+    let etag = response.headers().iter().find(|header| header.field.equiv("ETag")).map(|header| header.value.to_string());
+    let mut bytes = Vec::new();
+    response.into_reader().read_to_end(&mut bytes)?;
+    let body = match bytes.is_empty() {
+        true => None,
+        false => Some(serde_json::from_slice(&bytes)?),
+    };
+
+    Ok(Sent { status, etag, body })
+}
+
+#[test]
+fn server_etag() -> Result<(), Box<dyn std::error::Error>> {
+
+    let (mm, _dir) = test_db()?;
+
+    let first = send(&mm, Method::Get, "/elements/1", &[], "")?;
+    let etag = first.etag.ok_or("no ETag")?;
+    assert_eq!(200, first.status, "ETag Test Status");
+
+    let fresh = send(&mm, Method::Get, "/elements/1", &[("If-None-Match", &etag)], "")?;
+    assert_eq!((304, None), (fresh.status, fresh.body), "ETag Test Not Modified");
+
+    // any edit gives the element a new ETag
+    send(&mm, Method::Patch, "/elements/1", &[], r#"{"score": 85}"#)?;
+    let changed = send(&mm, Method::Get, "/elements/1", &[("If-None-Match", &etag)], "")?;
+    assert_eq!(200, changed.status, "ETag Test Changed");
+    assert_ne!(Some(etag), changed.etag, "ETag Test New ETag");
+
+    Ok(())
+}
+
+#[test]
+fn server_if_match() -> Result<(), Box<dyn std::error::Error>> {
+
+    let (mm, _dir) = test_db()?;
+    let etag = send(&mm, Method::Get, "/elements/1", &[], "")?.etag.ok_or("no ETag")?;
+
+    let patched = send(&mm, Method::Patch, "/elements/1", &[("If-Match", &etag)], r#"{"score": 85}"#)?;
+    assert_eq!(200, patched.status, "If-Match Test Current");
+
+    // the ETag read before the edit is stale now
+    let stale = send(&mm, Method::Patch, "/elements/1", &[("If-Match", &etag)], r#"{"score": 10}"#)?;
+    let deleted = send(&mm, Method::Delete, "/elements/1", &[("If-Match", &etag)], "")?;
+    assert_eq!((412, 412), (stale.status, deleted.status), "If-Match Test Stale");
+
+    // If-Match compares strongly, the current tag marked weak does not match it, If-None-Match takes it
+    let current = patched.etag.ok_or("no ETag")?;
+    let weak = format!("W/{}", current);
+    let weak_match = send(&mm, Method::Patch, "/elements/1", &[("If-Match", &weak)], r#"{"score": 10}"#)?;
+    let weak_none_match = send(&mm, Method::Get, "/elements/1", &[("If-None-Match", &weak)], "")?;
+    assert_eq!((412, 304), (weak_match.status, weak_none_match.status), "If-Match Test Weak");
+
+    let element = send(&mm, Method::Get, "/elements/1", &[], "")?.body.ok_or("no body")?;
+    assert_eq!(Some(85), element["score"].as_i64(), "If-Match Test Kept");
+    assert_eq!(Value::Null, element["deleted_at"], "If-Match Test Not Deleted");
+
+    Ok(())
+}
+
+#[test]
+fn server_paging() -> Result<(), Box<dyn std::error::Error>> {
+
+    let (mm, _dir) = test_db()?;
+
+    let first = send(&mm, Method::Get, "/elements?status=watching&tag=foo&sort=title&dir=desc&limit=2", &[], "")?.body.ok_or("no body")?;
+    let titles = |page: &Value| page["elements"].as_array().map(|elements| elements.iter().map(|e| e["title"].as_str().unwrap_or_default().to_string()).collect::<Vec<_>>());
+
+    assert_eq!(Some(vec!["A 5".to_string(), "A 4".to_string()]), titles(&first), "Paging Test First Page");
+    assert_eq!((Some(5), Some(2)), (first["total"].as_i64(), first["next_offset"].as_i64()), "Paging Test Total");
+
+    // the filtered out element does not take a place on any page
+    let after = first["next_after"].as_i64().ok_or("no next_after")?;
+    let second = send(&mm, Method::Get, &format!("/elements?status=watching&sort=title&dir=desc&limit=2&after={}", after), &[], "")?.body.ok_or("no body")?;
+    assert_eq!(Some(vec!["A 3".to_string(), "A 2".to_string()]), titles(&second), "Paging Test After");
+
+    let last = send(&mm, Method::Get, "/elements?status=watching&sort=title&dir=desc&limit=2&offset=4", &[], "")?.body.ok_or("no body")?;
+    assert_eq!(Some(vec!["A 1".to_string()]), titles(&last), "Paging Test Last Page");
+    assert_eq!((Value::Null, Value::Null), (last["next_offset"].clone(), last["next_after"].clone()), "Paging Test No Next");
+
+    let bad = send(&mm, Method::Get, "/elements?status=unknown", &[], "")?;
+    assert_eq!(400, bad.status, "Paging Test Bad Status");
+
+    Ok(())
+}
+
+#[test]
+fn server_constraint_error() -> Result<(), Box<dyn std::error::Error>> {
+
+    let (mm, _dir) = test_db()?;
+
+    // past the model's checks, the score CHECK in the schema still refuses it
+    let result = diesel::sql_query("UPDATE list SET score = 500 WHERE id = 1").execute(&mut *mm.db().get()?);
+    let ex = ApiError::from(Error::from(result.expect_err("score CHECK")));
+
+    assert_eq!(400, ex.status, "Constraint Error Test Status");
+
+    Ok(())
+}
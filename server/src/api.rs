@@ -0,0 +1,199 @@
+use backend::model::{
+    HistoryMAC, ListElement, ListElementMAC, ListElementPatch, ListElementStatus, ListQuery, ModelManager, SortDirection, SortField, TagMAC, TagMatch, UndoMAC,
+};
+use percent_encoding::percent_decode_str;
+use serde::{Deserialize, Serialize};
+use std::{io::Read, str::FromStr};
+use tiny_http::{Method, Request};
+
+use crate::reply::{etag, etag_matches, to_bytes, ApiError, Reply};
+
+// page size of GET /elements without ?limit, and the most one page can hold
+const DEFAULT_LIMIT: usize = 50;
+const MAX_LIMIT: usize = 500;
+// request bodies are single elements, anything larger is refused
+const MAX_BODY: u64 = 1024 * 1024;
+
+// One page of GET /elements
+#[derive(Debug, Serialize)]
+struct Page {
+    // elements matching the filters, over every page
+    total: usize,
+    offset: usize,
+    limit: usize,
+    // offset of the page after this one, None on the last page
+    next_offset: Option<usize>,
+    // ?after= for the page after this one, it holds its place when elements are added or removed
+    next_after: Option<i32>,
+    elements: Vec<ListElement>,
+}
+
+#[derive(Debug, Deserialize)]
+struct TagBody {
+    tag: String,
+}
+
+pub fn handle(mm: &ModelManager, request: &mut Request) -> Result<Reply, ApiError> {
+    let url = request.url().to_string();
+    let (path, query) = url.split_once('?').unwrap_or((&url, ""));
+    let query: Vec<(String, String)> = form_urlencoded::parse(query.as_bytes()).into_owned().collect();
+
+    let segments = path.split('/')
+        .filter(|segment| !segment.is_empty())
+        .map(|segment| percent_decode_str(segment).decode_utf8().map(|segment| segment.into_owned()))
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|_| ApiError::bad_request("path is not valid UTF-8"))?;
+    let segments: Vec<&str> = segments.iter().map(String::as_str).collect();
+
+    let method = request.method().clone();
+
+    match (&method, segments.as_slice()) {
+        (Method::Get, ["elements"]) => list_elements(mm, &query),
+        (Method::Post, ["elements"]) => {
+            let patch: ListElementPatch = read_json(request)?;
+            let element = UndoMAC::create(mm, patch)?;
+            let location = format!("/elements/{}", element.id);
+            Reply::created(element, location)
+        }
+        (Method::Get, ["elements", id]) => Reply::ok(ListElementMAC::get(mm, parse_id(id)?)?),
+        (Method::Patch, ["elements", id]) => {
+            let id = parse_id(id)?;
+            check_if_match(mm, request, id)?;
+
+            let patch: ListElementPatch = read_json(request)?;
+            Reply::ok(UndoMAC::update(mm, id, &patch)?)
+        }
+        (Method::Delete, ["elements", id]) => {
+            let id = parse_id(id)?;
+            check_if_match(mm, request, id)?;
+
+            UndoMAC::delete(mm, id)?;
+            Reply::no_content()
+        }
+        (Method::Get, ["elements", id, "history"]) => Reply::ok(HistoryMAC::get_for_element(mm, parse_id(id)?)?),
+        (Method::Post, ["elements", id, "tags"]) => {
+            let id = parse_id(id)?;
+            check_if_match(mm, request, id)?;
+
+            let body: TagBody = read_json(request)?;
+            Reply::ok(UndoMAC::add_tag(mm, id, body.tag)?)
+        }
+        (Method::Delete, ["elements", id, "tags", tag]) => {
+            let id = parse_id(id)?;
+            check_if_match(mm, request, id)?;
+
+            Reply::ok(UndoMAC::remove_tag(mm, id, tag.to_string())?)
+        }
+        (Method::Get, ["search"]) => {
+            let text = param(&query, "q").ok_or_else(|| ApiError::bad_request("q is required"))?;
+            let limit = param(&query, "limit").map(|limit| parse_number::<i64>("limit", limit)).transpose()?;
+
+            Reply::ok(ListElementMAC::search(mm, text.to_string(), &ListQuery::limited(limit))?)
+        }
+        (Method::Get, ["trash"]) => Reply::ok(ListElementMAC::get_trash(mm, &ListQuery::new(SortField::Trashed, SortDirection::Descending))?),
+        (Method::Post, ["trash", id, "restore"]) => Reply::ok(UndoMAC::restore(mm, parse_id(id)?)?),
+        (Method::Delete, ["trash", id]) => {
+            UndoMAC::purge(mm, parse_id(id)?)?;
+            Reply::no_content()
+        }
+        (Method::Get, ["tags"]) => Reply::ok(TagMAC::get_all(mm)?),
+        (Method::Get, ["stats"]) => Reply::ok(ListElementMAC::stats(mm)?),
+        (_, ["elements"] | ["elements", _] | ["elements", _, "history"] | ["elements", _, "tags"] | ["elements", _, "tags", _] | ["search"] | ["trash"] | ["trash", _] | ["trash", _, "restore"] | ["tags"] | ["stats"]) => {
+            Err(ApiError::new(405, format!("{} is not allowed on {}", method, path)))
+        }
+        _ => Err(ApiError::new(404, format!("no route for {}", path))),
+    }
+}
+
+// ?q= narrows the titles, ?status= and every ?tag= must match, ?sort= and ?dir= order them,
+// ?after= (an element id) ?offset= and ?limit= pick the page, all of it done by the db
+fn list_elements(mm: &ModelManager, query: &[(String, String)]) -> Result<Reply, ApiError> {
+    let offset = param(query, "offset").map(|offset| parse_number("offset", offset)).transpose()?.unwrap_or(0);
+    let limit = param(query, "limit").map(|limit| parse_number("limit", limit)).transpose()?.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT);
+
+    // one more than the page holds, to know if another one follows
+    let list_query = ListQuery {
+        offset: Some(offset as i64),
+        limit: Some(limit as i64 + 1),
+        ..list_query(mm, query)?
+    };
+
+    let total = ListElementMAC::count(mm, &list_query)? as usize;
+    let mut elements = ListElementMAC::get_all(mm, &list_query)?;
+    let more = elements.len() > limit;
+    elements.truncate(limit);
+
+    let next_offset = Some(offset + elements.len()).filter(|_| more && !elements.is_empty());
+    let next_after = next_offset.and(elements.last()).map(|element| element.id);
+
+    Reply::ok(Page { total, offset, limit, next_offset, next_after, elements })
+}
+
+// the filters, the order, and the element the page starts after
+fn list_query(mm: &ModelManager, query: &[(String, String)]) -> Result<ListQuery, ApiError> {
+    let status = param(query, "status")
+        .map(|status| ListElementStatus::from_str(status).map_err(|_| ApiError::bad_request(format!("unknown status {}", status))))
+        .transpose()?;
+    let order_by = param(query, "sort")
+        .map(|sort| SortField::from_str(sort).map_err(|_| ApiError::bad_request(format!("unknown sort {}", sort))))
+        .transpose()?
+        .unwrap_or_default();
+    let direction = param(query, "dir")
+        .map(|dir| SortDirection::from_str(dir).map_err(|_| ApiError::bad_request(format!("unknown dir {}, asc or desc", dir))))
+        .transpose()?
+        .unwrap_or_default();
+
+    let list_query = ListQuery {
+        name: param(query, "q").map(str::trim).filter(|text| !text.is_empty()).map(str::to_string),
+        status,
+        tags: query.iter().filter(|(key, _)| key == "tag").map(|(_, tag)| tag.clone()).collect(),
+        tag_match: TagMatch::All,
+        ..ListQuery::new(order_by, direction)
+    };
+    match param(query, "after") {
+        Some(id) => Ok(list_query.after(&ListElementMAC::get(mm, parse_id(id)?)?)),
+        None => Ok(list_query),
+    }
+}
+
+// a mutation carrying If-Match only goes through while the element still has that ETag
+fn check_if_match(mm: &ModelManager, request: &Request, id: i32) -> Result<(), ApiError> {
+    let Some(expected) = header(request, "If-Match") else { return Ok(()) };
+
+    let element = ListElementMAC::get(mm, id)?;
+    let current = etag(&to_bytes(&serde_json::to_value(element)?));
+
+    match etag_matches(&expected, &current, false) {
+        true => Ok(()),
+        false => Err(ApiError::new(412, format!("element {} has changed, its ETag is now {}", id, current))),
+    }
+}
+
+pub fn header(request: &Request, name: &'static str) -> Option<String> {
+    request.headers().iter()
+        .find(|header| header.field.equiv(name))
+        .map(|header| header.value.to_string())
+}
+
+fn read_json<T: for<'de> Deserialize<'de>>(request: &mut Request) -> Result<T, ApiError> {
+    let mut body = Vec::new();
+    request.as_reader().take(MAX_BODY + 1).read_to_end(&mut body).map_err(|ex| ApiError::bad_request(ex.to_string()))?;
+
+    if body.len() as u64 > MAX_BODY {
+        return Err(ApiError::new(413, format!("body is over {} bytes", MAX_BODY)));
+    }
+
+    Ok(serde_json::from_slice(&body)?)
+}
+
+fn param<'a>(query: &'a [(String, String)], name: &str) -> Option<&'a str> {
+    query.iter().find(|(key, _)| key == name).map(|(_, value)| value.as_str())
+}
+
+fn parse_id(id: &str) -> Result<i32, ApiError> {
+    parse_number("id", id)
+}
+
+fn parse_number<T: FromStr>(name: &str, value: &str) -> Result<T, ApiError> {
+    value.parse().map_err(|_| ApiError::bad_request(format!("{} must be a whole number, got {}", name, value)))
+}
@@ -0,0 +1,122 @@
+mod api;
+mod reply;
+
+use backend::model::{Config, ListElementMAC, ModelManager};
+use clap::Parser;
+use std::{io::Cursor, process::ExitCode};
+use tiny_http::{Header, Method, Request, Response, Server};
+
+use reply::{etag, etag_matches, to_bytes, ApiError, Reply};
+
+#[derive(Debug, Parser)]
+#[command(name = "the-list-server", about = "Serve the list as JSON over HTTP", version)]
+pub struct Cli {
+    /// SQLite file holding the list, created and migrated on first use.
+    /// THE_LIST_DB, then the config file, then the data directory when left out
+    #[arg(long)]
+    pub db: Option<String>,
+
+    /// Address to listen on, only this machine can connect by default
+    #[arg(long, env = "THE_LIST_BIND", default_value = "127.0.0.1:7878")]
+    pub bind: String,
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+
+    let opened = Config::load().and_then(|config| {
+        let (db, _) = config.resolve_db(cli.db.as_deref())?;
+        let mm = ModelManager::open(&db)?.with_source("api");
+        ListElementMAC::purge_expired(&mm, config.trash_retention())?;
+        Ok(mm)
+    });
+
+    let mm = match opened {
+        Ok(mm) => mm,
+        Err(ex) => {
+            eprintln!("the-list-server: {}", ex);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let server = match Server::http(&cli.bind) {
+        Ok(server) => server,
+        Err(ex) => {
+            eprintln!("the-list-server: can't listen on {}: {}", cli.bind, ex);
+            return ExitCode::FAILURE;
+        }
+    };
+    println!("Serving {} on http://{}", mm.path(), cli.bind);
+
+    // one request at a time, all of them borrow from the same pool
+    for mut request in server.incoming_requests() {
+        let result = api::handle(&mm, &mut request);
+        respond(request, result);
+    }
+
+    ExitCode::SUCCESS
+}
+
+// GET answers carry an ETag, and a matching If-None-Match gets 304 without the body
+fn respond(request: Request, result: Result<Reply, ApiError>) {
+    let response = to_response(&request, result);
+
+    if let Err(ex) = request.respond(response) {
+        eprintln!("WARNING: Error sending response. Cause: {}", ex);
+    }
+}
+
+fn to_response(request: &Request, result: Result<Reply, ApiError>) -> Response<Cursor<Vec<u8>>> {
+    let (status, body, location) = match result {
+        Ok(reply) => (reply.status, reply.body, reply.location),
+        Err(ex) => (ex.status, Some(ex.body()), None),
+    };
+
+    let mut headers = Vec::new();
+    if let Some(location) = location {
+        headers.push(header("Location", &location));
+    }
+
+    let response = match body {
+        None => Response::from_data(Vec::new()).with_status_code(status),
+        Some(body) => {
+            let bytes = to_bytes(&body);
+            headers.push(header("Content-Type", "application/json"));
+
+            if (200..300).contains(&status) {
+                let tag = etag(&bytes);
+                headers.push(header("ETag", &tag));
+
+                let fresh = *request.method() == Method::Get
+                    && api::header(request, "If-None-Match").is_some_and(|expected| etag_matches(&expected, &tag, true));
+                if fresh {
+                    headers.retain(|header| !header.field.equiv("Content-Type"));
+                    return with_headers(Response::from_data(Vec::new()).with_status_code(304), headers);
+                }
+            }
+
+            Response::from_data(bytes).with_status_code(status)
+        }
+    };
+
+    with_headers(response, headers)
+}
+
+fn with_headers(mut response: Response<Cursor<Vec<u8>>>, headers: Vec<Header>) -> Response<Cursor<Vec<u8>>> {
+    for header in headers {
+        response.add_header(header);
+    }
+
+    response
+}
+
+fn header(name: &str, value: &str) -> Header {
+    // names are fixed ASCII and values are JSON or ids, both always valid
+    Header::from_bytes(name.as_bytes(), value.as_bytes()).expect("valid header")
+}
+
+// region: Tests
+#[cfg(test)]
+#[path = "_tests/server.rs"]
+mod tests;
+// endregion: Tests
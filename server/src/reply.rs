@@ -0,0 +1,97 @@
+use backend::model::Error;
+use diesel::result::{DatabaseErrorKind, Error as DieselError};
+use serde::Serialize;
+use serde_json::{json, Value};
+use std::hash::{DefaultHasher, Hash, Hasher};
+
+// A successful response, turned into bytes and headers in main
+#[derive(Debug)]
+pub struct Reply {
+    pub status: u16,
+    pub body: Option<Value>,
+    // where a created resource lives
+    pub location: Option<String>,
+}
+
+impl Reply {
+    pub fn ok(body: impl Serialize) -> Result<Self, ApiError> {
+        Ok(Self { status: 200, body: Some(serde_json::to_value(body)?), location: None })
+    }
+
+    pub fn created(body: impl Serialize, location: String) -> Result<Self, ApiError> {
+        Ok(Self { status: 201, body: Some(serde_json::to_value(body)?), location: Some(location) })
+    }
+
+    pub fn no_content() -> Result<Self, ApiError> {
+        Ok(Self { status: 204, body: None, location: None })
+    }
+}
+
+// A failed request, sent as {"error": message}
+#[derive(Debug)]
+pub struct ApiError {
+    pub status: u16,
+    pub message: String,
+}
+
+impl ApiError {
+    pub fn new(status: u16, message: impl Into<String>) -> Self {
+        Self { status, message: message.into() }
+    }
+
+    pub fn bad_request(message: impl Into<String>) -> Self {
+        Self::new(400, message)
+    }
+
+    pub fn body(&self) -> Value {
+        json!({ "error": self.message })
+    }
+}
+
+impl From<Error> for ApiError {
+    fn from(ex: Error) -> Self {
+        let status = match &ex {
+            Error::EntityNotFound(..) | Error::DieselResultError(DieselError::NotFound) => 404,
+            Error::InvalidValue(..) | Error::JsonError(_) | Error::CsvError(_) | Error::XmlError(_) => 400,
+            Error::AmbiguousTitle(_) | Error::UndoConflict(_) => 409,
+            // a row the schema refuses is the request's fault, not the server's
+            Error::DieselResultError(DieselError::DatabaseError(kind, _)) => match kind {
+                DatabaseErrorKind::CheckViolation | DatabaseErrorKind::NotNullViolation => 400,
+                DatabaseErrorKind::UniqueViolation | DatabaseErrorKind::ForeignKeyViolation => 409,
+                _ => 500,
+            },
+            _ => 500,
+        };
+
+        Self::new(status, ex.to_string())
+    }
+}
+
+impl From<serde_json::Error> for ApiError {
+    fn from(ex: serde_json::Error) -> Self {
+        Self::bad_request(ex.to_string())
+    }
+}
+
+// the exact bytes sent for a JSON body, ETags are taken over these
+pub fn to_bytes(body: &Value) -> Vec<u8> {
+    body.to_string().into_bytes()
+}
+
+// strong validator over the response body, stable for as long as the server runs
+pub fn etag(bytes: &[u8]) -> String {
+    let mut hasher = DefaultHasher::new();
+    bytes.hash(&mut hasher);
+
+    format!("\"{:016x}\"", hasher.finish())
+}
+
+// If-None-Match / If-Match hold a list of tags or *. If-None-Match compares weakly, a W/ tag by its
+// opaque part. If-Match compares strongly (RFC 9110 13.1.1), a W/ tag never matches our strong ETags.
+pub fn etag_matches(header: &str, etag: &str, weak: bool) -> bool {
+    header.split(',').map(str::trim).any(|candidate| match candidate.strip_prefix("W/") {
+        _ if candidate == "*" => true,
+        Some(opaque) => weak && opaque == etag,
+        None => candidate == etag,
+    })
+}
@@ -0,0 +1,394 @@
+use backend::model::{Error, ListElement, ListElementMAC, ListElementStatus, ListQuery, ModelManager, ScoreScale, TagMatch, UndoMAC};
+use ratatui::{crossterm::event::{KeyCode, KeyEvent, KeyModifiers}, widgets::TableState};
+use strum::IntoEnumIterator;
+
+use crate::form::{FormAction, ListElementForm};
+
+// rows moved by ctrl-d / ctrl-u
+const HALF_PAGE: usize = 10;
+
+// What narrows the displayed list, every part has to match
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ListFilter {
+    // substring of the title
+    pub query: String,
+    pub status: Option<ListElementStatus>,
+    // elements must carry all of these
+    pub tags: Vec<String>,
+}
+
+impl ListFilter {
+    fn toggle_tag(&mut self, tag: String) {
+        match self.tags.iter().position(|t| t.eq_ignore_ascii_case(&tag)) {
+            Some(index) => { self.tags.remove(index); }
+            None => self.tags.push(tag),
+        }
+    }
+}
+
+// What the keys currently drive
+#[derive(Debug, Clone)]
+pub enum Mode {
+    Normal,
+    // typing into the title filter
+    Search,
+    // after ':'
+    Command(String),
+    // tag to toggle in the filter
+    Tag(String),
+    // element being edited, None while adding one
+    Edit(Option<i32>, ListElementForm),
+    ConfirmDelete(i32),
+    Help,
+}
+
+#[derive(Debug)]
+pub struct App {
+    pub mm: ModelManager,
+    pub scale: ScoreScale,
+    pub list: Vec<ListElement>,
+    pub filter: ListFilter,
+    pub table: TableState,
+    pub mode: Mode,
+    // first key of a two key command, gg and dd
+    pending: Option<char>,
+    pub message: Option<String>,
+    pub error: Option<String>,
+    pub quit: bool,
+}
+
+impl App {
+
+    pub fn new(mm: ModelManager, scale: ScoreScale) -> Result<Self, Error> {
+        let mut app = Self {
+            mm,
+            scale,
+            list: Vec::new(),
+            filter: ListFilter::default(),
+            table: TableState::default(),
+            mode: Mode::Normal,
+            pending: None,
+            message: None,
+            error: None,
+            quit: false,
+        };
+        app.refresh()?;
+
+        Ok(app)
+    }
+
+    // reload the elements matching the filter from the db, keeping the selected element when it is still shown
+    pub fn refresh(&mut self) -> Result<(), Error> {
+
+        let selected = self.selected().map(|element| element.id);
+
+        let text = self.filter.query.trim();
+        let query = ListQuery {
+            name: Some(text.to_string()).filter(|_| !text.is_empty()),
+            status: self.filter.status,
+            tags: self.filter.tags.clone(),
+            tag_match: TagMatch::All,
+            ..Default::default()
+        };
+        let elements = ListElementMAC::get_all(&self.mm, &query)?;
+
+        self.list = elements;
+
+        let index = selected
+            .and_then(|id| self.list.iter().position(|element| element.id == id))
+            .or_else(|| self.table.selected().map(|index| index.min(self.list.len().saturating_sub(1))))
+            .unwrap_or(0);
+        self.table.select((!self.list.is_empty()).then_some(index));
+
+        Ok(())
+    }
+
+    pub fn selected(&self) -> Option<&ListElement> {
+        self.table.selected().and_then(|index| self.list.get(index))
+    }
+
+    fn set_filter(&mut self, filter: ListFilter) {
+        if filter != self.filter {
+            self.filter = filter;
+            self.error = self.refresh().err().map(|ex| ex.to_string());
+        }
+    }
+
+    pub fn handle_key(&mut self, key: KeyEvent) {
+
+        // ctrl-c leaves from anywhere
+        if key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('c') {
+            self.quit = true;
+            return;
+        }
+
+        match self.mode.clone() {
+            Mode::Normal => self.normal_key(key),
+            Mode::Search => self.search_key(key),
+            Mode::Command(command) => self.command_key(key, command),
+            Mode::Tag(tag) => self.tag_key(key, tag),
+            Mode::Edit(id, form) => self.edit_key(key, id, form),
+            Mode::ConfirmDelete(id) => {
+                self.mode = Mode::Normal;
+                if key.code == KeyCode::Char('y') {
+                    self.error = UndoMAC::delete(&self.mm, id)
+                        .and_then(|_| self.refresh())
+                        .err()
+                        .map(|ex| ex.to_string());
+                    self.message = self.error.is_none().then(|| "Deleted".to_string());
+                }
+            }
+            Mode::Help => self.mode = Mode::Normal,
+        }
+    }
+
+    fn normal_key(&mut self, key: KeyEvent) {
+
+        let pending = self.pending.take();
+        self.message = None;
+
+        match key.code {
+            KeyCode::Char('q') => self.quit = true,
+            KeyCode::Char(':') => self.mode = Mode::Command(String::new()),
+            KeyCode::Char('?') => self.mode = Mode::Help,
+
+            // movement
+            KeyCode::Char('d') if key.modifiers.contains(KeyModifiers::CONTROL) => self.move_by(HALF_PAGE as isize),
+            KeyCode::Char('u') if key.modifiers.contains(KeyModifiers::CONTROL) => self.move_by(-(HALF_PAGE as isize)),
+            KeyCode::Char('j') | KeyCode::Down => self.move_by(1),
+            KeyCode::Char('k') | KeyCode::Up => self.move_by(-1),
+            KeyCode::Char('g') if pending == Some('g') => self.select(0),
+            KeyCode::Char('G') | KeyCode::End => self.select(self.list.len().saturating_sub(1)),
+            KeyCode::Home => self.select(0),
+
+            // filters
+            KeyCode::Char('/') => self.mode = Mode::Search,
+            KeyCode::Char('s') | KeyCode::Char('S') => {
+                let statuses: Vec<Option<ListElementStatus>> = std::iter::once(None).chain(ListElementStatus::iter().map(Some)).collect();
+                let filter = ListFilter {
+                    status: step(&statuses, self.filter.status, key.code == KeyCode::Char('s')),
+                    ..self.filter.clone()
+                };
+                self.set_filter(filter);
+            }
+            KeyCode::Char('t') => self.mode = Mode::Tag(String::new()),
+            KeyCode::Char('T') => self.set_filter(ListFilter { tags: Vec::new(), ..self.filter.clone() }),
+            KeyCode::Esc => self.set_filter(ListFilter::default()),
+
+            // the selected element
+            KeyCode::Char('a') => self.mode = Mode::Edit(None, ListElementForm::new()),
+            KeyCode::Char('e') | KeyCode::Enter => {
+                if let Some(element) = self.selected() {
+                    self.mode = Mode::Edit(Some(element.id), ListElementForm::from_element(element));
+                }
+            }
+            KeyCode::Char('d') if pending == Some('d') => {
+                if let Some(element) = self.selected() {
+                    self.mode = Mode::ConfirmDelete(element.id);
+                }
+            }
+            KeyCode::Char('+') => self.update_selected(UndoMAC::increment_episodes),
+            KeyCode::Char('-') => self.update_selected(UndoMAC::decrement_episodes),
+            KeyCode::Char(']') => self.nudge_score(true),
+            KeyCode::Char('[') => self.nudge_score(false),
+            KeyCode::Char('x') => {
+                let scale = self.scale;
+                self.update_selected(|mm, id| UndoMAC::set_score(mm, id, None, scale));
+            }
+
+            KeyCode::Char('r') if key.modifiers.contains(KeyModifiers::CONTROL) => self.undo(true),
+            KeyCode::Char('u') => self.undo(false),
+
+            KeyCode::Char(c @ ('g' | 'd')) => self.pending = Some(c),
+            _ => (),
+        }
+    }
+
+    fn search_key(&mut self, key: KeyEvent) {
+        let mut filter = self.filter.clone();
+
+        match key.code {
+            KeyCode::Enter => self.mode = Mode::Normal,
+            KeyCode::Esc => {
+                filter.query.clear();
+                self.mode = Mode::Normal;
+            }
+            KeyCode::Backspace => { filter.query.pop(); }
+            KeyCode::Char(c) => filter.query.push(c),
+            _ => (),
+        }
+
+        // the list narrows while typing
+        self.set_filter(filter);
+    }
+
+    fn command_key(&mut self, key: KeyEvent, mut command: String) {
+        match key.code {
+            KeyCode::Esc => self.mode = Mode::Normal,
+            KeyCode::Backspace if command.is_empty() => self.mode = Mode::Normal,
+            KeyCode::Backspace => {
+                command.pop();
+                self.mode = Mode::Command(command);
+            }
+            KeyCode::Enter => {
+                self.mode = Mode::Normal;
+                self.run_command(command.trim());
+            }
+            KeyCode::Char(c) => {
+                command.push(c);
+                self.mode = Mode::Command(command);
+            }
+            _ => (),
+        }
+    }
+
+    // :q quits, :<n> jumps to row n
+    fn run_command(&mut self, command: &str) {
+        match command {
+            "" => (),
+            "q" | "q!" | "qa" | "quit" | "wq" | "x" => self.quit = true,
+            _ => match command.parse::<usize>() {
+                Ok(row) => self.select(row.saturating_sub(1).min(self.list.len().saturating_sub(1))),
+                Err(_) => self.error = Some(format!("Not a command: {}", command)),
+            },
+        }
+    }
+
+    fn tag_key(&mut self, key: KeyEvent, mut tag: String) {
+        match key.code {
+            KeyCode::Esc => self.mode = Mode::Normal,
+            KeyCode::Enter => {
+                self.mode = Mode::Normal;
+
+                let tag = tag.trim().to_string();
+                if !tag.is_empty() {
+                    let mut filter = self.filter.clone();
+                    filter.toggle_tag(tag);
+                    self.set_filter(filter);
+                }
+            }
+            // completes to the selected element's tags, one after the other
+            KeyCode::Tab => {
+                let tags: Vec<String> = self.selected()
+                    .and_then(|element| element.tags.clone())
+                    .unwrap_or_default()
+                    .split_whitespace()
+                    .map(str::to_string)
+                    .collect();
+
+                if !tags.is_empty() {
+                    let next = match tags.iter().position(|t| *t == tag) {
+                        Some(index) => tags[(index + 1) % tags.len()].clone(),
+                        None => tags[0].clone(),
+                    };
+                    self.mode = Mode::Tag(next);
+                }
+            }
+            KeyCode::Backspace => {
+                tag.pop();
+                self.mode = Mode::Tag(tag);
+            }
+            KeyCode::Char(c) => {
+                tag.push(c);
+                self.mode = Mode::Tag(tag);
+            }
+            _ => (),
+        }
+    }
+
+    fn edit_key(&mut self, key: KeyEvent, id: Option<i32>, mut form: ListElementForm) {
+        match form.handle_key(key) {
+            FormAction::None => self.mode = Mode::Edit(id, form),
+            FormAction::Cancel => self.mode = Mode::Normal,
+            FormAction::Save => {
+                let patch = match form.to_patch() {
+                    Ok(patch) => patch,
+                    Err(message) => {
+                        form.error = Some(message);
+                        self.mode = Mode::Edit(id, form);
+                        return;
+                    }
+                };
+
+                let saved = match id {
+                    Some(id) => UndoMAC::update(&self.mm, id, &patch),
+                    None => UndoMAC::create(&self.mm, patch),
+                };
+
+                match saved.and_then(|element| self.refresh().map(|_| element)) {
+                    Ok(element) => {
+                        self.mode = Mode::Normal;
+                        self.message = Some(format!("Saved {}", element.title));
+                        if let Some(index) = self.list.iter().position(|e| e.id == element.id) {
+                            self.select(index);
+                        }
+                    }
+                    // keep the form open so nothing typed is lost
+                    Err(ex) => {
+                        form.error = Some(ex.to_string());
+                        self.mode = Mode::Edit(id, form);
+                    }
+                }
+            }
+        }
+    }
+
+    fn select(&mut self, index: usize) {
+        if !self.list.is_empty() {
+            self.table.select(Some(index.min(self.list.len() - 1)));
+        }
+    }
+
+    fn move_by(&mut self, rows: isize) {
+        let index = self.table.selected().unwrap_or(0);
+        self.select(index.saturating_add_signed(rows));
+    }
+
+    fn update_selected(&mut self, update: impl FnOnce(&ModelManager, i32) -> Result<ListElement, Error>) {
+        let Some(id) = self.selected().map(|element| element.id) else { return };
+
+        self.error = update(&self.mm, id).and_then(|_| self.refresh()).err().map(|ex| ex.to_string());
+    }
+
+    // step the db's undo stack back, or forward again with redo
+    fn undo(&mut self, redo: bool) {
+        let result = match redo {
+            true => UndoMAC::redo(&self.mm),
+            false => UndoMAC::undo(&self.mm),
+        };
+
+        match result.and_then(|operation| self.refresh().map(|_| operation)) {
+            Ok(Some(operation)) => self.message = Some(format!("{} {}", if redo { "Redid" } else { "Undid" }, operation)),
+            Ok(None) => self.message = Some(format!("Nothing to {}", if redo { "redo" } else { "undo" })),
+            Err(ex) => self.error = Some(ex.to_string()),
+        }
+    }
+
+    // one step up or down the score scale, stepping below the lowest value clears the score
+    fn nudge_score(&mut self, up: bool) {
+        let scale = self.scale;
+        let Some(current) = self.selected().map(|element| element.score.map(|score| scale.from_score(score))) else { return };
+
+        // a tenth of the scale at a time on the 100 point scale
+        let step = (scale.max() / 10).max(1);
+        let value = match (current, up) {
+            (None, true) => Some(1),
+            (None, false) => return,
+            (Some(value), true) => Some((value + step).min(scale.max())),
+            (Some(value), false) => Some(value - step).filter(|value| *value >= 1),
+        };
+
+        self.update_selected(|mm, id| UndoMAC::set_score(mm, id, value, scale));
+    }
+}
+
+// the item after (or before) `current` in `items`, wrapping around
+pub fn step<T: PartialEq + Copy>(items: &[T], current: T, forward: bool) -> T {
+    let index = items.iter().position(|item| *item == current).unwrap_or(0);
+    let len = items.len();
+
+    match forward {
+        true => items[(index + 1) % len],
+        false => items[(index + len - 1) % len],
+    }
+}
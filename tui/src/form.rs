@@ -0,0 +1,141 @@
+use backend::model::{ListElement, ListElementPatch, ListElementStatus};
+use ratatui::crossterm::event::{KeyCode, KeyEvent};
+use strum::IntoEnumIterator;
+
+use crate::app::step;
+
+pub const FIELDS: [&str; 6] = ["Title", "Notes", "Tags", "Status", "Episodes watched", "Episodes total"];
+const STATUS_FIELD: usize = 3;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FormAction {
+    None,
+    Save,
+    Cancel,
+}
+
+// Text backed form for creating or editing one list element, one field focused at a time
+#[derive(Debug, Clone, Default)]
+pub struct ListElementForm {
+    pub title: String,
+    pub notes: String,
+    pub tags: String,
+    pub status: ListElementStatus,
+    pub episodes_watched: String,
+    pub episodes_total: String,
+    pub focus: usize,
+    pub error: Option<String>,
+}
+
+impl ListElementForm {
+
+    pub fn new() -> Self {
+        Self {
+            episodes_watched: "0".to_string(),
+            ..Default::default()
+        }
+    }
+
+    pub fn from_element(le: &ListElement) -> Self {
+        Self {
+            title: le.title.clone(),
+            notes: le.notes.clone().unwrap_or_default(),
+            tags: le.tags.clone().unwrap_or_default(),
+            status: le.status,
+            episodes_watched: le.episodes_watched.to_string(),
+            episodes_total: le.episodes_total.map(|total| total.to_string()).unwrap_or_default(),
+            ..Default::default()
+        }
+    }
+    // validate the fields, the form holds the whole element so an emptied notes or total clears it
+    // validate the fields, an empty episode total leaves the total unknown
+    pub fn to_patch(&self) -> Result<ListElementPatch, String> {
+        let title = self.title.trim();
+        if title.is_empty() {
+            return Err("Title is required".to_string());
+        }
+
+        let episodes_watched = parse_episodes("Episodes watched", &self.episodes_watched)?;
+        let episodes_total = parse_episodes("Episodes total", &self.episodes_total)?;
+
+        if let (Some(watched), Some(total)) = (episodes_watched, episodes_total) {
+            if watched > total {
+                return Err(format!("Episodes watched ({}) is more than the total ({})", watched, total));
+            }
+        }
+
+        Ok(ListElementPatch {
+            title: Some(title.to_string()),
+            notes: Some(Some(self.notes.trim().to_string()).filter(|notes| !notes.is_empty())),
+            tags: Some(self.tags.trim().to_string()),
+            status: Some(self.status),
+            episodes_watched,
+            episodes_total: Some(episodes_total),
+            ..Default::default()
+        })
+    }
+
+    // shown value of the field at `index`, in FIELDS order
+    pub fn value(&self, index: usize) -> String {
+        match index {
+            0 => self.title.clone(),
+            1 => self.notes.clone(),
+            2 => self.tags.clone(),
+            STATUS_FIELD => format!("< {} >", self.status),
+            4 => self.episodes_watched.clone(),
+            _ => self.episodes_total.clone(),
+        }
+    }
+
+    pub fn is_text(index: usize) -> bool {
+        index != STATUS_FIELD
+    }
+
+    fn text_mut(&mut self) -> Option<&mut String> {
+        match self.focus {
+            0 => Some(&mut self.title),
+            1 => Some(&mut self.notes),
+            2 => Some(&mut self.tags),
+            4 => Some(&mut self.episodes_watched),
+            5 => Some(&mut self.episodes_total),
+            _ => None,
+        }
+    }
+
+    // typing goes to the focused field, Tab/arrows move between fields
+    pub fn handle_key(&mut self, key: KeyEvent) -> FormAction {
+        match key.code {
+            KeyCode::Enter => return FormAction::Save,
+            KeyCode::Esc => return FormAction::Cancel,
+            KeyCode::Tab | KeyCode::Down => self.focus = (self.focus + 1) % FIELDS.len(),
+            KeyCode::BackTab | KeyCode::Up => self.focus = (self.focus + FIELDS.len() - 1) % FIELDS.len(),
+            KeyCode::Left if self.focus == STATUS_FIELD => self.status = step(&ListElementStatus::iter().collect::<Vec<_>>(), self.status, false),
+            KeyCode::Right | KeyCode::Char(' ') if self.focus == STATUS_FIELD => self.status = step(&ListElementStatus::iter().collect::<Vec<_>>(), self.status, true),
+            KeyCode::Backspace => {
+                if let Some(text) = self.text_mut() {
+                    text.pop();
+                }
+            }
+            KeyCode::Char(c) => {
+                if let Some(text) = self.text_mut() {
+                    text.push(c);
+                }
+            }
+            _ => (),
+        }
+
+        FormAction::None
+    }
+}
+
+fn parse_episodes(field: &str, value: &str) -> Result<Option<i32>, String> {
+    let value = value.trim();
+    if value.is_empty() {
+        return Ok(None);
+    }
+
+    match value.parse::<i32>() {
+        Ok(episodes) if episodes >= 0 => Ok(Some(episodes)),
+        _ => Err(format!("{} must be a whole number, got {}", field, value)),
+    }
+}
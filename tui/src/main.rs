@@ -0,0 +1,68 @@
+mod app;
+mod form;
+mod ui;
+
+use backend::model::{Config, Error, ListElementMAC, ModelManager, ScoreScale};
+use clap::Parser;
+use ratatui::{crossterm::event::{self, Event, KeyEventKind}, DefaultTerminal};
+use std::process::ExitCode;
+
+use app::App;
+
+#[derive(Debug, Parser)]
+#[command(name = "the-list-tui", about = "Browse and edit the list in the terminal", version)]
+pub struct Cli {
+    /// SQLite file holding the list, created and migrated on first use.
+    /// THE_LIST_DB, then the config file, then the data directory when left out
+    #[arg(long)]
+    pub db: Option<String>,
+
+    /// Scale scores are entered and shown in, the one picked in the GUI when left out
+    #[arg(long)]
+    pub scale: Option<ScoreScale>,
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+
+    let result = Config::load()
+        .and_then(|config| {
+            let (db, _) = config.resolve_db(cli.db.as_deref())?;
+            let mm = ModelManager::open(&db)?.with_source("tui");
+            ListElementMAC::purge_expired(&mm, config.trash_retention())?;
+            Ok((mm, cli.scale.unwrap_or(config.score_scale)))
+        })
+        .and_then(|(mm, scale)| App::new(mm, scale))
+        .and_then(run);
+
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(ex) => {
+            eprintln!("the-list-tui: {}", ex);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+// the terminal is given back even when the loop fails
+fn run(mut app: App) -> Result<(), Error> {
+    let mut terminal = ratatui::try_init()?;
+    let result = event_loop(&mut terminal, &mut app);
+    ratatui::try_restore()?;
+
+    result
+}
+
+fn event_loop(terminal: &mut DefaultTerminal, app: &mut App) -> Result<(), Error> {
+    while !app.quit {
+        terminal.draw(|frame| ui::draw(frame, app))?;
+
+        if let Event::Key(key) = event::read()? {
+            if key.kind == KeyEventKind::Press {
+                app.handle_key(key);
+            }
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,236 @@
+use backend::model::{ListElement, ListElementStatus, TIMESTAMP_FORMAT};
+use chrono::NaiveDateTime;
+use ratatui::{
+    layout::{Constraint, Layout, Rect},
+    style::{Color, Modifier, Style, Stylize},
+    text::{Line, Span},
+    widgets::{Block, Cell, Clear, Paragraph, Row, Table, Tabs, Wrap},
+    Frame,
+};
+use strum::IntoEnumIterator;
+
+use crate::app::{App, Mode};
+use crate::form::{ListElementForm, FIELDS};
+
+const HELP: [(&str, &str); 19] = [
+    ("j / k", "down / up"),
+    ("gg / G", "first / last"),
+    ("ctrl-d / ctrl-u", "half a page down / up"),
+    ("/", "search titles"),
+    ("s / S", "next / previous status tab"),
+    ("t", "toggle a tag filter, Tab completes"),
+    ("T", "clear the tag filters"),
+    ("Esc", "clear every filter"),
+    ("a", "add an element"),
+    ("e / Enter", "edit the element"),
+    ("dd", "delete the element"),
+    ("+ / -", "one episode more / less"),
+    ("] / [", "score up / down"),
+    ("x", "clear the score"),
+    ("u", "undo"),
+    ("ctrl-r", "redo"),
+    (":<n>", "go to row n"),
+    (":q / q", "quit"),
+    ("?", "this help"),
+];
+
+pub fn draw(frame: &mut Frame, app: &mut App) {
+    let [filters, list, details, status_line] = Layout::vertical([
+        Constraint::Length(4),
+        Constraint::Fill(1),
+        Constraint::Length(6),
+        Constraint::Length(1),
+    ]).areas(frame.area());
+
+    draw_filters(frame, app, filters);
+    draw_list(frame, app, list);
+    draw_details(frame, app, details);
+    draw_status_line(frame, app, status_line);
+
+    match &app.mode {
+        Mode::Edit(id, form) => draw_form(frame, form, id.is_some()),
+        Mode::Help => draw_help(frame),
+        _ => (),
+    }
+}
+
+// status tabs, the search and the active tags
+fn draw_filters(frame: &mut Frame, app: &App, area: Rect) {
+    let block = Block::bordered().title(" Filter ");
+    let [tabs, query] = Layout::vertical([Constraint::Length(1), Constraint::Length(1)]).areas(block.inner(area));
+    frame.render_widget(block, area);
+
+    let titles = std::iter::once("All".to_string()).chain(ListElementStatus::iter().map(|status| status.to_string()));
+    let selected = match app.filter.status {
+        Some(status) => ListElementStatus::iter().position(|s| s == status).map_or(0, |index| index + 1),
+        None => 0,
+    };
+    frame.render_widget(Tabs::new(titles).select(selected).highlight_style(Style::new().reversed()), tabs);
+
+    let mut spans = vec![Span::raw("Search: "), Span::raw(app.filter.query.clone()).bold()];
+    if !app.filter.tags.is_empty() {
+        spans.push(Span::raw("   Tags: "));
+        for tag in &app.filter.tags {
+            spans.push(Span::raw(format!(" {} ", tag)).reversed());
+            spans.push(Span::raw(" "));
+        }
+    }
+    frame.render_widget(Paragraph::new(Line::from(spans)), query);
+}
+
+fn draw_list(frame: &mut Frame, app: &mut App, area: Rect) {
+    let header = Row::new(["Title", "Status", "Episodes", "Score", "Tags", "Modified"]).bold();
+
+    let rows = app.list.iter().map(|element| {
+        let tags = element.tags.as_deref().unwrap_or_default().split_whitespace().map(|tag| {
+            // tags in the filter stand out
+            match app.filter.tags.iter().any(|t| t.eq_ignore_ascii_case(tag)) {
+                true => Span::raw(tag.to_string()).reversed(),
+                false => Span::raw(tag.to_string()).fg(Color::Cyan),
+            }
+        });
+        let tags = Line::from(tags.flat_map(|tag| [tag, Span::raw(" ")]).collect::<Vec<_>>());
+
+        Row::new([
+            Cell::from(element.title.clone()),
+            Cell::from(element.status.to_string()),
+            Cell::from(progress(element)),
+            Cell::from(element.score.map(|score| app.scale.format(score)).unwrap_or_default()),
+            Cell::from(tags),
+            Cell::from(format_time(element.mtime.as_deref().unwrap_or(&element.ctime))),
+        ])
+    });
+
+    let widths = [
+        Constraint::Fill(3),
+        Constraint::Length(13),
+        Constraint::Length(9),
+        Constraint::Length(9),
+        Constraint::Fill(2),
+        Constraint::Length(16),
+    ];
+
+    let title = format!(" The List ({}) ", app.list.len());
+    let table = Table::new(rows, widths)
+        .header(header)
+        .block(Block::bordered().title(title))
+        .row_highlight_style(Style::new().add_modifier(Modifier::REVERSED))
+        .highlight_symbol("> ");
+
+    frame.render_stateful_widget(table, area, &mut app.table);
+}
+
+// notes and dates of the selected element
+fn draw_details(frame: &mut Frame, app: &App, area: Rect) {
+    let block = Block::bordered().title(" Details ");
+
+    let lines = match app.selected() {
+        Some(element) => vec![
+            Line::from(vec![Span::raw(element.title.clone()).bold()]),
+            Line::from(format!(
+                "Created At: {}   Last Modified At: {}",
+                format_time(&element.ctime),
+                format_time(element.mtime.as_deref().unwrap_or(&element.ctime)),
+            )),
+            Line::from(element.notes.clone().unwrap_or_default()).italic(),
+        ],
+        None => vec![Line::from("Nothing here, a adds an element").italic()],
+    };
+
+    frame.render_widget(Paragraph::new(lines).wrap(Wrap { trim: true }).block(block), area);
+}
+
+// the prompt being typed, otherwise the last error or message
+fn draw_status_line(frame: &mut Frame, app: &App, area: Rect) {
+    let line = match &app.mode {
+        Mode::Search => prompt(frame, area, "/", &app.filter.query),
+        Mode::Command(command) => prompt(frame, area, ":", command),
+        Mode::Tag(tag) => prompt(frame, area, "tag: ", tag),
+        Mode::ConfirmDelete(id) => {
+            let title = app.list.iter().find(|element| element.id == *id).map(|element| element.title.as_str()).unwrap_or_default();
+            Line::from(format!("Delete {}? y/n", title)).fg(Color::Yellow)
+        }
+        _ => match (&app.error, &app.message) {
+            (Some(error), _) => Line::from(error.clone()).fg(Color::Red),
+            (None, Some(message)) => Line::from(message.clone()),
+            (None, None) => Line::from(format!("{} scale   ? help", app.scale)).dark_gray(),
+        },
+    };
+
+    frame.render_widget(Paragraph::new(line), area);
+}
+
+fn prompt(frame: &mut Frame, area: Rect, label: &str, text: &str) -> Line<'static> {
+    let width = (label.chars().count() + text.chars().count()) as u16;
+    frame.set_cursor_position((area.x + width.min(area.width.saturating_sub(1)), area.y));
+
+    Line::from(format!("{}{}", label, text))
+}
+
+fn draw_form(frame: &mut Frame, form: &ListElementForm, editing: bool) {
+    let area = centered(frame.area(), 60, FIELDS.len() as u16 + 6);
+    let title = if editing { " Edit Element " } else { " Add Element " };
+    let block = Block::bordered().title(title);
+    let inner = block.inner(area);
+
+    frame.render_widget(Clear, area);
+    frame.render_widget(block, area);
+
+    let mut lines: Vec<Line> = FIELDS.iter().enumerate().map(|(index, name)| {
+        let label = Span::raw(format!("{:>17}: ", name));
+        let value = Span::raw(form.value(index));
+
+        match index == form.focus {
+            true => Line::from(vec![label.bold(), value.underlined()]),
+            false => Line::from(vec![label, value]),
+        }
+    }).collect();
+
+    lines.push(Line::from(""));
+    lines.push(match &form.error {
+        Some(error) => Line::from(error.clone()).fg(Color::Red),
+        None => Line::from(""),
+    });
+    lines.push(Line::from("Tab next field   \u{2190}/\u{2192} status   Enter save   Esc cancel").dark_gray());
+
+    frame.render_widget(Paragraph::new(lines), inner);
+
+    // cursor at the end of the focused text
+    if ListElementForm::is_text(form.focus) {
+        let x = inner.x + 19 + form.value(form.focus).chars().count() as u16;
+        frame.set_cursor_position((x.min(inner.right().saturating_sub(1)), inner.y + form.focus as u16));
+    }
+}
+
+fn draw_help(frame: &mut Frame) {
+    let area = centered(frame.area(), 56, HELP.len() as u16 + 2);
+
+    let rows = HELP.iter().map(|(keys, action)| Row::new([Cell::from(*keys).bold(), Cell::from(*action)]));
+    let table = Table::new(rows, [Constraint::Length(16), Constraint::Fill(1)])
+        .block(Block::bordered().title(" Keys, any key closes "));
+
+    frame.render_widget(Clear, area);
+    frame.render_widget(table, area);
+}
+
+fn centered(area: Rect, width: u16, height: u16) -> Rect {
+    let width = width.min(area.width);
+    let height = height.min(area.height);
+
+    Rect::new(area.x + (area.width - width) / 2, area.y + (area.height - height) / 2, width, height)
+}
+
+fn progress(element: &ListElement) -> String {
+    match element.episodes_total {
+        Some(total) => format!("{} / {}", element.episodes_watched, total),
+        None => format!("{} / ?", element.episodes_watched),
+    }
+}
+
+// same format the GUI shows, the raw value if it doesn't parse
+fn format_time(time: &str) -> String {
+    match NaiveDateTime::parse_from_str(time, TIMESTAMP_FORMAT) {
+        Ok(time) => time.format("%H:%M %d-%m-%Y").to_string(),
+        Err(_) => time.to_string(),
+    }
+}